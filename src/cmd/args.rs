@@ -1,7 +1,10 @@
 use clap::{ArgAction, Command, CommandFactory, Parser, Subcommand, ValueEnum};
 use clap_complete::{generate, Generator, Shell};
 use gr_bin::formatters::formatter::FormatterType;
-use gr_bin::vcs::common::RepositoryVisibility;
+use gr_bin::vcs::common::{
+    ArchiveFormat, CollaboratorPermission, RepositoryVisibility, WebhookContentType, WebhookEvent,
+};
+use serde::{Deserialize, Serialize};
 use std::io;
 use std::process;
 
@@ -27,6 +30,16 @@ pub enum UserFilter {
     All,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum IssueFilter {
+    /// Show only open issues (default)
+    Open,
+    /// Show only closed issues
+    Closed,
+    /// Show all issues
+    All,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Default)]
 pub enum OutputType {
     /// Print output in a human-readable way (default)
@@ -66,6 +79,102 @@ impl From<Visibility> for RepositoryVisibility {
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Default)]
+pub enum Permission {
+    /// Can read and clone the repository
+    Read,
+    /// Can push to the repository (default)
+    #[default]
+    Write,
+    /// Can change the settings of the repository
+    Admin,
+}
+
+impl From<Permission> for CollaboratorPermission {
+    fn from(val: Permission) -> Self {
+        match val {
+            Permission::Read => CollaboratorPermission::Read,
+            Permission::Write => CollaboratorPermission::Write,
+            Permission::Admin => CollaboratorPermission::Admin,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum HookEvent {
+    /// Commits pushed to a branch
+    Push,
+    /// Pull requests opened, updated or closed
+    PullRequest,
+    /// Tags created
+    Tag,
+    /// Releases published (GitHub, GitLab and Gitea only)
+    Release,
+    /// Issues opened or updated
+    Issue,
+}
+
+impl From<HookEvent> for WebhookEvent {
+    fn from(val: HookEvent) -> Self {
+        match val {
+            HookEvent::Push => WebhookEvent::Push,
+            HookEvent::PullRequest => WebhookEvent::PullRequest,
+            HookEvent::Tag => WebhookEvent::Tag,
+            HookEvent::Release => WebhookEvent::Release,
+            HookEvent::Issue => WebhookEvent::Issue,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Default)]
+pub enum HookContentType {
+    /// Send the payload as JSON (default)
+    #[default]
+    Json,
+    /// Send the payload as a form (GitHub and Gitea only)
+    Form,
+}
+
+impl From<HookContentType> for WebhookContentType {
+    fn from(val: HookContentType) -> Self {
+        match val {
+            HookContentType::Json => WebhookContentType::Json,
+            HookContentType::Form => WebhookContentType::Form,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Default)]
+pub enum ArchiveType {
+    /// Gzipped tarball (default)
+    #[default]
+    #[value(name = "tar.gz")]
+    TarGz,
+    /// Zip file
+    Zip,
+}
+
+impl From<ArchiveType> for ArchiveFormat {
+    fn from(val: ArchiveType) -> Self {
+        match val {
+            ArchiveType::TarGz => ArchiveFormat::TarGz,
+            ArchiveType::Zip => ArchiveFormat::Zip,
+        }
+    }
+}
+
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Protocol {
+    /// Use SSH URLs, fall back to HTTPS (default)
+    #[default]
+    Ssh,
+    /// Use HTTPS URLs, fall back to SSH
+    Https,
+}
+
 #[derive(Debug, Subcommand, Clone)]
 #[command(after_help = "Examples:
 
@@ -87,9 +196,16 @@ $ gr pr create -m 'Do things'
 Create a pull request with a title and a description:
 $ gr pr create -m 'Do things' -d 'Do things and stuff'
 
+The default description is the changelog of the commits, grouped by Conventional Commit types.
 Description can be provided from standard input (for example git-cliff):
 $ git-cliff --tag PR --strip all | gr pr create -m 'Do things'
 
+Use a named template of the repository (for example .github/PULL_REQUEST_TEMPLATE/bug.md):
+$ gr pr create -m 'Fix things' --template bug
+
+Write the title and the description in the editor:
+$ gr pr create --edit
+
 Create a pull request to merge into a different branch:
 $ gr pr create -m 'Do things' --target staging
 
@@ -97,13 +213,13 @@ Create a pull request and merge it immediately (good for fix branches):
 $ gr pr create -m 'Fix things' --merge --target staging --delete")]
     /// Create pull request for the current branch
     ///
-    /// The only required field is the title (--message / -m), other fields will be filled by sane defaults:
-    /// the description will be the list of commits, the target branch is the default branch.
+    /// The only required field is the title (--message / -m, or written in the editor), other fields will be filled by sane defaults:
+    /// the description will be the changelog of the commits, the target branch is the default branch.
     Create {
-        /// The title of the pull request
+        /// The title of the pull request (default: edit it in the editor in a terminal)
         #[arg(short, long)]
-        message: String,
-        /// The description of the pull request (default: stdin, or the list of commits)
+        message: Option<String>,
+        /// The description of the pull request (default: stdin, or the changelog of the commits)
         #[arg(short, long)]
         description: Option<String>,
         /// Change the target branch (default: the default branch in the repo)
@@ -124,6 +240,12 @@ $ gr pr create -m 'Fix things' --merge --target staging --delete")]
         /// Force the merge, even if there are local or remote changes (not recommended)
         #[arg(long)]
         force_merge: bool,
+        /// Use this pull request template of the repository as the description (default: the default template)
+        #[arg(long, conflicts_with = "description")]
+        template: Option<String>,
+        /// Edit the title and the description in the editor ($GIT_EDITOR or $EDITOR)
+        #[arg(short, long)]
+        edit: bool,
     },
     #[command(after_help = "Examples:
 
@@ -189,6 +311,23 @@ $ gr pr merge")]
     },
     #[command(after_help = "Examples:
 
+Merge the target branch into the pull request branch:
+$ gr pr update-branch
+
+Rebase the pull request branch on the target branch:
+$ gr pr update-branch --rebase
+")]
+    /// Update the pull request branch with the changes of the target branch
+    ///
+    /// The forge API is used if it can, otherwise the branch is updated locally and pushed
+    /// with --force-with-lease.
+    UpdateBranch {
+        /// Rebase the branch on the target, instead of merging the target into it
+        #[arg(long)]
+        rebase: bool,
+    },
+    #[command(after_help = "Examples:
+
 Decline the pull request:
 $ gr pr decline")]
     /// Close (decline) the pull request for the current branch
@@ -229,7 +368,7 @@ $ gr repo new new-repo --init --default-branch develop --gitignore Rust --licens
 ")]
     /// Create new repository
     New {
-        /// The name of the new repository, can be either: a full URL (e.g. "https://github.com/user/gr.git"), an organization and repo name, (e.g. "user/gr") or a repo name (will be created under user) e.g. "gr".
+        /// The name of the new repository, can be either: a full URL (e.g. "https://github.com/user/gr.git"), an organization and repo name, (e.g. "user/gr" or with GitLab subgroups "group/subgroup/gr") or a repo name (will be created under user) e.g. "gr".
         repository: String,
         /// The host of the server (e.g. "github.com")
         #[arg(long)]
@@ -269,16 +408,95 @@ $ gr repo fork https://github.com/daniel7grant/gr gr_forked
 
 Fork an existing repository to a different organization:
 $ gr repo fork https://github.com/daniel7grant/gr organization/gr
+
+Fork and clone with HTTPS, adding the original as the source remote:
+$ gr repo fork https://github.com/daniel7grant/gr --clone --protocol https --remote-name source
 ")]
     /// Fork existing repository
     Fork {
         /// The source repository to fork from
         source: String,
-        /// The target name, e.g. "name" or "org/name" (by default the same name to the current user)
+        /// The target name, e.g. "name", "org/name" or "group/subgroup/name" (by default the same name to the current user)
         repository: Option<String>,
         /// Whether to clone the forked repository
         #[arg(long)]
         clone: bool,
+        /// The protocol to clone with and to add the original repository as a remote (default: protocol in configuration or ssh)
+        #[arg(long)]
+        protocol: Option<Protocol>,
+        /// The name of the remote for the original repository
+        #[arg(long, default_value = "upstream")]
+        remote_name: String,
+    },
+    #[command(after_help = "Examples:
+
+Clone a repository of the current user:
+$ gr repo clone gr
+
+Clone a repository of an organization from a specific host:
+$ gr repo clone daniel7grant/gr --host github.com
+
+Clone a repository with HTTPS into a different directory:
+$ gr repo clone daniel7grant/gr --protocol https --dir path/to/another
+")]
+    /// Clone a repository by its name
+    Clone {
+        /// The repository to clone, can be either: a full URL, an organization and repo name (e.g. "user/gr" or "group/subgroup/gr") or a repo name of the current user (e.g. "gr")
+        repository: String,
+        /// The host of the server (e.g. "github.com")
+        #[arg(long)]
+        host: Option<String>,
+        /// The protocol to clone with (default: protocol in configuration or ssh)
+        #[arg(long)]
+        protocol: Option<Protocol>,
+        /// The name of the remote for the original repository, if it is a fork
+        #[arg(long, default_value = "upstream")]
+        remote_name: String,
+    },
+    #[command(after_help = "Examples:
+
+List your repositories:
+$ gr repo list
+
+List the private repositories of an organization:
+$ gr repo list --org organization --visibility private
+
+List the archived repositories on a specific host:
+$ gr repo list --archived --host github.com
+")]
+    /// List the repositories of a user or an organization
+    List {
+        /// The user, organization or group to list the repositories of (default: the current user)
+        #[arg(long)]
+        org: Option<String>,
+        /// Show only archived repositories
+        #[arg(long)]
+        archived: bool,
+        /// Show only repositories with this visibility
+        #[arg(long)]
+        visibility: Option<Visibility>,
+        /// The host of the server (default: the host of the current repository)
+        #[arg(long)]
+        host: Option<String>,
+    },
+    #[command(after_help = "Examples:
+
+Search for repositories:
+$ gr repo search gr
+
+Search for more repositories on a specific host:
+$ gr repo search gr --limit 100 --host github.com
+")]
+    /// Search for repositories
+    Search {
+        /// The text to search for
+        query: String,
+        /// The maximum number of repositories to show
+        #[arg(long, default_value = "30")]
+        limit: usize,
+        /// The host of the server (default: the host of the current repository)
+        #[arg(long)]
+        host: Option<String>,
     },
     #[command(after_help = "Examples:
 
@@ -301,6 +519,168 @@ $ gr repo open
 ")]
     /// Open the repository in the browser
     Open {},
+    #[command(after_help = "Examples:
+
+Change the description and the homepage of the repository:
+$ gr repo edit --description 'Interact with remote repositories' --homepage https://example.com
+
+Make the repository public and set its topics:
+$ gr repo edit --visibility public --topics rust,cli
+
+Allow only squash merges:
+$ gr repo edit --allow-merge-commit false --allow-rebase-merge false --allow-squash-merge true
+")]
+    /// Change the settings of the repository
+    Edit {
+        /// The new name of the repository
+        #[arg(long)]
+        name: Option<String>,
+        /// The new description of the repository
+        #[arg(short, long)]
+        description: Option<String>,
+        /// The new visibility of the repository
+        #[arg(long)]
+        visibility: Option<Visibility>,
+        /// The new default branch of the repository
+        #[arg(long)]
+        default_branch: Option<String>,
+        /// The homepage of the repository (GitHub, Gitea and Bitbucket only)
+        #[arg(long)]
+        homepage: Option<String>,
+        /// The comma-separated list of topics, replacing the existing ones (GitHub, GitLab and Gitea only)
+        #[arg(long, value_delimiter = ',')]
+        topics: Option<Vec<String>>,
+        /// Whether the repository is archived (GitHub, GitLab and Gitea only)
+        #[arg(long)]
+        archived: Option<bool>,
+        /// Whether merge commits are allowed (GitHub, GitLab and Gitea only)
+        #[arg(long)]
+        allow_merge_commit: Option<bool>,
+        /// Whether squash merges are allowed (GitHub, GitLab and Gitea only)
+        #[arg(long)]
+        allow_squash_merge: Option<bool>,
+        /// Whether rebase merges are allowed (GitHub, GitLab and Gitea only)
+        #[arg(long)]
+        allow_rebase_merge: Option<bool>,
+    },
+    #[command(after_help = "Examples:
+
+Rename the repository, updating the local remote:
+$ gr repo rename new-name
+")]
+    /// Rename the repository and update the local remote
+    Rename {
+        /// The new name of the repository (without the owner)
+        name: String,
+    },
+    #[command(after_help = "Examples:
+
+Transfer the repository to an organization, updating the local remote:
+$ gr repo transfer organization
+
+Transfer the repository to a GitLab subgroup:
+$ gr repo transfer group/subgroup
+")]
+    /// Transfer the repository to another user or organization and update the local remote (GitHub, GitLab and Gitea only)
+    Transfer {
+        /// The user, organization or group to transfer the repository to
+        owner: String,
+    },
+    #[command(after_help = "Examples:
+
+Require two approvals and passing CI on the main branch:
+$ gr repo protect main --required-approvals 2 --required-status-checks ci,lint
+
+Require linear history, but allow force pushes:
+$ gr repo protect develop --linear-history --allow-force-push
+")]
+    /// Protect a branch, replacing its existing protection rules
+    Protect {
+        /// The branch to protect
+        branch: String,
+        /// The number of approvals needed to merge (GitHub, Gitea and Bitbucket only)
+        #[arg(long, default_value = "0")]
+        required_approvals: u32,
+        /// The comma-separated list of status checks that have to pass to merge (GitHub and Gitea only)
+        #[arg(long, value_delimiter = ',')]
+        required_status_checks: Vec<String>,
+        /// Allow force pushing to the branch
+        #[arg(long)]
+        allow_force_push: bool,
+        /// Require linear history, blocking merge commits (GitHub only)
+        #[arg(long)]
+        linear_history: bool,
+    },
+    #[command(after_help = "Examples:
+
+List the protected branches of the repository:
+$ gr repo protections
+")]
+    /// List the branch protection rules of the repository
+    Protections {},
+    /// List, add and remove the collaborators of the repository
+    #[command(subcommand)]
+    Collaborators(CollaboratorCommands),
+    /// List, create, delete and test the webhooks of the repository
+    #[command(subcommand)]
+    Hooks(HookCommands),
+    /// List, add and remove the deploy keys of the repository
+    #[command(subcommand)]
+    DeployKeys(DeployKeyCommands),
+    #[command(after_help = "Examples:
+
+Archive the repository:
+$ gr repo archive
+")]
+    /// Archive the repository, making it read-only
+    Archive {},
+    #[command(after_help = "Examples:
+
+Unarchive the repository:
+$ gr repo unarchive
+")]
+    /// Unarchive the repository
+    Unarchive {},
+    #[command(after_help = "Examples:
+
+Download the default branch of the repository:
+$ gr repo download-archive
+
+Download a tag as a zip file:
+$ gr repo download-archive --ref v1.2 --format zip
+
+Extract a tag without saving the archive:
+$ gr repo download-archive --ref v1.2 --file - | tar xz
+")]
+    /// Download an archive of the repository (GitHub, GitLab, Gitea and Bitbucket only)
+    DownloadArchive {
+        /// The branch, tag or commit to download (default: the default branch)
+        #[arg(long = "ref")]
+        reference: Option<String>,
+        /// The format of the archive
+        #[arg(long, value_enum, default_value_t)]
+        format: ArchiveType,
+        /// The file to save the archive to, or - for the standard output (default: <name>-<ref>.<format>)
+        #[arg(long)]
+        file: Option<String>,
+    },
+    #[command(after_help = "Examples:
+
+Sync the default branch of the fork with the upstream:
+$ gr repo sync
+
+Sync a different branch of the fork:
+$ gr repo sync --branch develop
+
+Reset the fork to the upstream, dropping the commits of the fork:
+$ gr repo sync --force
+")]
+    /// Sync the fork with its upstream repository
+    Sync {
+        /// Reset the branch to the upstream if they have diverged
+        #[arg(long)]
+        force: bool,
+    },
     #[command(
         hide = true,
         after_help = "Examples:
@@ -317,6 +697,525 @@ $ gr repo delete
     },
 }
 
+#[derive(Debug, Subcommand, Clone)]
+#[command(after_help = "Examples:
+
+List the collaborators of the repository:
+$ gr repo collaborators list
+
+Give write access to a user:
+$ gr repo collaborators add username
+")]
+pub enum CollaboratorCommands {
+    #[command(after_help = "Examples:
+
+List the collaborators of the repository:
+$ gr repo collaborators list
+")]
+    /// List the collaborators and their permissions
+    List {},
+    #[command(after_help = "Examples:
+
+Give write access to a user:
+$ gr repo collaborators add username
+
+Give read-only access to a user:
+$ gr repo collaborators add username --permission read
+")]
+    /// Add a collaborator or change their permission
+    Add {
+        /// The username of the collaborator
+        username: String,
+        /// The permission to give
+        #[arg(long, default_value = "write")]
+        permission: Permission,
+    },
+    #[command(after_help = "Examples:
+
+Remove the access of a user:
+$ gr repo collaborators remove username
+")]
+    /// Remove a collaborator
+    Remove {
+        /// The username of the collaborator
+        username: String,
+    },
+}
+
+#[derive(Debug, Subcommand, Clone)]
+#[command(after_help = "Examples:
+
+List the webhooks of the repository:
+$ gr repo hooks list
+
+Create a webhook for pushes and pull requests:
+$ gr repo hooks create https://example.com/hook --events push,pull-request
+")]
+pub enum HookCommands {
+    #[command(after_help = "Examples:
+
+List the webhooks of the repository:
+$ gr repo hooks list
+
+List the webhooks as JSON:
+$ gr repo hooks list --output json
+")]
+    /// List the webhooks
+    List {},
+    #[command(after_help = "Examples:
+
+Create a webhook for pushes:
+$ gr repo hooks create https://example.com/hook
+
+Create a webhook with a secret for releases and tags:
+$ gr repo hooks create https://example.com/hook --secret s3cr3t --events release,tag
+")]
+    /// Create a webhook
+    Create {
+        /// The URL to send the events to
+        url: String,
+        /// The secret to sign the payloads with
+        #[arg(long)]
+        secret: Option<String>,
+        /// The format of the payload
+        #[arg(long, default_value = "json")]
+        content_type: HookContentType,
+        /// The comma-separated list of events to send
+        #[arg(long, value_delimiter = ',', default_value = "push")]
+        events: Vec<HookEvent>,
+    },
+    #[command(after_help = "Examples:
+
+Delete a webhook:
+$ gr repo hooks delete 123
+")]
+    /// Delete a webhook
+    Delete {
+        /// The id of the webhook (see gr repo hooks list)
+        id: String,
+    },
+    #[command(after_help = "Examples:
+
+Send a test event to a webhook:
+$ gr repo hooks test 123
+")]
+    /// Send a test event to a webhook (GitHub, GitLab and Gitea only)
+    Test {
+        /// The id of the webhook (see gr repo hooks list)
+        id: String,
+    },
+}
+
+#[derive(Debug, Subcommand, Clone)]
+#[command(after_help = "Examples:
+
+List the deploy keys of the repository:
+$ gr repo deploy-keys list
+
+Add a read-only deploy key:
+$ gr repo deploy-keys add ~/.ssh/id_ed25519.pub
+")]
+pub enum DeployKeyCommands {
+    #[command(after_help = "Examples:
+
+List the deploy keys of the repository:
+$ gr repo deploy-keys list
+")]
+    /// List the deploy keys
+    List {},
+    #[command(after_help = "Examples:
+
+Add a read-only deploy key from a file:
+$ gr repo deploy-keys add ~/.ssh/id_ed25519.pub
+
+Add a deploy key that can push from the standard input:
+$ cat ci.pub | gr repo deploy-keys add --title ci-runner --read-write
+")]
+    /// Add a deploy key
+    Add {
+        /// The path of the public key (default: read from the standard input)
+        key: Option<String>,
+        /// The title of the key (default: the comment of the key)
+        #[arg(long)]
+        title: Option<String>,
+        /// Allow pushing with the key (GitHub, GitLab and Gitea only)
+        #[arg(long)]
+        read_write: bool,
+    },
+    #[command(after_help = "Examples:
+
+Remove a deploy key:
+$ gr repo deploy-keys remove 123
+")]
+    /// Remove a deploy key
+    Remove {
+        /// The id of the deploy key (see gr repo deploy-keys list)
+        id: String,
+    },
+}
+
+#[derive(Debug, Subcommand, Clone)]
+#[command(after_help = "Examples:
+
+Create a release from a new tag on the current commit:
+$ gr release create v1.0.0 --create-tag
+
+List the releases of the current repository:
+$ gr release list
+")]
+pub enum ReleaseCommands {
+    #[command(after_help = "Examples:
+
+Create a release from an existing tag:
+$ gr release create v1.0.0
+
+Create and push the tag, use the notes from a file and upload the build artifacts:
+$ gr release create v1.0.0 --create-tag --notes-file NOTES.md target/release/gr
+
+Create a release with the changes since the previous tag as notes:
+$ gr release create v1.1.0 --create-tag --generate-notes
+
+Create a prerelease with the notes from the standard input:
+$ git log --oneline v0.9.0.. | gr release create v1.0.0-rc.1 --prerelease --notes-file -
+")]
+    /// Create a release from a tag
+    Create {
+        /// The tag of the release
+        tag: String,
+        /// The commit or branch to create the tag on (default: the current commit with --create-tag)
+        #[arg(long)]
+        target: Option<String>,
+        /// The title of the release (default: the tag)
+        #[arg(long)]
+        name: Option<String>,
+        /// Read the release notes from this file, or from the standard input with -
+        #[arg(long)]
+        notes_file: Option<String>,
+        /// Generate the release notes from the commits since the previous tag
+        #[arg(long, conflicts_with = "notes_file")]
+        generate_notes: bool,
+        /// Create the release as a draft
+        #[arg(long)]
+        draft: bool,
+        /// Mark the release as a prerelease
+        #[arg(long)]
+        prerelease: bool,
+        /// Create the tag locally and push it before creating the release
+        #[arg(long)]
+        create_tag: bool,
+        /// Files to upload to the release
+        assets: Vec<String>,
+    },
+    #[command(after_help = "Examples:
+
+List the releases of the current repository:
+$ gr release list
+")]
+    /// List the releases
+    List {},
+    #[command(after_help = "Examples:
+
+Get the release of a tag:
+$ gr release get v1.0.0
+
+Open the release in the browser:
+$ gr release get v1.0.0 --open
+")]
+    /// Get a release by its tag
+    Get {
+        /// The tag of the release
+        tag: String,
+        /// Open the release in the browser
+        #[arg(long)]
+        open: bool,
+    },
+    #[command(after_help = "Examples:
+
+Delete the release of a tag (the tag itself is kept):
+$ gr release delete v1.0.0
+")]
+    /// Delete a release
+    Delete {
+        /// The tag of the release
+        tag: String,
+    },
+    #[command(after_help = "Examples:
+
+Upload files to an existing release:
+$ gr release upload v1.0.0 gr-linux.tar.gz gr-macos.tar.gz
+")]
+    /// Upload files to a release
+    Upload {
+        /// The tag of the release
+        tag: String,
+        /// Files to upload
+        #[arg(required = true)]
+        files: Vec<String>,
+    },
+    #[command(after_help = "Examples:
+
+Download every file of a release to the current directory:
+$ gr release download v1.0.0
+
+Download only the zip files to a directory:
+$ gr release download v1.0.0 --pattern '*.zip' --download-dir dist
+")]
+    /// Download the files of a release
+    Download {
+        /// The tag of the release
+        tag: String,
+        /// Download only the files matching this pattern (with * and ? wildcards)
+        #[arg(long)]
+        pattern: Option<String>,
+        /// The directory to save the files to (default: the current directory)
+        #[arg(long)]
+        download_dir: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand, Clone)]
+#[command(after_help = "Examples:
+
+Create an issue:
+$ gr issue create -m 'Login is broken'
+
+List the open issues of the current repository:
+$ gr issue list
+")]
+pub enum IssueCommands {
+    #[command(after_help = "Examples:
+
+Create an issue:
+$ gr issue create -m 'Login is broken'
+
+Create an issue with a description:
+$ gr issue create -m 'Login is broken' -d 'The button does nothing.'
+")]
+    /// Create an issue
+    Create {
+        /// The title of the issue
+        #[arg(short, long)]
+        message: String,
+        /// The description of the issue
+        #[arg(short, long)]
+        description: Option<String>,
+        /// Open the issue in the browser
+        #[arg(long)]
+        open: bool,
+    },
+    #[command(after_help = "Examples:
+
+List the open issues:
+$ gr issue list
+
+List every issue, even the closed ones:
+$ gr issue list --state all
+")]
+    /// List the issues
+    List {
+        /// Filter by issue state
+        #[arg(long, value_enum)]
+        state: Option<IssueFilter>,
+    },
+    #[command(after_help = "Examples:
+
+Get an issue:
+$ gr issue get 12
+
+Open the issue in the browser:
+$ gr issue get 12 --open
+")]
+    /// Get an issue by its number
+    Get {
+        /// The number of the issue
+        id: u32,
+        /// Open the issue in the browser
+        #[arg(long)]
+        open: bool,
+    },
+    #[command(after_help = "Examples:
+
+Close an issue:
+$ gr issue close 12
+")]
+    /// Close an issue
+    Close {
+        /// The number of the issue
+        id: u32,
+    },
+}
+
+#[derive(Debug, Subcommand, Clone)]
+#[command(after_help = "Examples:
+
+Show the stack of the current branch:
+$ gr stack list
+
+Push the branches of the stack and create or update their pull requests:
+$ gr stack submit
+")]
+pub enum StackCommands {
+    #[command(after_help = "Examples:
+
+Show the branches of the stack and their pull requests:
+$ gr stack list
+
+Show a stack that is based on a different branch:
+$ gr stack list --base develop
+")]
+    /// List the branches of the stack and their pull requests
+    List {
+        /// The branch the stack is based on (default: the default branch of the repository)
+        #[arg(long)]
+        base: Option<String>,
+    },
+    #[command(after_help = "Examples:
+
+Create or update a pull request for every branch of the stack:
+$ gr stack submit
+
+Submit a stack that is based on a different branch:
+$ gr stack submit --base develop
+")]
+    /// Push the branches of the stack, create or update one pull request per branch
+    ///
+    /// Every pull request targets the branch below it and gets a table of the whole stack in
+    /// its description. If a pull request is merged, the ones above it are retargeted.
+    Submit {
+        /// The branch the stack is based on (default: the default branch of the repository)
+        #[arg(long)]
+        base: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand, Clone)]
+#[command(after_help = "Examples:
+
+Set a secret of the current repository from a file:
+$ gr secret set DEPLOY_TOKEN < token.txt
+
+List the secrets of an organization:
+$ gr secret list --org organization
+")]
+pub enum SecretCommands {
+    #[command(after_help = "Examples:
+
+Set a secret of the current repository (the value is read from the standard input):
+$ gr secret set DEPLOY_TOKEN
+
+Set a secret of an organization from a command:
+$ pass show deploy-token | gr secret set DEPLOY_TOKEN --org organization
+")]
+    /// Create or update a secret, reading the value from the standard input
+    Set {
+        /// The name of the secret
+        name: String,
+        /// Set the secret for the organization, group or workspace instead of the repository
+        #[arg(long)]
+        org: Option<String>,
+        /// The host of the server with --org (default: the host of the current repository)
+        #[arg(long)]
+        host: Option<String>,
+    },
+    #[command(after_help = "Examples:
+
+List the secrets of the current repository:
+$ gr secret list
+")]
+    /// List the names of the secrets
+    List {
+        /// List the secrets of the organization, group or workspace instead of the repository
+        #[arg(long)]
+        org: Option<String>,
+        /// The host of the server with --org (default: the host of the current repository)
+        #[arg(long)]
+        host: Option<String>,
+    },
+    #[command(after_help = "Examples:
+
+Delete a secret of the current repository:
+$ gr secret delete DEPLOY_TOKEN
+")]
+    /// Delete a secret
+    Delete {
+        /// The name of the secret
+        name: String,
+        /// Delete the secret of the organization, group or workspace instead of the repository
+        #[arg(long)]
+        org: Option<String>,
+        /// The host of the server with --org (default: the host of the current repository)
+        #[arg(long)]
+        host: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand, Clone)]
+#[command(after_help = "Examples:
+
+Set a variable of the current repository:
+$ echo production | gr variable set ENVIRONMENT
+
+List the variables of an organization:
+$ gr variable list --org organization
+")]
+pub enum VariableCommands {
+    #[command(after_help = "Examples:
+
+Set a variable of the current repository (the value is read from the standard input):
+$ echo production | gr variable set ENVIRONMENT
+
+Set a protected and masked variable of a GitLab group:
+$ gr variable set DEPLOY_TOKEN --org group --protected --masked < token.txt
+")]
+    /// Create or update a variable, reading the value from the standard input
+    Set {
+        /// The name of the variable
+        name: String,
+        /// Only expose the variable on protected branches and tags (GitLab only)
+        #[arg(long)]
+        protected: bool,
+        /// Hide the value of the variable in job logs (GitLab only)
+        #[arg(long)]
+        masked: bool,
+        /// Set the variable for the organization, group or workspace instead of the repository
+        #[arg(long)]
+        org: Option<String>,
+        /// The host of the server with --org (default: the host of the current repository)
+        #[arg(long)]
+        host: Option<String>,
+    },
+    #[command(after_help = "Examples:
+
+List the variables of the current repository:
+$ gr variable list
+")]
+    /// List the variables and their values
+    List {
+        /// List the variables of the organization, group or workspace instead of the repository
+        #[arg(long)]
+        org: Option<String>,
+        /// The host of the server with --org (default: the host of the current repository)
+        #[arg(long)]
+        host: Option<String>,
+    },
+    #[command(after_help = "Examples:
+
+Delete a variable of the current repository:
+$ gr variable delete ENVIRONMENT
+")]
+    /// Delete a variable
+    Delete {
+        /// The name of the variable
+        name: String,
+        /// Delete the variable of the organization, group or workspace instead of the repository
+        #[arg(long)]
+        org: Option<String>,
+        /// The host of the server with --org (default: the host of the current repository)
+        #[arg(long)]
+        host: Option<String>,
+    },
+}
+
 #[derive(Debug, Subcommand, Clone)]
 pub enum Commands {
     #[command(after_help = "Examples:
@@ -333,7 +1232,7 @@ $ gr login git.example.org --type gitlab")]
     Login {
         /// The host to login to (e.g. github.com, default: current repo)
         hostname: Option<String>,
-        /// The type of the instance, only required if self-hosted (e.g. gitlab, gitea)
+        /// The type of the instance, only required if self-hosted (e.g. gitlab, gitea, gogs, gerrit, sourcehut)
         #[arg(long = "type")]
         vcs_type: Option<String>,
         /// The repo which the authentication should only appeal
@@ -346,9 +1245,24 @@ $ gr login git.example.org --type gitlab")]
     /// Open, list and merge pull requests
     #[command(subcommand)]
     Pr(PrCommands),
+    /// Create, list and close issues
+    #[command(subcommand)]
+    Issue(IssueCommands),
     /// Fork or create repositories
     #[command(subcommand)]
     Repo(RepoCommands),
+    /// Create, list and upload files to releases
+    #[command(subcommand)]
+    Release(ReleaseCommands),
+    /// Create and update stacks of dependent pull requests
+    #[command(subcommand)]
+    Stack(StackCommands),
+    /// Set, list and delete CI secrets
+    #[command(subcommand)]
+    Secret(SecretCommands),
+    /// Set, list and delete CI variables
+    #[command(subcommand)]
+    Variable(VariableCommands),
     /// Generate tab completion to shell
     Completion { shell: Shell },
 }
@@ -374,6 +1288,9 @@ pub struct Cli {
     /// Change the source branch (default: the current branch)
     #[arg(short, long, global = true)]
     pub branch: Option<String>,
+    /// Change the remote (default: the branch upstream, the remote in the repository configuration or origin)
+    #[arg(long, global = true)]
+    pub remote: Option<String>,
     /// Change the repo directory (default: the current directory)
     #[arg(long, global = true)]
     pub dir: Option<String>,
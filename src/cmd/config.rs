@@ -1,16 +1,23 @@
+use crate::cmd::args::Protocol;
 use dirs::config_dir;
 use eyre::{eyre, Context, ContextCompat, Result};
-use gr_bin::vcs::common::VersionControlSettings;
+use gr_bin::{
+    git::{branch::BranchRule, changelog::ChangelogOptions, git::LocalRepository, url::parse_url},
+    vcs::common::VersionControlSettings,
+};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, fs::read_to_string, fs::write};
-use tracing::{info, instrument, trace};
+use tracing::{info, instrument, trace, warn};
 
 #[derive(Debug, Deserialize, Serialize, Default, Clone)]
 pub struct RepositoryConfig {
     pub auth: Option<String>,
     pub default_branch: Option<String>,
-    #[serde(default)]
-    pub fork: bool,
+    pub mailing_list: Option<String>,
+    pub changelog: Option<ChangelogOptions>,
+    pub branch_rules: Option<Vec<BranchRule>>,
+    pub remote: Option<String>,
+    pub upstream_remote: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -18,6 +25,8 @@ pub struct VcsConfig {
     #[serde(rename = "type")]
     pub vcs_type: Option<String>,
     pub auth: String,
+    pub sendmail: Option<String>,
+    pub protocol: Option<Protocol>,
     #[serde(default)]
     pub repositories: HashMap<String, RepositoryConfig>,
 }
@@ -94,6 +103,91 @@ impl Configuration {
         Ok(())
     }
 
+    #[instrument(skip(self))]
+    pub fn find_host(&self, host: Option<String>) -> Result<(String, VcsConfig)> {
+        host.clone()
+            .and_then(|host| self.vcs.get_key_value(&host))
+            .or_else(|| {
+                // If only one VCS is used, fallback to that one
+                if self.vcs.len() == 1 {
+                    self.vcs.iter().next()
+                } else {
+                    None
+                }
+            })
+            .map(|(hostname, vcs)| (hostname.clone(), vcs.clone()))
+            .wrap_err(if let Some(host) = host {
+                eyre!("There is no host {host} in the configuration file.")
+            } else {
+                eyre!("You have to pass the server name (like github.com) in the --host flag.")
+            })
+    }
+
+    #[instrument(skip(self))]
+    pub fn move_repository(&mut self, hostname: &str, from: &str, to: &str) -> bool {
+        // Keep the per-repository settings when the repository is renamed or transferred
+        if let Some(vcs) = self.vcs.get_mut(hostname) {
+            if let Some(repository) = vcs.repositories.remove(from) {
+                vcs.repositories.insert(to.to_string(), repository);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    #[instrument(skip(self))]
+    pub fn find_repository(&self, hostname: &str, repo: &str) -> Option<&RepositoryConfig> {
+        self.vcs.get(hostname)?.repositories.get(repo)
+    }
+
+    #[instrument(skip(self, repository))]
+    pub fn find_remote(&self, repository: &LocalRepository) -> Option<String> {
+        // Any remote of the repository can pin it, e.g. the fork or the upstream
+        repository
+            .get_remotes()
+            .ok()?
+            .into_iter()
+            .find_map(|remote| {
+                let url = repository.get_remote_url(&remote).ok()?;
+                let (hostname, repo) = parse_url(&url).ok()?;
+                self.find_repository(&hostname, &repo)?.remote.clone()
+            })
+    }
+
+    #[instrument(skip(self))]
+    pub fn find_upstream_remote(&self, hostname: &str, repo: &str) -> String {
+        self.find_repository(hostname, repo)
+            .and_then(|r| r.upstream_remote.clone())
+            .unwrap_or("upstream".to_string())
+    }
+
+    #[instrument(skip(self))]
+    pub fn set_upstream_remote(&mut self, hostname: &str, repo: &str, name: &str) -> Result<()> {
+        // Only store the upstream remote name if it differs from the default
+        if name == "upstream" {
+            return Ok(());
+        }
+
+        let Some(vcs) = self.vcs.get_mut(hostname) else {
+            warn!("There is no host {hostname} in the configuration file, remote {name} is not stored as the upstream.");
+            return Ok(());
+        };
+        vcs.repositories
+            .entry(repo.to_string())
+            .or_default()
+            .upstream_remote = Some(name.to_string());
+
+        self.clone().save()
+    }
+
+    #[instrument(skip(self))]
+    pub fn open_repository(&self, dir: Option<String>) -> Result<LocalRepository> {
+        let conf = self.clone();
+        Ok(LocalRepository::init(dir)?
+            .with_pinned_remote(move |repository| conf.find_remote(repository)))
+    }
+
     #[instrument]
     pub fn find_settings(&self, hostname: &str, repo: &str) -> Option<VersionControlSettings> {
         let vcs = self.vcs.get(hostname);
@@ -104,8 +198,26 @@ impl Configuration {
                 auth: r.and_then(|r| r.auth.clone()).unwrap_or(v.auth.clone()),
                 default_branch: r.and_then(|r| r.default_branch.clone()),
                 vcs_type: v.vcs_type.clone(),
-                fork: r.map(|r| r.fork).unwrap_or_default(),
+                upstream: None,
+                sendmail: v.sendmail.clone(),
+                mailing_list: r.and_then(|r| r.mailing_list.clone()),
             }
         })
     }
+
+    // Pull requests of forks are opened on the upstream repository
+    #[instrument(skip(self, repository, settings))]
+    pub fn with_upstream(
+        &self,
+        repository: &LocalRepository,
+        hostname: &str,
+        repo: &str,
+        settings: VersionControlSettings,
+    ) -> Result<VersionControlSettings> {
+        let upstream = self.find_upstream_remote(hostname, repo);
+        Ok(VersionControlSettings {
+            upstream: repository.get_parsed_upstream(hostname, repo, &upstream)?,
+            ..settings
+        })
+    }
 }
@@ -0,0 +1,92 @@
+use crate::cmd::{
+    args::{Cli, Commands, IssueCommands, IssueFilter, OutputType},
+    config::Configuration,
+};
+use eyre::{eyre, ContextCompat, Result};
+use gr_bin::{
+    formatters::formatter::Formatter,
+    vcs::common::{init_vcs, CreateIssue, IssueStateFilter, VersionControlSettings},
+};
+use tracing::{info, instrument};
+
+#[instrument(skip_all, fields(command = ?args.command))]
+pub fn issue(args: Cli, conf: Configuration) -> Result<()> {
+    let Cli {
+        command,
+        branch,
+        dir,
+        remote,
+        auth,
+        output,
+        ..
+    } = args;
+    if let Commands::Issue(command) = command {
+        let repository = conf.open_repository(dir)?;
+        let (hostname, repository_name, ..) = repository.get_parsed_remote(branch, remote)?;
+
+        // Find settings or use the auth command
+        let settings = conf.find_settings(&hostname, &repository_name);
+        let settings = if let Some(auth) = auth {
+            VersionControlSettings {
+                auth,
+                ..settings.unwrap_or_default()
+            }
+        } else {
+            settings.wrap_err(eyre!(
+                "Authentication not found for {} in {}.",
+                &hostname,
+                &repository_name
+            ))?
+        };
+
+        let vcs = init_vcs(hostname, repository_name, settings)?;
+
+        let message = match command {
+            IssueCommands::Create {
+                message,
+                description,
+                open,
+            } => {
+                let issue = vcs.create_issue(CreateIssue {
+                    title: message,
+                    description: description.unwrap_or_default(),
+                })?;
+                issue.print(open, output.into());
+                return Ok(());
+            }
+            IssueCommands::List { state } => {
+                let state = match state {
+                    Some(IssueFilter::Open) | None => IssueStateFilter::Open,
+                    Some(IssueFilter::Closed) => IssueStateFilter::Closed,
+                    Some(IssueFilter::All) => IssueStateFilter::All,
+                };
+                let issues = vcs.list_issues(state)?;
+                for issue in issues {
+                    match output {
+                        OutputType::Json => print!("{}", issue.show_json()),
+                        _ => print!("{}", issue.show_short()),
+                    }
+                }
+                return Ok(());
+            }
+            IssueCommands::Get { id, open } => {
+                let issue = vcs.get_issue(id)?;
+                issue.print(open, output.into());
+                return Ok(());
+            }
+            IssueCommands::Close { id } => {
+                let issue = vcs.close_issue(id)?;
+                format!("Issue #{} is closed.", issue.id)
+            }
+        };
+
+        match output {
+            OutputType::Json => info!("{}", message),
+            _ => println!("{}", message),
+        };
+
+        Ok(())
+    } else {
+        Err(eyre!("Invalid command!"))
+    }
+}
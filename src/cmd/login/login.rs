@@ -3,16 +3,18 @@ use crate::cmd::{
     config::{Configuration, RepositoryConfig, VcsConfig},
 };
 use eyre::{eyre, Context, Result};
-use gr_bin::{
-    git::git::LocalRepository,
-    vcs::common::{init_vcs, VersionControlSettings},
-};
+use gr_bin::vcs::common::{init_vcs, VersionControlSettings};
 use std::{collections::HashMap, io, io::Write, thread::sleep, time::Duration};
 use tracing::instrument;
 
 #[instrument(skip_all, fields(command = ?args.command))]
 pub fn login(args: Cli, mut conf: Configuration) -> Result<()> {
-    let Cli { command, dir, .. } = args;
+    let Cli {
+        command,
+        dir,
+        remote,
+        ..
+    } = args;
     if let Commands::Login {
         hostname,
         vcs_type,
@@ -28,8 +30,8 @@ pub fn login(args: Cli, mut conf: Configuration) -> Result<()> {
         let (hostname, repo) = if let Some(hostname) = hostname {
             (hostname, repo_name.clone().unwrap_or_default())
         } else {
-            let repo = LocalRepository::init(dir)?;
-            let (hostname, repo, ..) = repo.get_parsed_remote(None)?;
+            let repo = conf.open_repository(dir)?;
+            let (hostname, repo, ..) = repo.get_parsed_remote(None, remote)?;
 
             (hostname, repo)
         };
@@ -100,6 +102,8 @@ pub fn login(args: Cli, mut conf: Configuration) -> Result<()> {
                     })
                     .or_insert(VcsConfig {
                         auth: token,
+                        sendmail: None,
+                        protocol: None,
                         repositories: HashMap::default(),
                         vcs_type,
                     });
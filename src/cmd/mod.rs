@@ -1,5 +1,10 @@
 pub mod args;
 pub mod config;
+pub mod issue;
 pub mod login;
 pub mod pr;
+pub mod release;
 pub mod repo;
+pub mod secret;
+pub mod stack;
+pub mod variable;
@@ -4,7 +4,7 @@ use crate::cmd::{
 };
 use eyre::{eyre, ContextCompat, Result};
 use gr_bin::vcs::common::init_vcs;
-use gr_bin::{git::git::LocalRepository, vcs::common::VersionControlSettings};
+use gr_bin::vcs::common::VersionControlSettings;
 use tracing::instrument;
 
 #[instrument(skip_all, fields(command = ?args.command))]
@@ -13,13 +13,14 @@ pub fn close(args: Cli, conf: Configuration) -> Result<()> {
         command,
         branch,
         dir,
+        remote,
         auth,
         output,
         ..
     } = args;
     if let Commands::Pr(PrCommands::Close {}) = command {
-        let repository = LocalRepository::init(dir)?;
-        let (hostname, repo, remote_branch) = repository.get_parsed_remote(branch)?;
+        let repository = conf.open_repository(dir)?;
+        let (hostname, repo, remote_branch) = repository.get_parsed_remote(branch, remote)?;
         let remote_branch = remote_branch.wrap_err(eyre!(
             "You have to push this branch first before you can close a PR."
         ))?;
@@ -39,6 +40,8 @@ pub fn close(args: Cli, conf: Configuration) -> Result<()> {
             ))?
         };
 
+        let settings = conf.with_upstream(&repository, &hostname, &repo, settings)?;
+
         let vcs = init_vcs(hostname, repo, settings)?;
         let pr = vcs.get_pr_by_branch(&remote_branch)?;
         let pr = vcs.close_pr(pr.id)?;
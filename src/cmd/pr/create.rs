@@ -1,13 +1,21 @@
-use std::io::{stdin, BufRead, Error};
+use std::{
+    fs::{read_to_string, write},
+    io::{stdin, BufRead, Error},
+};
 
 use crate::cmd::{
     args::{Cli, Commands, OutputType, PrCommands},
     config::{Configuration, RepositoryConfig},
     pr::merge::merge,
 };
-use eyre::{eyre, ContextCompat, Result};
-use gr_bin::vcs::common::{init_vcs, CreatePullRequest};
-use gr_bin::{git::git::LocalRepository, vcs::common::VersionControlSettings};
+use eyre::{eyre, Context, ContextCompat, Result};
+use gr_bin::git::{
+    branch::match_branch,
+    changelog::generate_changelog,
+    message::{build_message, parse_message},
+    template::{fill_template, find_templates, select_template},
+};
+use gr_bin::vcs::common::{init_vcs, CreatePullRequest, VersionControlSettings};
 use tracing::{debug, info, instrument, trace};
 
 #[instrument(skip_all, fields(command = ?args.command))]
@@ -16,6 +24,7 @@ pub fn create(mut args: Cli, mut conf: Configuration) -> Result<()> {
         command,
         branch,
         dir,
+        remote,
         auth,
         output,
         ..
@@ -29,56 +38,155 @@ pub fn create(mut args: Cli, mut conf: Configuration) -> Result<()> {
         reviewers,
         should_merge,
         force_merge,
+        template,
+        edit,
     }) = command
     {
-        let repository = LocalRepository::init(dir)?;
-        let (hostname, repo, remote_branch) = repository.get_parsed_remote(branch.clone())?;
+        // Open the editor if it is asked for, or if the title is missing in a terminal
+        let should_edit = edit || (message.is_none() && atty::is(atty::Stream::Stdin));
+
+        let repository = conf.open_repository(dir)?;
+        let (hostname, repo, remote_branch) =
+            repository.get_parsed_remote(branch.clone(), remote.clone())?;
+
+        // Derive the title, the labels and the target from the naming rules of the branches
+        let branch_name = match remote_branch.clone().or(branch.clone()) {
+            Some(branch) => branch,
+            None => repository.get_branch()?,
+        };
+        let rules = conf
+            .find_repository(&hostname, &repo)
+            .and_then(|r| r.branch_rules.clone())
+            .unwrap_or_default();
+        let rule = match_branch(&rules, &branch_name)?.unwrap_or_default();
+        let message = message.or(rule.title);
+        if message.is_none() && !should_edit {
+            return Err(eyre!(
+                "The title of the pull request is missing, pass it with -m, use --edit or add a branch rule."
+            ));
+        }
+
+        // Find settings or use the auth command
+        let settings = conf.find_settings(&hostname, &repo);
+        let settings = if let Some(auth) = auth {
+            VersionControlSettings {
+                auth,
+                ..settings.unwrap_or_default()
+            }
+        } else {
+            settings.wrap_err(eyre!(
+                "Authentication not found for {} in {}.",
+                &hostname,
+                &repo
+            ))?
+        };
+
+        let settings = conf.with_upstream(&repository, &hostname, &repo, settings)?;
+
+        let default_branch = settings.default_branch.clone();
+        let changelog = conf
+            .find_repository(&hostname, &repo)
+            .and_then(|r| r.changelog.clone())
+            .unwrap_or_default();
+
+        let vcs = init_vcs(hostname.clone(), repo.clone(), settings)?;
+
+        // Without a pull request on the server, there is nothing to open or merge
+        let is_tracked = vcs.tracks_pull_requests()?;
+        if !is_tracked && (open || should_merge) {
+            return Err(eyre!(
+                "Pull requests are not kept on {hostname}, they cannot be opened or merged."
+            ));
+        }
 
         // If branch has no remote, we can push it first
         let remote_branch = remote_branch
             .wrap_err("You have to push this branch first before you can create a PR.")
             .or_else(|_| {
-                let remotes = repository.get_remotes()?;
-                let remote = remotes.first().wrap_err(
-                    "There are no remotes, push to a repository before you can create a PR.",
-                )?;
+                let remote = if let Some(remote) = &remote {
+                    remote.to_string()
+                } else {
+                    repository.get_default_remote()?
+                };
 
                 let branch = if let Some(branch) = &branch {
                     branch.to_string()
                 } else {
                     repository.get_branch()?
                 };
+
+                // Some forges don't need the branch pushed, changes are sent on create
+                if !vcs.requires_pushed_branch()? {
+                    return Ok(branch);
+                }
+
                 let message = format!("Branch {branch} is not pushed, pushing to {remote}.");
                 match output {
                     OutputType::Json => info!("{}", message),
                     _ => println!("{}", message),
                 };
-                repository.push(remote, &branch)?;
+                repository.push(&remote, &branch)?;
 
                 Ok(branch) as Result<String>
             })?;
 
-        // Find settings or use the auth command
-        let settings = conf.find_settings(&hostname, &repo);
-        let settings = if let Some(auth) = auth {
-            VersionControlSettings {
-                auth,
-                ..settings.unwrap_or_default()
-            }
-        } else {
-            settings.wrap_err(eyre!(
-                "Authentication not found for {} in {}.",
-                &hostname,
-                &repo
-            ))?
+        let target = target.or(rule.target);
+        let is_default_branch = target.is_none();
+        let target_branch = match target.or(default_branch) {
+            Some(target) => target,
+            None => vcs.get_repository()?.default_branch,
         };
 
-        let default_branch = settings.default_branch.clone();
+        // Generate the changelog of the commits, grouped by Conventional Commit types
+        let generate_commits = || {
+            let source_branch = branch.clone().unwrap_or("HEAD".to_string());
+            let commits = repository
+                .get_commit_messages(Some(&target_branch), &source_branch)
+                .map(|messages| generate_changelog(&messages, &changelog));
 
-        let vcs = init_vcs(hostname.clone(), repo.clone(), settings)?;
+            match commits {
+                Ok(commits) => {
+                    trace!("Description is {}.", commits);
+                    Some(commits)
+                }
+                Err(err) => {
+                    info!(
+                        "Commit generation failed: {}, description will be empty.",
+                        err
+                    );
+                    None
+                }
+            }
+        };
 
-        // Read the description from the STDIN or fallback to
+        // Fill the pull request template of the repository, if there is one
+        let is_template_selected = template.is_some();
+        let templates = repository
+            .get_root()
+            .map(|root| find_templates(&root))
+            .unwrap_or_default();
+        let template = select_template(&templates, template.as_deref())?;
+        let fill = || {
+            let template = template.as_ref()?;
+            info!("Using pull request template {}.", template.name);
+            let content = read_to_string(&template.path)
+                .map_err(|err| info!("Cannot read template {}: {err}.", template.name))
+                .ok()?;
+            let commits = generate_commits().unwrap_or_default();
+            Some(fill_template(
+                &content,
+                &[
+                    ("title", message.as_deref().unwrap_or_default()),
+                    ("branch", &remote_branch),
+                    ("target", &target_branch),
+                    ("commits", &commits),
+                ],
+            ))
+        };
+
+        // Read the description from the STDIN or fallback to the template or the commits
         let description = description
+            .or_else(|| if is_template_selected { fill() } else { None })
             .or_else(|| {
                 if atty::is(atty::Stream::Stdin) {
                     debug!("Stdin is closed, nothing to read from here.");
@@ -90,47 +198,48 @@ pub fn create(mut args: Cli, mut conf: Configuration) -> Result<()> {
                 debug!("Received data from stdin: {:?}", str);
                 str.ok()
             })
-            .or_else(|| {
-                let commits = repository
-                    .get_branch_commits_from_target(
-                        branch.clone(),
-                        target
-                            .clone()
-                            .or(default_branch)
-                            // TODO: fallback to query from the VCS
-                            .unwrap_or("master".to_string()),
-                    )
-                    .map(|commits| {
-                        commits
-                            .into_iter()
-                            .map(|s| format!("- {}\n", s))
-                            .collect::<String>()
-                    });
-
-                match commits {
-                    Ok(commits) => {
-                        trace!("Description is {}.", commits);
-                        Some(commits)
-                    }
-                    Err(err) => {
-                        info!(
-                            "Commit generation failed: {}, description will be empty.",
-                            err
-                        );
-                        None
-                    }
-                }
-            })
+            .or_else(fill)
+            .or_else(generate_commits)
             .unwrap_or_default();
-        let is_default_branch = target.is_none();
+
+        let (title, description) = if should_edit {
+            let mut help = vec![
+                format!("Source: {remote_branch}"),
+                format!("Target: {target_branch}"),
+            ];
+            if let Some(reviewers) = reviewers.as_ref().filter(|r| !r.is_empty()) {
+                help.push(format!("Reviewers: {}", reviewers.join(", ")));
+            }
+            let commits = repository
+                .get_branch_commits_from_target(branch.clone(), target_branch.clone())
+                .unwrap_or_default();
+            if !commits.is_empty() {
+                help.push("".to_string());
+                help.push("Commits:".to_string());
+                help.extend(commits.into_iter().map(|commit| format!("  {commit}")));
+            }
+
+            // Edit the message in the git directory, like git commit does
+            let path = format!("{}/PULLREQ_EDITMSG", repository.get_git_dir()?);
+            let content =
+                build_message(message.as_deref().unwrap_or_default(), &description, &help);
+            write(&path, content).wrap_err(eyre!("Cannot write to {path}."))?;
+            repository.edit_file(&path)?;
+            let content = read_to_string(&path).wrap_err(eyre!("Cannot read {path}."))?;
+
+            parse_message(&content).wrap_err("Aborting pull request due to empty title.")?
+        } else {
+            (message.unwrap_or_default(), description)
+        };
 
         let pr = vcs.create_pr(CreatePullRequest {
-            title: message,
+            title,
             description,
             source: remote_branch,
-            target,
+            target: Some(target_branch),
             close_source_branch: delete,
             reviewers: reviewers.unwrap_or_default(),
+            labels: rule.labels,
         })?;
 
         pr.print(open, output.into());
@@ -146,7 +255,7 @@ pub fn create(mut args: Cli, mut conf: Configuration) -> Result<()> {
         }
 
         // Save default branch to config for caching
-        if is_default_branch {
+        if is_tracked && is_default_branch {
             conf.vcs.entry(hostname).and_modify(|host| {
                 host.repositories
                     .entry(repo)
@@ -3,13 +3,13 @@ use crate::cmd::{
     config::Configuration,
 };
 use eyre::{eyre, ContextCompat, Result};
+use gr_bin::vcs::common::ListPullRequestFilters;
 use gr_bin::{
     formatters::formatter::Formatter,
     vcs::common::{
         init_vcs, PullRequestStateFilter, PullRequestUserFilter, VersionControlSettings,
     },
 };
-use gr_bin::{git::git::LocalRepository, vcs::common::ListPullRequestFilters};
 use tracing::instrument;
 
 #[instrument(skip_all, fields(command = ?args.command))]
@@ -17,14 +17,15 @@ pub fn list(args: Cli, conf: Configuration) -> Result<()> {
     let Cli {
         command,
         dir,
+        remote,
         auth,
         output,
         ..
     } = args;
     if let Commands::Pr(PrCommands::List { author, state }) = command {
-        let repository = LocalRepository::init(dir)?;
+        let repository = conf.open_repository(dir)?;
         // Find remote from branch upstream, or fallback to origin or any remote
-        let (hostname, repo, ..) = repository.get_parsed_remote(None)?;
+        let (hostname, repo, ..) = repository.get_parsed_remote(None, remote)?;
 
         // Find settings or use the auth command
         let settings = conf.find_settings(&hostname, &repo);
@@ -41,6 +42,8 @@ pub fn list(args: Cli, conf: Configuration) -> Result<()> {
             ))?
         };
 
+        let settings = conf.with_upstream(&repository, &hostname, &repo, settings)?;
+
         let vcs = init_vcs(hostname, repo, settings)?;
 
         let prs = vcs.list_prs(ListPullRequestFilters {
@@ -5,7 +5,7 @@ use crate::cmd::{
 use colored::Colorize;
 use eyre::{eyre, ContextCompat, Result};
 use gr_bin::vcs::common::init_vcs;
-use gr_bin::{git::git::LocalRepository, vcs::common::VersionControlSettings};
+use gr_bin::vcs::common::VersionControlSettings;
 use tracing::{info, instrument};
 
 #[instrument(skip_all, fields(command = ?args.command))]
@@ -14,13 +14,15 @@ pub fn merge(args: Cli, conf: Configuration) -> Result<()> {
         command,
         branch,
         dir,
+        remote,
         auth,
         output,
         ..
     } = args;
     if let Commands::Pr(PrCommands::Merge { delete, force }) = command {
-        let repository = LocalRepository::init(dir)?;
-        let (hostname, repo, remote_branch) = repository.get_parsed_remote(branch.clone())?;
+        let repository = conf.open_repository(dir)?;
+        let (hostname, repo, remote_branch) =
+            repository.get_parsed_remote(branch.clone(), remote)?;
         let remote_branch = remote_branch.wrap_err(eyre!(
             "You have to push this branch first, before you can merge it."
         ))?;
@@ -40,6 +42,8 @@ pub fn merge(args: Cli, conf: Configuration) -> Result<()> {
             ))?
         };
 
+        let settings = conf.with_upstream(&repository, &hostname, &repo, settings)?;
+
         // Check if there are local changes if we are on this branch
         if branch.is_none() && repository.has_modifications()? && !force {
             return Err(eyre!("You can't merge until there are local modifications. If you are sure, pass the --force argument."));
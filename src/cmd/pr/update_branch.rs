@@ -0,0 +1,144 @@
+use crate::cmd::{
+    args::{Cli, Commands, OutputType, PrCommands},
+    config::Configuration,
+};
+use eyre::{eyre, ContextCompat, Result};
+use gr_bin::vcs::common::VersionControlSettings;
+use gr_bin::vcs::common::{init_vcs, BranchUpdate, PullRequest, VersionControl};
+use std::{thread::sleep, time::Duration};
+use tracing::{info, instrument};
+
+// Forges update the pull requests in the background, so they can show the old commit for a while
+#[instrument(skip(vcs, is_updated))]
+fn wait_for_update(
+    vcs: &dyn VersionControl,
+    id: u32,
+    is_updated: impl Fn(&str) -> bool,
+) -> Result<PullRequest> {
+    for _ in 0..10 {
+        let pr = vcs.get_pr_by_id(id)?;
+        if is_updated(&pr.source_sha) {
+            return Ok(pr);
+        }
+        sleep(Duration::from_secs(1));
+    }
+
+    info!("Pull request {id} is not updated yet, it may take a while.");
+    vcs.get_pr_by_id(id)
+}
+
+#[instrument(skip_all, fields(command = ?args.command))]
+pub fn update_branch(args: Cli, conf: Configuration) -> Result<()> {
+    let Cli {
+        command,
+        branch,
+        dir,
+        remote,
+        auth,
+        output,
+        ..
+    } = args;
+    if let Commands::Pr(PrCommands::UpdateBranch { rebase }) = command {
+        let repository = conf.open_repository(dir)?;
+        let (hostname, repo, remote_branch) =
+            repository.get_parsed_remote(branch.clone(), remote.clone())?;
+        let remote_branch = remote_branch.wrap_err(eyre!(
+            "You have to push this branch first, before you can update it."
+        ))?;
+        let (remote, _) = repository.get_branch_upstream(branch.clone(), remote)?;
+        let local_branch = match branch {
+            Some(branch) => branch,
+            None => repository.get_branch()?,
+        };
+
+        // Find settings or use the auth command
+        let settings = conf.find_settings(&hostname, &repo);
+        let settings = if let Some(auth) = auth {
+            VersionControlSettings {
+                auth,
+                ..settings.unwrap_or_default()
+            }
+        } else {
+            settings.wrap_err(eyre!(
+                "Authentication not found for {} in {}.",
+                &hostname,
+                &repo
+            ))?
+        };
+
+        let settings = conf.with_upstream(&repository, &hostname, &repo, settings)?;
+        let target_remote = if settings.upstream.is_some() {
+            conf.find_upstream_remote(&hostname, &repo)
+        } else {
+            remote.clone()
+        };
+
+        let vcs = init_vcs(hostname, repo, settings)?;
+        let pr = vcs.get_pr_by_branch(&remote_branch)?;
+
+        // The same check as merge, updating the remote branch would conflict with local commits
+        let branch_sha = repository.get_branch_sha(Some(local_branch.clone()))?;
+        if !branch_sha.starts_with(&pr.source_sha) {
+            return Err(eyre!(
+                "Branch {local_branch} has unpushed changes, push them before updating it."
+            ));
+        }
+
+        let message = match vcs.update_pr_branch(pr.id, &pr.source_sha, rebase)? {
+            BranchUpdate::Updated => {
+                let updated = wait_for_update(vcs.as_ref(), pr.id, |sha| sha != pr.source_sha)?;
+
+                // Move the local branch to the new commits, so merge doesn't see it as diverged
+                repository.fetch(&remote, &remote_branch)?;
+                repository.reset_branch(&local_branch, &format!("{remote}/{remote_branch}"))?;
+
+                if rebase {
+                    format!("Rebased {remote_branch} on {}.", updated.target)
+                } else {
+                    format!("Merged {} into {remote_branch}.", updated.target)
+                }
+            }
+            BranchUpdate::Unsupported => {
+                if repository.get_branch()? != local_branch {
+                    return Err(eyre!(
+                        "Branch {local_branch} has to be checked out to update it locally."
+                    ));
+                }
+                if repository.has_modifications()? {
+                    return Err(eyre!(
+                        "You can't update the branch with local modifications, commit or stash them first."
+                    ));
+                }
+
+                info!("Updating {remote_branch} locally, the forge has no API for it.");
+                repository.fetch(&target_remote, &pr.target)?;
+                let target_ref = format!("{target_remote}/{}", pr.target);
+                if rebase {
+                    repository.rebase(&target_ref)?;
+                } else {
+                    repository.merge(&target_ref)?;
+                }
+                // The local branch can have a different name than the one on the remote
+                repository.force_push_with_lease(&remote, &local_branch, &remote_branch)?;
+
+                let branch_sha = repository.get_branch_sha(Some(local_branch.clone()))?;
+                wait_for_update(vcs.as_ref(), pr.id, |sha| branch_sha.starts_with(sha))?;
+
+                if rebase {
+                    format!("Rebased {remote_branch} on {target_ref} and pushed it.")
+                } else {
+                    format!("Merged {target_ref} into {remote_branch} and pushed it.")
+                }
+            }
+        };
+
+        match output {
+            OutputType::Json => info!("{}", message),
+            _ => println!("{}", message),
+        };
+
+        Ok(())
+    } else {
+        Err(eyre!("Invalid command!"))
+    }
+}
@@ -0,0 +1,229 @@
+use crate::cmd::{
+    args::{Cli, Commands, OutputType, ReleaseCommands},
+    config::Configuration,
+};
+use eyre::{eyre, Context, ContextCompat, Result};
+use gr_bin::{
+    files::{matches_pattern, save_to_file},
+    formatters::formatter::Formatter,
+    git::{
+        changelog::{generate_changelog, ChangelogOptions},
+        git::LocalRepository,
+    },
+    vcs::common::{init_vcs, CreateRelease, VersionControl, VersionControlSettings},
+};
+use std::{
+    fs::{read, read_to_string},
+    io::{self, stdin},
+    path::{Path, PathBuf},
+};
+use tracing::{info, instrument};
+
+#[instrument]
+fn read_notes(path: Option<String>) -> Result<String> {
+    match path.as_deref() {
+        Some("-") => io::read_to_string(stdin()).wrap_err("Cannot read release notes from stdin."),
+        Some(path) => {
+            read_to_string(path).wrap_err(eyre!("Cannot read release notes from {path}."))
+        }
+        None => Ok(String::new()),
+    }
+}
+
+#[instrument(skip(repository))]
+fn generate_notes(
+    repository: &LocalRepository,
+    tag: &str,
+    target: Option<String>,
+    options: &ChangelogOptions,
+) -> Result<String> {
+    // Use the tag if it exists locally, otherwise the commit it will be created on
+    let reference = if repository.get_branch_sha(Some(tag.to_string())).is_ok() {
+        tag.to_string()
+    } else {
+        target.unwrap_or("HEAD".to_string())
+    };
+    let previous_tag = repository.get_previous_tag(&reference);
+    info!(
+        "Generating notes from {} to {reference}.",
+        previous_tag.as_deref().unwrap_or("the first commit")
+    );
+
+    let messages = repository.get_commit_messages(previous_tag.as_deref(), &reference)?;
+    Ok(generate_changelog(&messages, options))
+}
+
+#[instrument(skip(vcs))]
+fn upload_assets(
+    vcs: &dyn VersionControl,
+    tag: &str,
+    files: Vec<String>,
+    output: OutputType,
+) -> Result<()> {
+    for file in files {
+        let name = Path::new(&file)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .wrap_err(eyre!("Invalid file name {file}."))?;
+        let content = read(&file).wrap_err(eyre!("Cannot read {file}."))?;
+        let asset = vcs.upload_release_asset(tag, name, &content)?;
+
+        let message = format!("Uploaded {} to {}.", asset.name, asset.url);
+        match output {
+            OutputType::Json => info!("{}", message),
+            _ => println!("{}", message),
+        };
+    }
+
+    Ok(())
+}
+
+#[instrument(skip_all, fields(command = ?args.command))]
+pub fn release(args: Cli, conf: Configuration) -> Result<()> {
+    let Cli {
+        command,
+        branch,
+        dir,
+        remote,
+        auth,
+        output,
+        ..
+    } = args;
+    if let Commands::Release(command) = command {
+        let repository = conf.open_repository(dir)?;
+        let (remote, _) = repository.get_branch_upstream(branch, remote)?;
+        let (hostname, repository_name, ..) =
+            repository.get_parsed_remote(None, Some(remote.clone()))?;
+
+        // Find settings or use the auth command
+        let settings = conf.find_settings(&hostname, &repository_name);
+        let settings = if let Some(auth) = auth {
+            VersionControlSettings {
+                auth,
+                ..settings.unwrap_or_default()
+            }
+        } else {
+            settings.wrap_err(eyre!(
+                "Authentication not found for {} in {}.",
+                &hostname,
+                &repository_name
+            ))?
+        };
+
+        let changelog = conf
+            .find_repository(&hostname, &repository_name)
+            .and_then(|r| r.changelog.clone())
+            .unwrap_or_default();
+
+        let vcs = init_vcs(hostname, repository_name, settings)?;
+
+        let message = match command {
+            ReleaseCommands::Create {
+                tag,
+                target,
+                name,
+                notes_file,
+                generate_notes: should_generate_notes,
+                draft,
+                prerelease,
+                create_tag,
+                assets,
+            } => {
+                let description = if should_generate_notes {
+                    generate_notes(&repository, &tag, target.clone(), &changelog)?
+                } else {
+                    read_notes(notes_file)?
+                };
+
+                if create_tag {
+                    repository.create_tag(&tag, target.clone())?;
+                    let tag_ref = format!("refs/tags/{tag}");
+                    repository.push_to_ref(&remote, &tag_ref, &tag_ref)?;
+                }
+
+                let release = vcs.create_release(CreateRelease {
+                    tag: tag.clone(),
+                    target,
+                    name,
+                    description,
+                    draft,
+                    prerelease,
+                })?;
+                print!("{}", release.show(output.into()));
+                upload_assets(vcs.as_ref(), &tag, assets, output)?;
+                return Ok(());
+            }
+            ReleaseCommands::List {} => {
+                let releases = vcs.list_releases()?;
+                for release in releases {
+                    match output {
+                        OutputType::Json => print!("{}", release.show_json()),
+                        _ => print!("{}", release.show_short()),
+                    }
+                }
+                return Ok(());
+            }
+            ReleaseCommands::Get { tag, open } => {
+                let release = vcs.get_release(&tag)?;
+                release.print(open, output.into());
+                return Ok(());
+            }
+            ReleaseCommands::Delete { tag } => {
+                vcs.delete_release(&tag)?;
+                format!("Release {tag} is deleted.")
+            }
+            ReleaseCommands::Upload { tag, files } => {
+                upload_assets(vcs.as_ref(), &tag, files, output)?;
+                return Ok(());
+            }
+            ReleaseCommands::Download {
+                tag,
+                pattern,
+                download_dir,
+            } => {
+                let release = vcs.get_release(&tag)?;
+                let assets: Vec<_> = release
+                    .assets
+                    .iter()
+                    .filter(|asset| {
+                        pattern
+                            .as_ref()
+                            .is_none_or(|pattern| matches_pattern(pattern, &asset.name, false))
+                    })
+                    .collect();
+                if assets.is_empty() {
+                    return Err(eyre!("Release {tag} has no matching files."));
+                }
+
+                let download_dir = PathBuf::from(download_dir.unwrap_or(".".to_string()));
+                for asset in assets {
+                    // Never write outside of the directory, even if the name has a path in it
+                    let name = Path::new(&asset.name)
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .wrap_err(eyre!("Invalid file name {}.", asset.name))?;
+                    let path = download_dir.join(name);
+                    let path = path.to_string_lossy();
+                    let size =
+                        save_to_file(&path, |writer| vcs.download_release_asset(asset, writer))?;
+
+                    let message = format!("Downloaded {} ({size} bytes).", path);
+                    match output {
+                        OutputType::Json => info!("{}", message),
+                        _ => println!("{}", message),
+                    };
+                }
+                return Ok(());
+            }
+        };
+
+        match output {
+            OutputType::Json => info!("{}", message),
+            _ => println!("{}", message),
+        };
+
+        Ok(())
+    } else {
+        Err(eyre!("Invalid command!"))
+    }
+}
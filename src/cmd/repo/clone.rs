@@ -0,0 +1,92 @@
+use crate::cmd::{
+    args::{Cli, Commands, Protocol, RepoCommands},
+    config::{Configuration, VcsConfig},
+};
+use eyre::{eyre, Result};
+use gr_bin::{
+    git::{git::LocalRepository, url::parse_url},
+    vcs::common::{init_vcs, VersionControlSettings},
+};
+use tracing::{info, instrument};
+
+#[instrument(skip_all, fields(command = ?args.command))]
+pub fn clone(args: Cli, mut conf: Configuration) -> Result<()> {
+    let Cli {
+        command,
+        output,
+        dir,
+        auth: auth_override,
+        ..
+    } = args;
+    if let Commands::Repo(RepoCommands::Clone {
+        repository: repository_name,
+        host,
+        protocol,
+        remote_name,
+    }) = command
+    {
+        // Check if the host if full URL
+        let (parsed_host, path) =
+            parse_url(&LocalRepository::init(None)?.rewrite_url(&repository_name))
+                .map(|(host, path)| (Some(host), path))
+                .unwrap_or((None, repository_name));
+
+        // Figure out the final hostname to use
+        let (
+            hostname,
+            VcsConfig {
+                auth,
+                vcs_type,
+                protocol: default_protocol,
+                ..
+            },
+        ) = conf.find_host(host.or(parsed_host))?;
+        let protocol = protocol.or(default_protocol).unwrap_or_default();
+
+        let settings = VersionControlSettings {
+            auth: auth_override.unwrap_or(auth),
+            vcs_type,
+            ..Default::default()
+        };
+
+        // If only the name is given, clone the repository of the current user
+        let path = if path.contains('/') {
+            path
+        } else {
+            let user =
+                init_vcs(hostname.clone(), "".to_string(), settings.clone())?.get_current_user()?;
+            format!("{}/{}", user.username, path)
+        };
+        info!("Cloning repository {path} from {hostname}.");
+
+        let vcs = init_vcs(hostname.clone(), path, settings.clone())?;
+        let repo = vcs.get_repository()?;
+
+        let (url, fallback_url) = match protocol {
+            Protocol::Ssh => (repo.ssh_url.clone(), repo.https_url.clone()),
+            Protocol::Https => (repo.https_url.clone(), repo.ssh_url.clone()),
+        };
+        let repository = LocalRepository::init(dir.clone())?;
+        let cloned = repository
+            .clone(url, dir.clone())
+            .or_else(|_| repository.clone(fallback_url, dir))?;
+
+        // If the repository is a fork, add the original repository as a remote
+        if let Some(forked) = &repo.forked_from {
+            let original =
+                init_vcs(hostname.clone(), forked.full_name.clone(), settings)?.get_repository()?;
+            let upstream_url = match protocol {
+                Protocol::Ssh => original.ssh_url,
+                Protocol::Https => original.https_url,
+            };
+            cloned.set_remote(remote_name.clone(), upstream_url)?;
+            conf.set_upstream_remote(&hostname, &repo.full_name, &remote_name)?;
+        }
+
+        repo.print(false, output.into());
+
+        Ok(())
+    } else {
+        Err(eyre!("Invalid command!"))
+    }
+}
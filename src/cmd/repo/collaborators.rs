@@ -0,0 +1,79 @@
+use crate::cmd::{
+    args::{Cli, CollaboratorCommands, Commands, OutputType, RepoCommands},
+    config::Configuration,
+};
+use eyre::{eyre, ContextCompat, Result};
+use gr_bin::formatters::formatter::Formatter;
+use gr_bin::vcs::common::init_vcs;
+use gr_bin::vcs::common::VersionControlSettings;
+use tracing::{info, instrument};
+
+#[instrument(skip_all, fields(command = ?args.command))]
+pub fn collaborators(args: Cli, conf: Configuration) -> Result<()> {
+    let Cli {
+        command,
+        branch,
+        dir,
+        remote,
+        auth,
+        output,
+        ..
+    } = args;
+    if let Commands::Repo(RepoCommands::Collaborators(command)) = command {
+        let repository = conf.open_repository(dir)?;
+        let (hostname, repository_name, ..) = repository.get_parsed_remote(branch, remote)?;
+
+        // Find settings or use the auth command
+        let settings = conf.find_settings(&hostname, &repository_name);
+        let settings = if let Some(auth) = auth {
+            VersionControlSettings {
+                auth,
+                ..settings.unwrap_or_default()
+            }
+        } else {
+            settings.wrap_err(eyre!(
+                "Authentication not found for {} in {}.",
+                &hostname,
+                &repository_name
+            ))?
+        };
+
+        let vcs = init_vcs(hostname, repository_name.clone(), settings)?;
+
+        let message = match command {
+            CollaboratorCommands::List {} => {
+                let collaborators = vcs.list_collaborators()?;
+                for collaborator in collaborators {
+                    match output {
+                        OutputType::Json => print!("{}", collaborator.show_json()),
+                        _ => print!("{}", collaborator.show_short()),
+                    }
+                }
+                return Ok(());
+            }
+            CollaboratorCommands::Add {
+                username,
+                permission,
+            } => {
+                vcs.add_collaborator(&username, permission.into())?;
+                format!(
+                    "Added {username} to {repository_name} with {} permission.",
+                    format!("{permission:?}").to_lowercase()
+                )
+            }
+            CollaboratorCommands::Remove { username } => {
+                vcs.remove_collaborator(&username)?;
+                format!("Removed {username} from {repository_name}.")
+            }
+        };
+
+        match output {
+            OutputType::Json => info!("{}", message),
+            _ => println!("{}", message),
+        };
+
+        Ok(())
+    } else {
+        Err(eyre!("Invalid command!"))
+    }
+}
@@ -7,7 +7,7 @@ use crate::cmd::{
 use colored::Colorize;
 use eyre::{eyre, Context, ContextCompat, Result};
 use gr_bin::vcs::common::init_vcs;
-use gr_bin::{git::git::LocalRepository, vcs::common::VersionControlSettings};
+use gr_bin::vcs::common::VersionControlSettings;
 use tracing::instrument;
 
 #[instrument(skip_all, fields(command = ?args.command))]
@@ -16,13 +16,14 @@ pub fn delete(args: Cli, conf: Configuration) -> Result<()> {
         command,
         branch,
         dir,
+        remote,
         auth,
         output,
         ..
     } = args;
     if let Commands::Repo(RepoCommands::Delete { force }) = command {
-        let repository = LocalRepository::init(dir)?;
-        let (hostname, repo, ..) = repository.get_parsed_remote(branch)?;
+        let repository = conf.open_repository(dir)?;
+        let (hostname, repo, ..) = repository.get_parsed_remote(branch, remote)?;
 
         // Find settings or use the auth command
         let settings = conf.find_settings(&hostname, &repo);
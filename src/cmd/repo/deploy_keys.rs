@@ -0,0 +1,106 @@
+use crate::cmd::{
+    args::{Cli, Commands, DeployKeyCommands, OutputType, RepoCommands},
+    config::Configuration,
+};
+use eyre::{eyre, Context, ContextCompat, Result};
+use gr_bin::formatters::formatter::Formatter;
+use gr_bin::vcs::common::VersionControlSettings;
+use gr_bin::vcs::common::{init_vcs, CreateDeployKey};
+use std::{
+    fs::read_to_string,
+    io::{self, stdin},
+};
+use tracing::{info, instrument};
+
+#[instrument]
+fn read_key(path: Option<String>) -> Result<String> {
+    let content = match path.as_deref() {
+        Some(path) if path != "-" => {
+            read_to_string(path).wrap_err(eyre!("Cannot read public key from {path}."))?
+        }
+        _ => io::read_to_string(stdin()).wrap_err("Cannot read public key from stdin.")?,
+    };
+
+    // Only the first line is the key, to avoid uploading something else by mistake
+    content
+        .lines()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty())
+        .map(|line| line.to_string())
+        .wrap_err("The public key is empty.")
+}
+
+#[instrument(skip_all, fields(command = ?args.command))]
+pub fn deploy_keys(args: Cli, conf: Configuration) -> Result<()> {
+    let Cli {
+        command,
+        branch,
+        dir,
+        remote,
+        auth,
+        output,
+        ..
+    } = args;
+    if let Commands::Repo(RepoCommands::DeployKeys(command)) = command {
+        let repository = conf.open_repository(dir)?;
+        let (hostname, repository_name, ..) = repository.get_parsed_remote(branch, remote)?;
+
+        // Find settings or use the auth command
+        let settings = conf.find_settings(&hostname, &repository_name);
+        let settings = if let Some(auth) = auth {
+            VersionControlSettings {
+                auth,
+                ..settings.unwrap_or_default()
+            }
+        } else {
+            settings.wrap_err(eyre!(
+                "Authentication not found for {} in {}.",
+                &hostname,
+                &repository_name
+            ))?
+        };
+
+        let vcs = init_vcs(hostname, repository_name, settings)?;
+
+        match command {
+            DeployKeyCommands::List {} => {
+                let keys = vcs.list_deploy_keys()?;
+                for key in keys {
+                    match output {
+                        OutputType::Json => print!("{}", key.show_json()),
+                        _ => print!("{}", key.show_short()),
+                    }
+                }
+            }
+            DeployKeyCommands::Add {
+                key,
+                title,
+                read_write,
+            } => {
+                let key = read_key(key)?;
+                // Public keys are in the format of "type key comment"
+                let title = title
+                    .or_else(|| key.splitn(3, ' ').nth(2).map(|c| c.to_string()))
+                    .wrap_err("The key doesn't have a comment, set the title with --title.")?;
+                let key = vcs.add_deploy_key(CreateDeployKey {
+                    title,
+                    key,
+                    read_only: !read_write,
+                })?;
+                print!("{}", key.show(output.into()));
+            }
+            DeployKeyCommands::Remove { id } => {
+                vcs.remove_deploy_key(&id)?;
+                let message = format!("Removed deploy key {id}.");
+                match output {
+                    OutputType::Json => info!("{}", message),
+                    _ => println!("{}", message),
+                };
+            }
+        };
+
+        Ok(())
+    } else {
+        Err(eyre!("Invalid command!"))
+    }
+}
@@ -0,0 +1,81 @@
+use crate::cmd::{
+    args::{Cli, Commands, OutputType, RepoCommands},
+    config::Configuration,
+};
+use eyre::{eyre, ContextCompat, Result};
+use gr_bin::files::save_to_file;
+use gr_bin::vcs::common::VersionControlSettings;
+use gr_bin::vcs::common::{init_vcs, ArchiveFormat};
+use tracing::{info, instrument};
+
+#[instrument(skip_all, fields(command = ?args.command))]
+pub fn download_archive(args: Cli, conf: Configuration) -> Result<()> {
+    let Cli {
+        command,
+        branch,
+        dir,
+        remote,
+        auth,
+        output,
+        ..
+    } = args;
+    if let Commands::Repo(RepoCommands::DownloadArchive {
+        reference,
+        format,
+        file,
+    }) = command
+    {
+        let repository = conf.open_repository(dir)?;
+        let (hostname, repository_name, ..) = repository.get_parsed_remote(branch, remote)?;
+
+        // Find settings or use the auth command
+        let settings = conf.find_settings(&hostname, &repository_name);
+        let settings = if let Some(auth) = auth {
+            VersionControlSettings {
+                auth,
+                ..settings.unwrap_or_default()
+            }
+        } else {
+            settings.wrap_err(eyre!(
+                "Authentication not found for {} in {}.",
+                &hostname,
+                &repository_name
+            ))?
+        };
+
+        let vcs = init_vcs(hostname, repository_name.clone(), settings)?;
+
+        let reference = match reference {
+            Some(reference) => reference,
+            None => vcs.get_repository()?.default_branch,
+        };
+        let format: ArchiveFormat = format.into();
+        let file = file.unwrap_or_else(|| {
+            let name = repository_name
+                .rsplit_once('/')
+                .map_or(repository_name.as_str(), |(_, name)| name);
+            format!(
+                "{name}-{}.{}",
+                reference.replace('/', "-"),
+                format.extension()
+            )
+        });
+
+        let size = save_to_file(&file, |writer| {
+            vcs.download_archive(&reference, format, writer)
+        })?;
+
+        // Don't mix the message into the archive on the standard output
+        if file != "-" {
+            let message = format!("Downloaded {reference} to {file} ({size} bytes).");
+            match output {
+                OutputType::Json => info!("{}", message),
+                _ => println!("{}", message),
+            };
+        }
+
+        Ok(())
+    } else {
+        Err(eyre!("Invalid command!"))
+    }
+}
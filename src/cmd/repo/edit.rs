@@ -0,0 +1,91 @@
+use crate::cmd::{
+    args::{Cli, Commands, RepoCommands},
+    config::Configuration,
+    repo::rename::move_local_repository,
+};
+use eyre::{eyre, ContextCompat, Result};
+use gr_bin::vcs::common::VersionControlSettings;
+use gr_bin::vcs::common::{init_vcs, UpdateRepository};
+use tracing::instrument;
+
+#[instrument(skip_all, fields(command = ?args.command))]
+pub fn edit(args: Cli, conf: Configuration) -> Result<()> {
+    let Cli {
+        command,
+        branch,
+        dir,
+        remote,
+        auth,
+        output,
+        ..
+    } = args;
+    if let Commands::Repo(RepoCommands::Edit {
+        name,
+        description,
+        visibility,
+        default_branch,
+        homepage,
+        topics,
+        archived,
+        allow_merge_commit,
+        allow_squash_merge,
+        allow_rebase_merge,
+    }) = command
+    {
+        let repository = conf.open_repository(dir)?;
+        let (remote, _) = repository.get_branch_upstream(branch, remote)?;
+        let (hostname, repository_name, ..) =
+            repository.get_parsed_remote(None, Some(remote.clone()))?;
+
+        // Find settings or use the auth command
+        let settings = conf.find_settings(&hostname, &repository_name);
+        let settings = if let Some(auth) = auth {
+            VersionControlSettings {
+                auth,
+                ..settings.unwrap_or_default()
+            }
+        } else {
+            settings.wrap_err(eyre!(
+                "Authentication not found for {} in {}.",
+                &hostname,
+                &repository_name
+            ))?
+        };
+
+        let vcs = init_vcs(hostname.clone(), repository_name.clone(), settings)?;
+
+        // Fail before the server is changed, if the remote can't follow the repository
+        let renamed = name.is_some();
+        if renamed {
+            repository.check_move_remote(&remote, &repository_name)?;
+        }
+
+        let repo = vcs.update_repository(UpdateRepository {
+            name,
+            description,
+            visibility: visibility.map(|v| v.into()),
+            default_branch,
+            homepage,
+            topics,
+            archived,
+            allow_merge_commit,
+            allow_squash_merge,
+            allow_rebase_merge,
+        })?;
+        if renamed {
+            move_local_repository(
+                &repository,
+                conf,
+                &remote,
+                &hostname,
+                &repository_name,
+                &repo.full_name,
+            )?;
+        }
+        repo.print(false, output.into());
+
+        Ok(())
+    } else {
+        Err(eyre!("Invalid command!"))
+    }
+}
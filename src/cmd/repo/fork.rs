@@ -1,6 +1,6 @@
 use crate::cmd::{
-    args::{Cli, Commands, RepoCommands},
-    config::{Configuration, RepositoryConfig},
+    args::{Cli, Commands, Protocol, RepoCommands},
+    config::Configuration,
 };
 use eyre::{eyre, ContextCompat, Result};
 use gr_bin::{
@@ -23,9 +23,15 @@ pub fn fork(args: Cli, mut conf: Configuration) -> Result<()> {
         source,
         repository: repository_name,
         clone,
+        protocol,
+        remote_name,
     }) = command
     {
-        let (hostname, original) = parse_url(&source)?;
+        let (hostname, original) = parse_url(&LocalRepository::init(None)?.rewrite_url(&source))?;
+
+        let protocol = protocol
+            .or(conf.vcs.get(&hostname).and_then(|vcs| vcs.protocol))
+            .unwrap_or_default();
 
         let settings = conf.find_settings(&hostname, &original);
         let settings = if let Some(auth) = auth {
@@ -43,7 +49,7 @@ pub fn fork(args: Cli, mut conf: Configuration) -> Result<()> {
 
         let (organization, name) = repository_name
             .map(|name| {
-                if let Some((org, name)) = name.split_once('/') {
+                if let Some((org, name)) = name.rsplit_once('/') {
                     (Some(org.to_string()), Some(name.to_string()))
                 } else {
                     (None, Some(name))
@@ -51,12 +57,13 @@ pub fn fork(args: Cli, mut conf: Configuration) -> Result<()> {
             })
             .unwrap_or((None, None));
 
-        let vcs = init_vcs(hostname.clone(), original.clone(), settings.clone())?;
+        let vcs = init_vcs(hostname.clone(), original, settings.clone())?;
+        let original = vcs.get_repository()?;
         let repo = vcs.fork_repository(ForkRepository { organization, name })?;
 
         repo.print(false, output.into());
 
-        let vcs = init_vcs(hostname.clone(), repo.full_name, settings)?;
+        let vcs = init_vcs(hostname.clone(), repo.full_name.clone(), settings)?;
 
         if clone {
             // Wait until we have our repository, to let the server finish the fork
@@ -68,23 +75,19 @@ pub fn fork(args: Cli, mut conf: Configuration) -> Result<()> {
             }
 
             // If clone is given, clone it to the directory (or here)
+            let (url, fallback_url, upstream_url) = match protocol {
+                Protocol::Ssh => (repo.ssh_url, repo.https_url, original.ssh_url),
+                Protocol::Https => (repo.https_url, repo.ssh_url, original.https_url),
+            };
             let repository = LocalRepository::init(dir.clone())?;
-            repository
-                .clone(repo.ssh_url, dir.clone())
-                .or_else(|_| repository.clone(repo.https_url, dir))?;
-        }
+            let cloned = repository
+                .clone(url, dir.clone())
+                .or_else(|_| repository.clone(fallback_url, dir))?;
 
-        // Update configuration with setting up if it is forked
-        conf.vcs.entry(hostname).and_modify(|host| {
-            host.repositories
-                .entry(original)
-                .and_modify(|r| r.fork = repo.forked_from.is_some())
-                .or_insert(RepositoryConfig {
-                    fork: repo.forked_from.is_some(),
-                    ..Default::default()
-                });
-        });
-        conf.save()?;
+            // Add the original repository as a remote, to open pull requests there
+            cloned.set_remote(remote_name.clone(), upstream_url)?;
+            conf.set_upstream_remote(&hostname, &repo.full_name, &remote_name)?;
+        }
 
         Ok(())
     } else {
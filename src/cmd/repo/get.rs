@@ -1,25 +1,26 @@
 use crate::cmd::{
     args::{Cli, Commands, RepoCommands},
-    config::{Configuration, RepositoryConfig},
+    config::Configuration,
 };
 use eyre::{eyre, ContextCompat, Result};
 use gr_bin::vcs::common::init_vcs;
-use gr_bin::{git::git::LocalRepository, vcs::common::VersionControlSettings};
+use gr_bin::vcs::common::VersionControlSettings;
 use tracing::instrument;
 
 #[instrument(skip_all, fields(command = ?args.command))]
-pub fn get(args: Cli, mut conf: Configuration) -> Result<()> {
+pub fn get(args: Cli, conf: Configuration) -> Result<()> {
     let Cli {
         command,
         branch,
         dir,
+        remote,
         auth,
         output,
         ..
     } = args;
     if let Commands::Repo(RepoCommands::Get { open }) = command {
-        let repository = LocalRepository::init(dir)?;
-        let (hostname, repository_name, ..) = repository.get_parsed_remote(branch)?;
+        let repository = conf.open_repository(dir)?;
+        let (hostname, repository_name, ..) = repository.get_parsed_remote(branch, remote)?;
 
         // Find settings or use the auth command
         let settings = conf.find_settings(&hostname, &repository_name);
@@ -36,23 +37,11 @@ pub fn get(args: Cli, mut conf: Configuration) -> Result<()> {
             ))?
         };
 
-        let vcs = init_vcs(hostname.clone(), repository_name.clone(), settings)?;
+        let vcs = init_vcs(hostname, repository_name, settings)?;
 
         let repo = vcs.get_repository()?;
         repo.print(open, output.into());
 
-        // Update configuration with setting up if it is forked
-        conf.vcs.entry(hostname).and_modify(|host| {
-            host.repositories
-                .entry(repository_name)
-                .and_modify(|r| r.fork = repo.forked_from.is_some())
-                .or_insert(RepositoryConfig {
-                    fork: repo.forked_from.is_some(),
-                    ..Default::default()
-                });
-        });
-        conf.save()?;
-
         Ok(())
     } else {
         Err(eyre!("Invalid command!"))
@@ -0,0 +1,89 @@
+use crate::cmd::{
+    args::{Cli, Commands, HookCommands, OutputType, RepoCommands},
+    config::Configuration,
+};
+use eyre::{eyre, ContextCompat, Result};
+use gr_bin::formatters::formatter::Formatter;
+use gr_bin::vcs::common::VersionControlSettings;
+use gr_bin::vcs::common::{init_vcs, CreateWebhook};
+use tracing::{info, instrument};
+
+// The command isn't recorded, it can hold the secret of the webhook
+#[instrument(skip_all)]
+pub fn hooks(args: Cli, conf: Configuration) -> Result<()> {
+    let Cli {
+        command,
+        branch,
+        dir,
+        remote,
+        auth,
+        output,
+        ..
+    } = args;
+    if let Commands::Repo(RepoCommands::Hooks(command)) = command {
+        let repository = conf.open_repository(dir)?;
+        let (hostname, repository_name, ..) = repository.get_parsed_remote(branch, remote)?;
+
+        // Find settings or use the auth command
+        let settings = conf.find_settings(&hostname, &repository_name);
+        let settings = if let Some(auth) = auth {
+            VersionControlSettings {
+                auth,
+                ..settings.unwrap_or_default()
+            }
+        } else {
+            settings.wrap_err(eyre!(
+                "Authentication not found for {} in {}.",
+                &hostname,
+                &repository_name
+            ))?
+        };
+
+        let vcs = init_vcs(hostname, repository_name, settings)?;
+
+        let message = match command {
+            HookCommands::List {} => {
+                let webhooks = vcs.list_webhooks()?;
+                for webhook in webhooks {
+                    match output {
+                        OutputType::Json => print!("{}", webhook.show_json()),
+                        _ => print!("{}", webhook.show_short()),
+                    }
+                }
+                return Ok(());
+            }
+            HookCommands::Create {
+                url,
+                secret,
+                content_type,
+                events,
+            } => {
+                let webhook = vcs.create_webhook(CreateWebhook {
+                    url,
+                    secret,
+                    content_type: content_type.into(),
+                    events: events.into_iter().map(|e| e.into()).collect(),
+                })?;
+                print!("{}", webhook.show(output.into()));
+                return Ok(());
+            }
+            HookCommands::Delete { id } => {
+                vcs.delete_webhook(&id)?;
+                format!("Deleted webhook {id}.")
+            }
+            HookCommands::Test { id } => {
+                vcs.test_webhook(&id)?;
+                format!("Sent a test event to webhook {id}.")
+            }
+        };
+
+        match output {
+            OutputType::Json => info!("{}", message),
+            _ => println!("{}", message),
+        };
+
+        Ok(())
+    } else {
+        Err(eyre!("Invalid command!"))
+    }
+}
@@ -0,0 +1,64 @@
+use crate::cmd::{
+    args::{Cli, Commands, OutputType, RepoCommands},
+    config::{Configuration, VcsConfig},
+};
+use eyre::{eyre, Result};
+use gr_bin::{
+    formatters::formatter::Formatter,
+    vcs::common::{init_vcs, ListRepositoryFilters, VersionControlSettings},
+};
+use tracing::instrument;
+
+#[instrument(skip_all, fields(command = ?args.command))]
+pub fn list(args: Cli, conf: Configuration) -> Result<()> {
+    let Cli {
+        command,
+        dir,
+        remote,
+        auth: auth_override,
+        output,
+        ..
+    } = args;
+    if let Commands::Repo(RepoCommands::List {
+        org,
+        archived,
+        visibility,
+        host,
+    }) = command
+    {
+        // Use the host of the current repository, if there is one
+        let host = host.or_else(|| {
+            conf.open_repository(dir)
+                .and_then(|repository| repository.get_parsed_remote(None, remote))
+                .map(|(hostname, ..)| hostname)
+                .ok()
+        });
+        let (hostname, VcsConfig { auth, vcs_type, .. }) = conf.find_host(host)?;
+
+        let settings = VersionControlSettings {
+            auth: auth_override.unwrap_or(auth),
+            vcs_type,
+            ..Default::default()
+        };
+
+        let vcs = init_vcs(hostname, "".to_string(), settings)?;
+        let repos = vcs.list_repositories(
+            org.as_deref(),
+            ListRepositoryFilters {
+                archived,
+                visibility: visibility.map(|v| v.into()),
+            },
+        )?;
+
+        for repo in repos {
+            match output {
+                OutputType::Json => print!("{}", repo.show_json()),
+                _ => print!("{}", repo.show_short()),
+            }
+        }
+
+        Ok(())
+    } else {
+        Err(eyre!("Invalid command!"))
+    }
+}
@@ -1,4 +1,17 @@
+pub mod clone;
+pub mod collaborators;
 pub mod delete;
+pub mod deploy_keys;
+pub mod download_archive;
+pub mod edit;
 pub mod fork;
 pub mod get;
+pub mod hooks;
+pub mod list;
 pub mod new;
+pub mod protect;
+pub mod protections;
+pub mod rename;
+pub mod search;
+pub mod sync;
+pub mod transfer;
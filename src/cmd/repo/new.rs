@@ -31,12 +31,13 @@ pub fn new(args: Cli, mut conf: Configuration) -> Result<()> {
     }) = command
     {
         // Check if the host if full URL
-        let (parsed_host, path) = parse_url(&repository_name)
-            .map(|(host, path)| (Some(host), path))
-            .unwrap_or((None, repository_name));
+        let (parsed_host, path) =
+            parse_url(&LocalRepository::init(None)?.rewrite_url(&repository_name))
+                .map(|(host, path)| (Some(host), path))
+                .unwrap_or((None, repository_name));
 
         // Check if the path can be split into parts
-        let (organization, name) = match path.split_once('/') {
+        let (organization, name) = match path.rsplit_once('/') {
             Some((organization, name)) => (Some(organization.to_string()), name.to_string()),
             None => (None, path),
         };
@@ -0,0 +1,62 @@
+use crate::cmd::{
+    args::{Cli, Commands, RepoCommands},
+    config::Configuration,
+};
+use eyre::{eyre, ContextCompat, Result};
+use gr_bin::formatters::formatter::Formatter;
+use gr_bin::vcs::common::VersionControlSettings;
+use gr_bin::vcs::common::{init_vcs, BranchProtection};
+use tracing::instrument;
+
+#[instrument(skip_all, fields(command = ?args.command))]
+pub fn protect(args: Cli, conf: Configuration) -> Result<()> {
+    let Cli {
+        command,
+        dir,
+        remote,
+        auth,
+        output,
+        ..
+    } = args;
+    if let Commands::Repo(RepoCommands::Protect {
+        branch,
+        required_approvals,
+        required_status_checks,
+        allow_force_push,
+        linear_history,
+    }) = command
+    {
+        let repository = conf.open_repository(dir)?;
+        let (hostname, repository_name, ..) = repository.get_parsed_remote(None, remote)?;
+
+        // Find settings or use the auth command
+        let settings = conf.find_settings(&hostname, &repository_name);
+        let settings = if let Some(auth) = auth {
+            VersionControlSettings {
+                auth,
+                ..settings.unwrap_or_default()
+            }
+        } else {
+            settings.wrap_err(eyre!(
+                "Authentication not found for {} in {}.",
+                &hostname,
+                &repository_name
+            ))?
+        };
+
+        let vcs = init_vcs(hostname, repository_name, settings)?;
+
+        let protection = vcs.protect_branch(BranchProtection {
+            branch,
+            required_approvals,
+            required_status_checks,
+            allow_force_push,
+            linear_history,
+        })?;
+        print!("{}", protection.show(output.into()));
+
+        Ok(())
+    } else {
+        Err(eyre!("Invalid command!"))
+    }
+}
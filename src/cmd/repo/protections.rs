@@ -0,0 +1,55 @@
+use crate::cmd::{
+    args::{Cli, Commands, OutputType, RepoCommands},
+    config::Configuration,
+};
+use eyre::{eyre, ContextCompat, Result};
+use gr_bin::formatters::formatter::Formatter;
+use gr_bin::vcs::common::init_vcs;
+use gr_bin::vcs::common::VersionControlSettings;
+use tracing::instrument;
+
+#[instrument(skip_all, fields(command = ?args.command))]
+pub fn protections(args: Cli, conf: Configuration) -> Result<()> {
+    let Cli {
+        command,
+        branch,
+        dir,
+        remote,
+        auth,
+        output,
+        ..
+    } = args;
+    if let Commands::Repo(RepoCommands::Protections {}) = command {
+        let repository = conf.open_repository(dir)?;
+        let (hostname, repository_name, ..) = repository.get_parsed_remote(branch, remote)?;
+
+        // Find settings or use the auth command
+        let settings = conf.find_settings(&hostname, &repository_name);
+        let settings = if let Some(auth) = auth {
+            VersionControlSettings {
+                auth,
+                ..settings.unwrap_or_default()
+            }
+        } else {
+            settings.wrap_err(eyre!(
+                "Authentication not found for {} in {}.",
+                &hostname,
+                &repository_name
+            ))?
+        };
+
+        let vcs = init_vcs(hostname, repository_name, settings)?;
+
+        let protections = vcs.list_branch_protections()?;
+        for protection in protections {
+            match output {
+                OutputType::Json => print!("{}", protection.show_json()),
+                _ => print!("{}", protection.show_short()),
+            }
+        }
+
+        Ok(())
+    } else {
+        Err(eyre!("Invalid command!"))
+    }
+}
@@ -0,0 +1,96 @@
+use crate::cmd::{
+    args::{Cli, Commands, RepoCommands},
+    config::Configuration,
+};
+use eyre::{eyre, ContextCompat, Result};
+use gr_bin::vcs::common::{init_vcs, UpdateRepository};
+use gr_bin::{git::git::LocalRepository, vcs::common::VersionControlSettings};
+use tracing::{info, instrument};
+
+#[instrument(skip(repository, conf))]
+pub fn move_local_repository(
+    repository: &LocalRepository,
+    mut conf: Configuration,
+    remote: &str,
+    hostname: &str,
+    from: &str,
+    to: &str,
+) -> Result<()> {
+    if from == to {
+        return Ok(());
+    }
+
+    // Point the local checkout to the new location
+    let url = repository.move_remote(remote, from, to)?;
+    info!("Changed the URL of remote {remote} to {url}.");
+
+    // Move the stored settings (e.g. auth or default branch) to the new name
+    if conf.move_repository(hostname, from, to) {
+        conf.save()?;
+    }
+
+    Ok(())
+}
+
+#[instrument(skip_all, fields(command = ?args.command))]
+pub fn rename(args: Cli, conf: Configuration) -> Result<()> {
+    let Cli {
+        command,
+        branch,
+        dir,
+        remote,
+        auth,
+        output,
+        ..
+    } = args;
+    if let Commands::Repo(RepoCommands::Rename { name }) = command {
+        if name.contains('/') {
+            return Err(eyre!(
+                "The new name {name} cannot contain a slash, use gr repo transfer to move it."
+            ));
+        }
+
+        let repository = conf.open_repository(dir)?;
+        let (remote, _) = repository.get_branch_upstream(branch, remote)?;
+        let (hostname, repository_name, ..) =
+            repository.get_parsed_remote(None, Some(remote.clone()))?;
+
+        // Find settings or use the auth command
+        let settings = conf.find_settings(&hostname, &repository_name);
+        let settings = if let Some(auth) = auth {
+            VersionControlSettings {
+                auth,
+                ..settings.unwrap_or_default()
+            }
+        } else {
+            settings.wrap_err(eyre!(
+                "Authentication not found for {} in {}.",
+                &hostname,
+                &repository_name
+            ))?
+        };
+
+        let vcs = init_vcs(hostname.clone(), repository_name.clone(), settings)?;
+
+        // Fail before the server is changed, if the remote can't follow the repository
+        repository.check_move_remote(&remote, &repository_name)?;
+
+        let repo = vcs.update_repository(UpdateRepository {
+            name: Some(name),
+            ..Default::default()
+        })?;
+        move_local_repository(
+            &repository,
+            conf,
+            &remote,
+            &hostname,
+            &repository_name,
+            &repo.full_name,
+        )?;
+        repo.print(false, output.into());
+
+        Ok(())
+    } else {
+        Err(eyre!("Invalid command!"))
+    }
+}
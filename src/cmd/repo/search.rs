@@ -0,0 +1,52 @@
+use crate::cmd::{
+    args::{Cli, Commands, OutputType, RepoCommands},
+    config::{Configuration, VcsConfig},
+};
+use eyre::{eyre, Result};
+use gr_bin::{
+    formatters::formatter::Formatter,
+    vcs::common::{init_vcs, VersionControlSettings},
+};
+use tracing::instrument;
+
+#[instrument(skip_all, fields(command = ?args.command))]
+pub fn search(args: Cli, conf: Configuration) -> Result<()> {
+    let Cli {
+        command,
+        dir,
+        remote,
+        auth: auth_override,
+        output,
+        ..
+    } = args;
+    if let Commands::Repo(RepoCommands::Search { query, limit, host }) = command {
+        // Use the host of the current repository, if there is one
+        let host = host.or_else(|| {
+            conf.open_repository(dir)
+                .and_then(|repository| repository.get_parsed_remote(None, remote))
+                .map(|(hostname, ..)| hostname)
+                .ok()
+        });
+        let (hostname, VcsConfig { auth, vcs_type, .. }) = conf.find_host(host)?;
+
+        let settings = VersionControlSettings {
+            auth: auth_override.unwrap_or(auth),
+            vcs_type,
+            ..Default::default()
+        };
+
+        let vcs = init_vcs(hostname, "".to_string(), settings)?;
+        let repos = vcs.search_repositories(&query, limit)?;
+
+        for repo in repos {
+            match output {
+                OutputType::Json => print!("{}", repo.show_json()),
+                _ => print!("{}", repo.show_short()),
+            }
+        }
+
+        Ok(())
+    } else {
+        Err(eyre!("Invalid command!"))
+    }
+}
@@ -0,0 +1,149 @@
+use crate::cmd::{
+    args::{Cli, Commands, OutputType, RepoCommands},
+    config::Configuration,
+};
+use eyre::{eyre, ContextCompat, Result};
+use gr_bin::{
+    git::git::LocalRepository,
+    vcs::common::{init_vcs, ForkSync, ForkedFromRepository, VersionControlSettings},
+};
+use tracing::{info, instrument};
+
+#[instrument(skip(repository))]
+fn sync_locally(
+    repository: &LocalRepository,
+    remote: &str,
+    upstream_remote: &str,
+    upstream: &ForkedFromRepository,
+    branch: &str,
+    force: bool,
+) -> Result<String> {
+    // Add the original repository as a remote, if it isn't there yet
+    if !repository
+        .get_remotes()?
+        .iter()
+        .any(|remote| remote == upstream_remote)
+    {
+        info!("Adding {} as remote {upstream_remote}.", upstream.html_url);
+        repository.set_remote(upstream_remote.to_string(), upstream.html_url.clone())?;
+    }
+    repository.fetch(upstream_remote, branch)?;
+    repository.fetch(remote, branch)?;
+
+    let upstream_ref = format!("{upstream_remote}/{branch}");
+    let fork_ref = format!("{remote}/{branch}");
+    let target_ref = format!("refs/heads/{branch}");
+    let behind = repository.count_commits(&fork_ref, &upstream_ref)?;
+    let ahead = repository.count_commits(&upstream_ref, &fork_ref)?;
+
+    if ahead == 0 && behind == 0 {
+        Ok(format!(
+            "Branch {branch} is up to date with {}.",
+            upstream.full_name
+        ))
+    } else if ahead == 0 {
+        repository.push_to_ref(remote, &upstream_ref, &target_ref)?;
+        Ok(format!(
+            "Fast-forwarded {branch} by {behind} commits from {}.",
+            upstream.full_name
+        ))
+    } else if force {
+        repository.force_push_to_ref(remote, &upstream_ref, &target_ref)?;
+        Ok(format!(
+            "Reset {branch} to {}, dropping {ahead} commits.",
+            upstream.full_name
+        ))
+    } else if behind == 0 {
+        Ok(format!(
+            "Branch {branch} is {ahead} commits ahead of {}, nothing to sync.",
+            upstream.full_name
+        ))
+    } else {
+        Err(eyre!(
+            "Branch {branch} has diverged from {} ({ahead} commits ahead, {behind} commits behind), use --force to reset it.",
+            upstream.full_name
+        ))
+    }
+}
+
+#[instrument(skip_all, fields(command = ?args.command))]
+pub fn sync(args: Cli, conf: Configuration) -> Result<()> {
+    let Cli {
+        command,
+        branch,
+        dir,
+        remote,
+        auth,
+        output,
+        ..
+    } = args;
+    if let Commands::Repo(RepoCommands::Sync { force }) = command {
+        let repository = conf.open_repository(dir)?;
+        let remote = if let Some(remote) = remote {
+            remote
+        } else {
+            repository.get_default_remote()?
+        };
+        let (hostname, repo, ..) = repository.get_parsed_remote(None, Some(remote.clone()))?;
+
+        // Find settings or use the auth command
+        let settings = conf.find_settings(&hostname, &repo);
+        let settings = if let Some(auth) = auth {
+            VersionControlSettings {
+                auth,
+                ..settings.unwrap_or_default()
+            }
+        } else {
+            settings.wrap_err(eyre!(
+                "Authentication not found for {} in {}.",
+                &hostname,
+                &repo
+            ))?
+        };
+
+        let upstream_remote = conf.find_upstream_remote(&hostname, &repo);
+        let vcs = init_vcs(hostname, repo, settings)?;
+
+        let fork = vcs.get_repository()?;
+        let upstream = fork
+            .forked_from
+            .wrap_err(eyre!("Repository {} is not a fork.", fork.full_name))?;
+        let branch = branch.unwrap_or(fork.default_branch);
+
+        // Sync with the forge API if possible, resetting the branch can only be done locally
+        let sync = if force {
+            ForkSync::Unsupported
+        } else {
+            vcs.sync_fork(&branch)?
+        };
+        let message = match sync {
+            ForkSync::FastForward => {
+                format!("Fast-forwarded {branch} from {}.", upstream.full_name)
+            }
+            ForkSync::Merge => format!(
+                "Branch {branch} has diverged from {}, merged the upstream changes.",
+                upstream.full_name
+            ),
+            ForkSync::UpToDate => {
+                format!("Branch {branch} is up to date with {}.", upstream.full_name)
+            }
+            ForkSync::Unsupported => sync_locally(
+                &repository,
+                &remote,
+                &upstream_remote,
+                &upstream,
+                &branch,
+                force,
+            )?,
+        };
+
+        match output {
+            OutputType::Json => info!("{}", message),
+            _ => println!("{}", message),
+        };
+
+        Ok(())
+    } else {
+        Err(eyre!("Invalid command!"))
+    }
+}
@@ -0,0 +1,63 @@
+use crate::cmd::{
+    args::{Cli, Commands, RepoCommands},
+    config::Configuration,
+    repo::rename::move_local_repository,
+};
+use eyre::{eyre, ContextCompat, Result};
+use gr_bin::vcs::common::init_vcs;
+use gr_bin::vcs::common::VersionControlSettings;
+use tracing::instrument;
+
+#[instrument(skip_all, fields(command = ?args.command))]
+pub fn transfer(args: Cli, conf: Configuration) -> Result<()> {
+    let Cli {
+        command,
+        branch,
+        dir,
+        remote,
+        auth,
+        output,
+        ..
+    } = args;
+    if let Commands::Repo(RepoCommands::Transfer { owner }) = command {
+        let repository = conf.open_repository(dir)?;
+        let (remote, _) = repository.get_branch_upstream(branch, remote)?;
+        let (hostname, repository_name, ..) =
+            repository.get_parsed_remote(None, Some(remote.clone()))?;
+
+        // Find settings or use the auth command
+        let settings = conf.find_settings(&hostname, &repository_name);
+        let settings = if let Some(auth) = auth {
+            VersionControlSettings {
+                auth,
+                ..settings.unwrap_or_default()
+            }
+        } else {
+            settings.wrap_err(eyre!(
+                "Authentication not found for {} in {}.",
+                &hostname,
+                &repository_name
+            ))?
+        };
+
+        let vcs = init_vcs(hostname.clone(), repository_name.clone(), settings)?;
+
+        // Fail before the server is changed, if the remote can't follow the repository
+        repository.check_move_remote(&remote, &repository_name)?;
+
+        let repo = vcs.transfer_repository(&owner)?;
+        move_local_repository(
+            &repository,
+            conf,
+            &remote,
+            &hostname,
+            &repository_name,
+            &repo.full_name,
+        )?;
+        repo.print(false, output.into());
+
+        Ok(())
+    } else {
+        Err(eyre!("Invalid command!"))
+    }
+}
@@ -0,0 +1,151 @@
+use crate::cmd::{
+    args::{Cli, Commands, OutputType, SecretCommands},
+    config::{Configuration, VcsConfig},
+};
+use eyre::{eyre, Context, ContextCompat, Result};
+use gr_bin::{
+    formatters::formatter::Formatter,
+    vcs::common::{init_vcs, VariableScope, VersionControl, VersionControlSettings},
+};
+use rpassword::prompt_password;
+use std::io::{self, stdin, Write};
+use tracing::{info, instrument};
+
+#[instrument(skip(conf, auth))]
+pub fn init_scoped_vcs(
+    conf: &Configuration,
+    dir: Option<String>,
+    branch: Option<String>,
+    remote: Option<String>,
+    auth: Option<String>,
+    org: Option<String>,
+    host: Option<String>,
+) -> Result<(Box<dyn VersionControl>, VariableScope)> {
+    if let Some(org) = org {
+        // Use the host of the current repository, if there is one
+        let host = host.or_else(|| {
+            conf.open_repository(dir)
+                .and_then(|repository| repository.get_parsed_remote(None, remote))
+                .map(|(hostname, ..)| hostname)
+                .ok()
+        });
+        let (
+            hostname,
+            VcsConfig {
+                auth: token,
+                vcs_type,
+                ..
+            },
+        ) = conf.find_host(host)?;
+
+        let settings = VersionControlSettings {
+            auth: auth.unwrap_or(token),
+            vcs_type,
+            ..Default::default()
+        };
+
+        let vcs = init_vcs(hostname, "".to_string(), settings)?;
+        Ok((vcs, VariableScope::Organization(org)))
+    } else {
+        let repository = conf.open_repository(dir)?;
+        let (hostname, repository_name, ..) = repository.get_parsed_remote(branch, remote)?;
+
+        // Find settings or use the auth command
+        let settings = conf.find_settings(&hostname, &repository_name);
+        let settings = if let Some(auth) = auth {
+            VersionControlSettings {
+                auth,
+                ..settings.unwrap_or_default()
+            }
+        } else {
+            settings.wrap_err(eyre!(
+                "Authentication not found for {} in {}.",
+                &hostname,
+                &repository_name
+            ))?
+        };
+
+        let vcs = init_vcs(hostname, repository_name, settings)?;
+        Ok((vcs, VariableScope::Repository))
+    }
+}
+
+#[instrument]
+pub fn read_value(name: &str, hidden: bool) -> Result<String> {
+    // Values are never read from the arguments, to keep them out of the shell history
+    let value = if atty::is(atty::Stream::Stdin) && hidden {
+        // Don't show secrets on the screen while they are typed
+        prompt_password(format!("Enter the value of {name}: "))
+            .wrap_err(eyre!("Reading the value of {name} failed."))?
+    } else if atty::is(atty::Stream::Stdin) {
+        let mut stdout = io::stdout();
+        write!(stdout, "Enter the value of {name}: ")?;
+        stdout.flush()?;
+        let mut value = String::new();
+        stdin()
+            .read_line(&mut value)
+            .wrap_err(eyre!("Reading the value of {name} failed."))?;
+        value
+    } else {
+        io::read_to_string(stdin()).wrap_err(eyre!("Reading the value of {name} failed."))?
+    };
+
+    // Remove the trailing newline of echo or files
+    let value = value
+        .strip_suffix('\n')
+        .map(|value| value.strip_suffix('\r').unwrap_or(value))
+        .unwrap_or(&value);
+    if value.is_empty() {
+        return Err(eyre!("The value of {name} cannot be empty."));
+    }
+
+    Ok(value.to_string())
+}
+
+#[instrument(skip_all, fields(command = ?args.command))]
+pub fn secret(args: Cli, conf: Configuration) -> Result<()> {
+    let Cli {
+        command,
+        branch,
+        dir,
+        remote,
+        auth,
+        output,
+        ..
+    } = args;
+    if let Commands::Secret(command) = command {
+        let message = match command {
+            SecretCommands::Set { name, org, host } => {
+                let (vcs, scope) = init_scoped_vcs(&conf, dir, branch, remote, auth, org, host)?;
+                let value = read_value(&name, true)?;
+                vcs.set_secret(&scope, &name, &value)?;
+                format!("Secret {name} is set.")
+            }
+            SecretCommands::List { org, host } => {
+                let (vcs, scope) = init_scoped_vcs(&conf, dir, branch, remote, auth, org, host)?;
+                let secrets = vcs.list_secrets(&scope)?;
+                for secret in secrets {
+                    match output {
+                        OutputType::Json => print!("{}", secret.show_json()),
+                        _ => print!("{}", secret.show_short()),
+                    }
+                }
+                return Ok(());
+            }
+            SecretCommands::Delete { name, org, host } => {
+                let (vcs, scope) = init_scoped_vcs(&conf, dir, branch, remote, auth, org, host)?;
+                vcs.delete_secret(&scope, &name)?;
+                format!("Secret {name} is deleted.")
+            }
+        };
+
+        match output {
+            OutputType::Json => info!("{}", message),
+            _ => println!("{}", message),
+        };
+
+        Ok(())
+    } else {
+        Err(eyre!("Invalid command!"))
+    }
+}
@@ -0,0 +1,229 @@
+use crate::cmd::{
+    args::{Cli, Commands, OutputType, StackCommands},
+    config::Configuration,
+};
+use eyre::{eyre, ContextCompat, Result};
+use gr_bin::{
+    formatters::formatter::Formatter,
+    git::{
+        branch::match_branch,
+        changelog::generate_changelog,
+        stack::{build_stack_table, get_stack_targets, replace_stack_table, StackItem},
+    },
+    vcs::common::{
+        init_vcs, CreatePullRequest, PullRequest, PullRequestState, UpdatePullRequest,
+        VersionControlSettings,
+    },
+};
+use tracing::{info, instrument};
+
+#[instrument(skip_all, fields(command = ?args.command))]
+pub fn stack(args: Cli, conf: Configuration) -> Result<()> {
+    let Cli {
+        command,
+        branch,
+        dir,
+        remote,
+        auth,
+        output,
+        ..
+    } = args;
+    if let Commands::Stack(command) = command {
+        let repository = conf.open_repository(dir)?;
+        let (remote, _) = repository.get_branch_upstream(branch.clone(), remote)?;
+        let (hostname, repository_name, ..) =
+            repository.get_parsed_remote(None, Some(remote.clone()))?;
+
+        // Find settings or use the auth command
+        let settings = conf.find_settings(&hostname, &repository_name);
+        let settings = if let Some(auth) = auth {
+            VersionControlSettings {
+                auth,
+                ..settings.unwrap_or_default()
+            }
+        } else {
+            settings.wrap_err(eyre!(
+                "Authentication not found for {} in {}.",
+                &hostname,
+                &repository_name
+            ))?
+        };
+
+        let repository_config = conf
+            .find_repository(&hostname, &repository_name)
+            .cloned()
+            .unwrap_or_default();
+        let default_branch = settings.default_branch.clone();
+
+        let vcs = init_vcs(hostname, repository_name, settings)?;
+
+        let base = match command {
+            StackCommands::List { ref base } | StackCommands::Submit { ref base } => base.clone(),
+        };
+        let base = match base.or(default_branch) {
+            Some(base) => base,
+            None => vcs.get_repository()?.default_branch,
+        };
+
+        // Compare with the remote base, so branches merged into it are not part of the stack
+        repository.fetch(&remote, &base)?;
+        let base_ref = format!("{remote}/{base}");
+        let current = match branch {
+            Some(branch) => branch,
+            None => repository.get_branch()?,
+        };
+        let branches = repository.get_stack(&base_ref, &current)?;
+        if branches.is_empty() {
+            return Err(eyre!("Branch {current} has no commits on top of {base}."));
+        }
+        info!("Found the stack {} on {base}.", branches.join(", "));
+
+        match command {
+            StackCommands::List { .. } => {
+                for name in branches {
+                    match vcs.get_pr_by_branch(&name) {
+                        Ok(pr) => match output {
+                            OutputType::Json => print!("{}", pr.show_json()),
+                            _ => print!("{}", pr.show_short()),
+                        },
+                        Err(_) => {
+                            let message = format!("Branch {name} has no pull request.");
+                            match output {
+                                OutputType::Json => info!("{}", message),
+                                _ => println!("{}", message),
+                            };
+                        }
+                    }
+                }
+            }
+            StackCommands::Submit { .. } => {
+                let rules = repository_config.branch_rules.unwrap_or_default();
+                let changelog = repository_config.changelog.unwrap_or_default();
+
+                let existing = branches
+                    .iter()
+                    .map(|name| {
+                        vcs.get_pr_by_branch(name)
+                            .ok()
+                            .filter(|pr| !matches!(pr.state, PullRequestState::Closed))
+                    })
+                    .collect::<Vec<Option<PullRequest>>>();
+                let merged = branches
+                    .iter()
+                    .zip(&existing)
+                    .map(|(name, pr)| {
+                        let merged = pr
+                            .as_ref()
+                            .is_some_and(|pr| matches!(pr.state, PullRequestState::Merged));
+                        (name.as_str(), merged)
+                    })
+                    .collect::<Vec<(&str, bool)>>();
+                let targets = get_stack_targets(&base, &merged);
+
+                let mut prs: Vec<PullRequest> = vec![];
+                for ((name, existing), target) in branches.iter().zip(existing).zip(targets) {
+                    let pr = match (existing, target) {
+                        // Merged pull requests are kept only for the table
+                        (pr, None) => {
+                            prs.extend(pr);
+                            continue;
+                        }
+                        (Some(pr), Some(target)) => {
+                            repository.force_push_with_lease(&remote, name, name)?;
+                            if pr.target != target {
+                                let message = format!(
+                                    "Retargeting pull request #{} from {} to {target}.",
+                                    pr.id, pr.target
+                                );
+                                match output {
+                                    OutputType::Json => info!("{}", message),
+                                    _ => println!("{}", message),
+                                };
+                                vcs.update_pr(
+                                    pr.id,
+                                    UpdatePullRequest {
+                                        target: Some(target),
+                                        ..Default::default()
+                                    },
+                                )?
+                            } else {
+                                pr
+                            }
+                        }
+                        (None, Some(target)) => {
+                            repository.force_push_with_lease(&remote, name, name)?;
+
+                            // Use the branch rules for the title, or the first commit of the branch
+                            let parent_ref = if target == base {
+                                base_ref.clone()
+                            } else {
+                                target.clone()
+                            };
+                            let messages =
+                                repository.get_commit_messages(Some(&parent_ref), name)?;
+                            let rule = match_branch(&rules, name)?.unwrap_or_default();
+                            let title = rule
+                                .title
+                                .or_else(|| {
+                                    messages
+                                        .last()
+                                        .and_then(|message| message.lines().next())
+                                        .map(|subject| subject.to_string())
+                                })
+                                .unwrap_or(name.clone());
+                            vcs.create_pr(CreatePullRequest {
+                                title,
+                                description: generate_changelog(&messages, &changelog),
+                                source: name.clone(),
+                                target: Some(target),
+                                close_source_branch: false,
+                                reviewers: vec![],
+                                labels: rule.labels,
+                            })?
+                        }
+                    };
+                    prs.push(pr);
+                }
+
+                // Keep the navigation table of the stack up to date in every open pull request
+                let items = prs
+                    .iter()
+                    .map(|pr| StackItem {
+                        id: pr.id,
+                        title: pr.title.clone(),
+                        url: pr.url.clone(),
+                        merged: matches!(pr.state, PullRequestState::Merged),
+                    })
+                    .collect::<Vec<StackItem>>();
+                for (i, pr) in prs.into_iter().enumerate() {
+                    let pr = if items[i].merged {
+                        pr
+                    } else {
+                        let table = build_stack_table(&base, &items, i);
+                        let description = replace_stack_table(&pr.description, &table);
+                        if description != pr.description {
+                            vcs.update_pr(
+                                pr.id,
+                                UpdatePullRequest {
+                                    description: Some(description),
+                                    ..Default::default()
+                                },
+                            )?
+                        } else {
+                            pr
+                        }
+                    };
+
+                    match output {
+                        OutputType::Json => print!("{}", pr.show_json()),
+                        _ => print!("{}", pr.show_short()),
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    } else {
+        Err(eyre!("Invalid command!"))
+    }
+}
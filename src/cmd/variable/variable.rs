@@ -0,0 +1,70 @@
+use crate::cmd::{
+    args::{Cli, Commands, OutputType, VariableCommands},
+    config::Configuration,
+    secret::secret::{init_scoped_vcs, read_value},
+};
+use eyre::{eyre, Result};
+use gr_bin::{formatters::formatter::Formatter, vcs::common::Variable};
+use tracing::{info, instrument};
+
+#[instrument(skip_all, fields(command = ?args.command))]
+pub fn variable(args: Cli, conf: Configuration) -> Result<()> {
+    let Cli {
+        command,
+        branch,
+        dir,
+        remote,
+        auth,
+        output,
+        ..
+    } = args;
+    if let Commands::Variable(command) = command {
+        let message = match command {
+            VariableCommands::Set {
+                name,
+                protected,
+                masked,
+                org,
+                host,
+            } => {
+                let (vcs, scope) = init_scoped_vcs(&conf, dir, branch, remote, auth, org, host)?;
+                let value = read_value(&name, false)?;
+                vcs.set_variable(
+                    &scope,
+                    Variable {
+                        name: name.clone(),
+                        value,
+                        protected,
+                        masked,
+                    },
+                )?;
+                format!("Variable {name} is set.")
+            }
+            VariableCommands::List { org, host } => {
+                let (vcs, scope) = init_scoped_vcs(&conf, dir, branch, remote, auth, org, host)?;
+                let variables = vcs.list_variables(&scope)?;
+                for variable in variables {
+                    match output {
+                        OutputType::Json => print!("{}", variable.show_json()),
+                        _ => print!("{}", variable.show_short()),
+                    }
+                }
+                return Ok(());
+            }
+            VariableCommands::Delete { name, org, host } => {
+                let (vcs, scope) = init_scoped_vcs(&conf, dir, branch, remote, auth, org, host)?;
+                vcs.delete_variable(&scope, &name)?;
+                format!("Variable {name} is deleted.")
+            }
+        };
+
+        match output {
+            OutputType::Json => info!("{}", message),
+            _ => println!("{}", message),
+        };
+
+        Ok(())
+    } else {
+        Err(eyre!("Invalid command!"))
+    }
+}
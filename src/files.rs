@@ -0,0 +1,65 @@
+use eyre::{eyre, Context, Result};
+use std::{
+    fs::{remove_file, File},
+    io::{self, BufWriter, Write},
+};
+use tracing::instrument;
+
+fn matches_chars(pattern: &[char], name: &[char], ignore_case: bool) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some('*'), _) => {
+            matches_chars(&pattern[1..], name, ignore_case)
+                || (!name.is_empty() && matches_chars(pattern, &name[1..], ignore_case))
+        }
+        (Some('?'), Some(_)) => matches_chars(&pattern[1..], &name[1..], ignore_case),
+        (Some(p), Some(n)) if p == n || (ignore_case && p.eq_ignore_ascii_case(n)) => {
+            matches_chars(&pattern[1..], &name[1..], ignore_case)
+        }
+        _ => false,
+    }
+}
+
+/// Match a name against a shell-like pattern with * and ? wildcards.
+pub fn matches_pattern(pattern: &str, name: &str, ignore_case: bool) -> bool {
+    matches_chars(
+        &pattern.chars().collect::<Vec<char>>(),
+        &name.chars().collect::<Vec<char>>(),
+        ignore_case,
+    )
+}
+
+/// Write a download to the file, or to the standard output with -, removing partial files.
+#[instrument(skip(download))]
+pub fn save_to_file(
+    path: &str,
+    download: impl FnOnce(&mut dyn Write) -> Result<u64>,
+) -> Result<u64> {
+    if path == "-" {
+        return download(&mut io::stdout().lock());
+    }
+
+    let file = File::create(path).wrap_err(eyre!("Cannot create {path}."))?;
+    let result = download(&mut BufWriter::new(file));
+
+    // Don't leave partial downloads behind
+    if result.is_err() {
+        let _ = remove_file(path);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::matches_pattern;
+
+    #[test]
+    fn matches_patterns() {
+        assert!(matches_pattern("*.zip", "gr-linux.zip", false));
+        assert!(matches_pattern("gr-?.tar.gz", "gr-1.tar.gz", false));
+        assert!(!matches_pattern("*.zip", "gr-linux.tar.gz", false));
+        assert!(!matches_pattern("*.ZIP", "gr-linux.zip", false));
+        assert!(matches_pattern("*.EXAMPLE.com", "git.example.com", true));
+    }
+}
@@ -1,5 +1,8 @@
 use super::utils::to_fixed_length;
-use crate::vcs::common::{PullRequest, PullRequestState, Repository};
+use crate::vcs::common::{
+    BranchProtection, Collaborator, CollaboratorPermission, DeployKey, Issue, IssueState,
+    PullRequest, PullRequestState, Release, Repository, Secret, Variable, Webhook, WebhookEvent,
+};
 use colored::Colorize;
 
 pub enum FormatterType {
@@ -81,6 +84,60 @@ impl Formatter for PullRequest {
     }
 }
 
+impl Formatter for Issue {
+    fn show_json(&self) -> String {
+        serde_json::to_string(&self).unwrap()
+    }
+    fn show_normal(&self) -> String {
+        let title = to_fixed_length(&self.title, TITLE_SIZE, true);
+        let title = match self.state {
+            IssueState::Open => title.bold(),
+            IssueState::Closed => title.bold().red(),
+        };
+        let colored_id = format!("#{}", self.id).dimmed();
+        let title_line = format!("{} {:>width$}", title, colored_id, width = ID_SIZE);
+        let details_line = format!(
+            "{} {} {} {} {} {}",
+            "opened by".dimmed(),
+            self.author
+                .as_ref()
+                .map_or("anonymous", |author| author.username.as_str()),
+            "on".dimmed(),
+            self.created_at.date(),
+            "updated on".dimmed(),
+            self.updated_at.date(),
+        );
+        let labels_line = if !self.labels.is_empty() {
+            format!("{}\n", self.labels.join(", ").yellow())
+        } else {
+            "".to_string()
+        };
+        let description = if !self.description.is_empty() {
+            format!("\n{}\n---", self.description)
+        } else {
+            "".to_string()
+        };
+        let url_line = format!("{}", self.url.dimmed());
+
+        format!(
+            "{title_line}
+{details_line}
+{labels_line}{description}
+{url_line}
+"
+        )
+    }
+    fn show_short(&self) -> String {
+        let title = to_fixed_length(&self.title, TITLE_SIZE, true);
+        let title = match self.state {
+            IssueState::Open => title.bold(),
+            IssueState::Closed => title.bold().red(),
+        };
+        let colored_id = format!("#{}", self.id).dimmed();
+        format!("{} {:>6}\n", title, colored_id)
+    }
+}
+
 impl Formatter for Repository {
     fn show_json(&self) -> String {
         serde_json::to_string(&self).unwrap()
@@ -117,3 +174,227 @@ impl Formatter for Repository {
         format!("{}\n", title)
     }
 }
+
+impl Formatter for BranchProtection {
+    fn show_json(&self) -> String {
+        serde_json::to_string(&self).unwrap()
+    }
+    fn show_normal(&self) -> String {
+        let title_line = to_fixed_length(&self.branch, TITLE_SIZE, true).bold();
+        let approvals_line = format!(
+            "{} {}",
+            self.required_approvals.to_string().yellow(),
+            "required approvals".dimmed()
+        );
+        let checks_line = if self.required_status_checks.is_empty() {
+            format!("{}", "no required status checks".dimmed())
+        } else {
+            format!(
+                "{} {}",
+                "required status checks:".dimmed(),
+                self.required_status_checks.join(", ")
+            )
+        };
+        let force_push_line = if self.allow_force_push {
+            "force push allowed".red()
+        } else {
+            "force push blocked".green()
+        };
+        let linear_history_line = if self.linear_history {
+            "linear history required".green()
+        } else {
+            "linear history not required".dimmed()
+        };
+
+        format!(
+            "{title_line}
+{approvals_line}
+{checks_line}
+{force_push_line}
+{linear_history_line}
+"
+        )
+    }
+    fn show_short(&self) -> String {
+        let branch = to_fixed_length(&self.branch, SHORT_BRANCH_SIZE, true).blue();
+        let force_push = if self.allow_force_push {
+            "force push allowed".red()
+        } else {
+            "force push blocked".green()
+        };
+        format!(
+            "{} {} approvals, {} checks, {}{}\n",
+            branch,
+            self.required_approvals,
+            self.required_status_checks.len(),
+            force_push,
+            if self.linear_history {
+                ", linear history"
+            } else {
+                ""
+            }
+        )
+    }
+}
+
+impl Formatter for Collaborator {
+    fn show_json(&self) -> String {
+        serde_json::to_string(&self).unwrap()
+    }
+    fn show_normal(&self) -> String {
+        self.show_short()
+    }
+    fn show_short(&self) -> String {
+        let username = to_fixed_length(&self.user.username, SHORT_TITLE_SIZE, true).bold();
+        let permission = match self.permission {
+            CollaboratorPermission::Read => "read".normal(),
+            CollaboratorPermission::Write => "write".yellow(),
+            CollaboratorPermission::Admin => "admin".red(),
+        };
+        format!("{} {}\n", username, permission)
+    }
+}
+
+impl Formatter for Webhook {
+    fn show_json(&self) -> String {
+        serde_json::to_string(&self).unwrap()
+    }
+    fn show_normal(&self) -> String {
+        self.show_short()
+    }
+    fn show_short(&self) -> String {
+        let events = self
+            .events
+            .iter()
+            .map(|event| match event {
+                WebhookEvent::Push => "push",
+                WebhookEvent::PullRequest => "pull_request",
+                WebhookEvent::Tag => "tag",
+                WebhookEvent::Release => "release",
+                WebhookEvent::Issue => "issue",
+            })
+            .collect::<Vec<&str>>()
+            .join(",");
+        let url = to_fixed_length(&self.url, SHORT_TITLE_SIZE, true);
+        let url = if self.active {
+            url.bold()
+        } else {
+            url.dimmed()
+        };
+        format!(
+            "{} {} {}\n",
+            url,
+            to_fixed_length(&events, SHORT_BRANCH_SIZE, true).blue(),
+            self.id.dimmed()
+        )
+    }
+}
+
+impl Formatter for DeployKey {
+    fn show_json(&self) -> String {
+        serde_json::to_string(&self).unwrap()
+    }
+    fn show_normal(&self) -> String {
+        self.show_short()
+    }
+    fn show_short(&self) -> String {
+        let title = to_fixed_length(&self.title, SHORT_TITLE_SIZE, true).bold();
+        let access = if self.read_only {
+            to_fixed_length("read-only", SHORT_BRANCH_SIZE, true).normal()
+        } else {
+            to_fixed_length("read-write", SHORT_BRANCH_SIZE, true).yellow()
+        };
+        format!("{} {} {}\n", title, access, self.id.dimmed())
+    }
+}
+
+impl Formatter for Secret {
+    fn show_json(&self) -> String {
+        serde_json::to_string(&self).unwrap()
+    }
+    fn show_normal(&self) -> String {
+        self.show_short()
+    }
+    fn show_short(&self) -> String {
+        format!("{}\n", self.name.bold())
+    }
+}
+
+impl Formatter for Variable {
+    fn show_json(&self) -> String {
+        serde_json::to_string(&self).unwrap()
+    }
+    fn show_normal(&self) -> String {
+        self.show_short()
+    }
+    fn show_short(&self) -> String {
+        let value = if self.masked {
+            "********".dimmed()
+        } else {
+            to_fixed_length(&self.value, SHORT_TITLE_SIZE, true).normal()
+        };
+        let flags = [(self.protected, "protected"), (self.masked, "masked")]
+            .into_iter()
+            .filter_map(|(enabled, flag)| enabled.then_some(flag))
+            .collect::<Vec<&str>>()
+            .join(", ");
+        format!(
+            "{} {} {}\n",
+            to_fixed_length(&self.name, SHORT_BRANCH_SIZE, true).bold(),
+            value,
+            flags.dimmed()
+        )
+    }
+}
+
+impl Formatter for Release {
+    fn show_json(&self) -> String {
+        serde_json::to_string(&self).unwrap()
+    }
+    fn show_normal(&self) -> String {
+        let title_line = to_fixed_length(&self.name, TITLE_SIZE, true).bold();
+        let flags = [(self.draft, "draft"), (self.prerelease, "prerelease")]
+            .into_iter()
+            .filter_map(|(enabled, flag)| enabled.then_some(flag))
+            .collect::<Vec<&str>>()
+            .join(", ");
+        let tag_line = format!(
+            "{} {} {}",
+            self.tag.blue(),
+            self.created_at.date().to_string().dimmed(),
+            flags.yellow()
+        );
+        let description = if !self.description.is_empty() {
+            format!("\n{}\n---", self.description)
+        } else {
+            "".to_string()
+        };
+        let assets = self
+            .assets
+            .iter()
+            .map(|asset| format!("{} {}\n", "-".dimmed(), asset.name))
+            .collect::<String>();
+        let url_line = format!("{}", self.url.dimmed());
+
+        format!(
+            "{title_line}
+{tag_line}
+{description}
+{assets}{url_line}
+"
+        )
+    }
+    fn show_short(&self) -> String {
+        let title = to_fixed_length(&self.name, SHORT_TITLE_SIZE, true);
+        let title = if self.draft {
+            title.dimmed()
+        } else {
+            title.bold()
+        };
+        format!(
+            "{} {}\n",
+            title,
+            to_fixed_length(&self.tag, SHORT_BRANCH_SIZE, true).blue()
+        )
+    }
+}
@@ -0,0 +1,144 @@
+use crate::git::template::fill_template;
+use eyre::{eyre, Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct BranchRule {
+    /// Regular expression of the branch name, like ^feature/(?P<issue>[A-Z]+-\d+)-(?P<name>.+)$
+    pub pattern: String,
+    /// Title of the pull request, {{name}} or {{1}} is replaced by the captured group
+    pub title: Option<String>,
+    /// Labels added to the pull request
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Target branch of the pull request, placeholders are replaced as in the title
+    pub target: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BranchRuleMatch {
+    pub title: Option<String>,
+    pub labels: Vec<String>,
+    pub target: Option<String>,
+}
+
+// Turn words of a branch like add-login into a sentence like Add login, keep keys like PROJ-123
+fn humanize(words: &str) -> String {
+    if !words.chars().any(|c| c.is_lowercase()) {
+        return words.to_string();
+    }
+
+    let sentence = words
+        .split(['-', '_'])
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<&str>>()
+        .join(" ");
+    let mut chars = sentence.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => sentence,
+    }
+}
+
+/// Find the first rule matching the branch, and fill its templates with the captured groups.
+pub fn match_branch(rules: &[BranchRule], branch: &str) -> Result<Option<BranchRuleMatch>> {
+    for rule in rules {
+        let regex = Regex::new(&rule.pattern)
+            .wrap_err(eyre!("Invalid branch rule pattern {}.", rule.pattern))?;
+        let Some(captures) = regex.captures(branch) else {
+            continue;
+        };
+
+        // Groups can be used by their index or by their name
+        let mut groups: Vec<(String, &str)> = vec![];
+        for (i, name) in regex.capture_names().enumerate() {
+            let Some(value) = captures.get(i).map(|m| m.as_str()) else {
+                continue;
+            };
+            groups.push((i.to_string(), value));
+            if let Some(name) = name {
+                groups.push((name.to_string(), value));
+            }
+        }
+        let humanized = groups
+            .iter()
+            .map(|(key, value)| (key.as_str(), humanize(value)))
+            .collect::<Vec<(&str, String)>>();
+        let humanized = humanized
+            .iter()
+            .map(|(key, value)| (*key, value.as_str()))
+            .collect::<Vec<(&str, &str)>>();
+        let raw = groups
+            .iter()
+            .map(|(key, value)| (key.as_str(), *value))
+            .collect::<Vec<(&str, &str)>>();
+
+        return Ok(Some(BranchRuleMatch {
+            title: rule
+                .title
+                .as_ref()
+                .map(|title| fill_template(title, &humanized)),
+            labels: rule.labels.clone(),
+            target: rule
+                .target
+                .as_ref()
+                .map(|target| fill_template(target, &raw)),
+        }));
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{humanize, match_branch, BranchRule, BranchRuleMatch};
+
+    #[test]
+    fn humanizes_branch_words() {
+        assert_eq!(humanize("add-login"), "Add login");
+        assert_eq!(humanize("fix__the_build-"), "Fix the build");
+        assert_eq!(humanize("PROJ-123"), "PROJ-123");
+        assert_eq!(humanize(""), "");
+    }
+
+    #[test]
+    fn matches_the_first_rule() {
+        let rules = vec![
+            BranchRule {
+                pattern: r"^feature/(?P<issue>[A-Z]+-\d+)-(?P<name>.+)$".to_string(),
+                title: Some("{{issue}}: {{name}}".to_string()),
+                labels: vec!["feature".to_string()],
+                target: Some("develop".to_string()),
+            },
+            BranchRule {
+                pattern: r"^hotfix/(\d+\.\d+)/".to_string(),
+                target: Some("release/{{1}}".to_string()),
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(
+            match_branch(&rules, "feature/PROJ-123-add-login").unwrap(),
+            Some(BranchRuleMatch {
+                title: Some("PROJ-123: Add login".to_string()),
+                labels: vec!["feature".to_string()],
+                target: Some("develop".to_string()),
+            })
+        );
+        assert_eq!(
+            match_branch(&rules, "hotfix/1.2/crash").unwrap(),
+            Some(BranchRuleMatch {
+                target: Some("release/1.2".to_string()),
+                ..Default::default()
+            })
+        );
+        assert_eq!(match_branch(&rules, "main").unwrap(), None);
+
+        let invalid = vec![BranchRule {
+            pattern: "(".to_string(),
+            ..Default::default()
+        }];
+        assert!(match_branch(&invalid, "main").is_err());
+    }
+}
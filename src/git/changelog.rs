@@ -0,0 +1,335 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ChangelogOptions {
+    /// Group the commits by their Conventional Commit type, otherwise list them as they are
+    pub group_commits: bool,
+    /// Link the references with issue keys to this URL, {} is replaced by the reference like PROJ-123
+    pub issue_url: Option<String>,
+    /// Project keys of Jira-style references like PROJ-123, only these keys are references
+    pub issue_keys: Vec<String>,
+}
+
+impl Default for ChangelogOptions {
+    fn default() -> Self {
+        ChangelogOptions {
+            group_commits: true,
+            issue_url: None,
+            issue_keys: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Section {
+    Breaking,
+    Features,
+    Fixes,
+    Other,
+}
+
+impl Section {
+    fn title(&self) -> &str {
+        match self {
+            Section::Breaking => "Breaking changes",
+            Section::Features => "Features",
+            Section::Fixes => "Bug fixes",
+            Section::Other => "Other changes",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct ConventionalCommit<'a> {
+    kind: &'a str,
+    scope: Option<&'a str>,
+    breaking: bool,
+    description: &'a str,
+}
+
+// Parse a subject like feat(parser)!: add arrays
+fn parse_subject(subject: &str) -> Option<ConventionalCommit<'_>> {
+    let (header, description) = subject.split_once(": ")?;
+    let (header, breaking) = match header.strip_suffix('!') {
+        Some(header) => (header, true),
+        None => (header, false),
+    };
+    let (kind, scope) = match header.split_once('(') {
+        Some((kind, scope)) => (kind, Some(scope.strip_suffix(')')?)),
+        None => (header, None),
+    };
+    if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    Some(ConventionalCommit {
+        kind,
+        scope,
+        breaking,
+        description: description.trim(),
+    })
+}
+
+// Issue references are like #123 (GitHub, GitLab, Gitea) or PROJ-123 (Jira, with a configured key),
+// words like UTF-8 or SHA-256 look the same, so keys are not guessed
+fn is_issue(word: &str, keys: &[String]) -> bool {
+    let number = if let Some(number) = word.strip_prefix('#') {
+        number
+    } else if let Some((key, number)) = word.rsplit_once('-') {
+        if !keys.iter().any(|k| k == key) {
+            return false;
+        }
+        number
+    } else {
+        return false;
+    };
+
+    !number.is_empty() && number.chars().all(|c| c.is_ascii_digit())
+}
+
+// Find the byte ranges of the issue references in a text
+fn find_issue_ranges(text: &str, keys: &[String]) -> Vec<(usize, usize)> {
+    let mut ranges = vec![];
+    let mut start = 0;
+    for (i, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        if c.is_ascii_alphanumeric() || c == '#' || c == '-' {
+            continue;
+        }
+        let word = &text[start..i];
+        let trimmed = word.trim_matches('-');
+        if is_issue(trimmed, keys) {
+            let offset = start + word.len() - word.trim_start_matches('-').len();
+            ranges.push((offset, offset + trimmed.len()));
+        }
+        start = i + c.len_utf8();
+    }
+
+    ranges
+}
+
+fn find_issues<'a>(text: &'a str, keys: &[String]) -> Vec<&'a str> {
+    find_issue_ranges(text, keys)
+        .into_iter()
+        .map(|(start, end)| &text[start..end])
+        .collect()
+}
+
+// References like #123 are left as they are, the forge links them to its own issues
+fn link_issue(issue: &str, options: &ChangelogOptions) -> String {
+    match &options.issue_url {
+        Some(url) if !issue.starts_with('#') => format!("[{issue}]({})", url.replace("{}", issue)),
+        _ => issue.to_string(),
+    }
+}
+
+fn format_entry(description: &str, issues: &[&str], options: &ChangelogOptions) -> String {
+    // Link the references in the text, and add the ones from the footers at the end
+    let mut entry = String::new();
+    let mut last = 0;
+    for (start, end) in find_issue_ranges(description, &options.issue_keys) {
+        entry.push_str(&description[last..start]);
+        entry.push_str(&link_issue(&description[start..end], options));
+        last = end;
+    }
+    entry.push_str(&description[last..]);
+
+    let inline_issues = find_issues(description, &options.issue_keys);
+    let mut footer_issues: Vec<String> = vec![];
+    for issue in issues {
+        let issue = link_issue(issue, options);
+        if !inline_issues
+            .iter()
+            .any(|i| link_issue(i, options) == issue)
+            && !footer_issues.contains(&issue)
+        {
+            footer_issues.push(issue);
+        }
+    }
+    if !footer_issues.is_empty() {
+        entry = format!("{entry} ({})", footer_issues.join(", "));
+    }
+
+    entry
+}
+
+/// Generate a Markdown changelog from full commit messages, grouped by Conventional Commit types.
+pub fn generate_changelog(messages: &[String], options: &ChangelogOptions) -> String {
+    let mut entries: Vec<(Section, String)> = vec![];
+    let mut is_conventional = false;
+    for message in messages {
+        let mut lines = message.lines();
+        let subject = lines.next().unwrap_or_default().trim();
+        if subject.is_empty() {
+            continue;
+        }
+        let body = lines.collect::<Vec<&str>>();
+
+        // Only the footers are checked for references, to skip mentions in the body
+        let issues = body
+            .iter()
+            .filter(|line| {
+                let line = line.to_lowercase();
+                ["closes", "fixes", "resolves", "refs"]
+                    .iter()
+                    .any(|keyword| line.starts_with(keyword))
+            })
+            .flat_map(|line| find_issues(line, &options.issue_keys))
+            .collect::<Vec<&str>>();
+
+        let entry = match parse_subject(subject).filter(|_| options.group_commits) {
+            Some(commit) => {
+                is_conventional = true;
+                let breaking = commit.breaking
+                    || body.iter().any(|line| {
+                        line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+                    });
+                let section = match commit.kind.to_lowercase().as_str() {
+                    _ if breaking => Section::Breaking,
+                    "feat" => Section::Features,
+                    "fix" => Section::Fixes,
+                    _ => Section::Other,
+                };
+                let description = match commit.scope {
+                    Some(scope) => format!("**{scope}:** {}", commit.description),
+                    None => commit.description.to_string(),
+                };
+                (section, format_entry(&description, &issues, options))
+            }
+            None => (Section::Other, format_entry(subject, &issues, options)),
+        };
+        entries.push(entry);
+    }
+
+    // Without any Conventional Commits the headings would only add noise
+    if !is_conventional {
+        return entries
+            .into_iter()
+            .map(|(_, entry)| format!("- {entry}\n"))
+            .collect();
+    }
+
+    entries.sort_by_key(|(section, _)| *section);
+    let mut changelog = String::new();
+    let mut current = None;
+    for (section, entry) in entries {
+        if current != Some(section) {
+            if current.is_some() {
+                changelog.push('\n');
+            }
+            changelog.push_str(&format!("### {}\n\n", section.title()));
+            current = Some(section);
+        }
+        changelog.push_str(&format!("- {entry}\n"));
+    }
+
+    changelog
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_issues, generate_changelog, parse_subject, ChangelogOptions};
+
+    fn messages(messages: &[&str]) -> Vec<String> {
+        messages.iter().map(|m| m.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_conventional_subjects() {
+        let commit = parse_subject("feat(parser)!: add arrays").unwrap();
+        assert_eq!(commit.kind, "feat");
+        assert_eq!(commit.scope, Some("parser"));
+        assert!(commit.breaking);
+        assert_eq!(commit.description, "add arrays");
+
+        assert!(parse_subject("fix: handle empty input").is_some());
+        assert!(parse_subject("Merge branch 'main'").is_none());
+        assert!(parse_subject("Update README: typo").is_none());
+    }
+
+    #[test]
+    fn finds_issue_references() {
+        let keys = vec!["PROJ".to_string()];
+        assert_eq!(
+            find_issues("fix login (#12), see PROJ-34 and gh-5", &keys),
+            vec!["#12", "PROJ-34"]
+        );
+        assert_eq!(
+            find_issues("fix login (#12), see PROJ-34", &[]),
+            vec!["#12"]
+        );
+        assert!(find_issues("update to utf-8 and x86-64", &keys).is_empty());
+        assert!(find_issues("decode UTF-8, hash with SHA-256 and AES-128", &keys).is_empty());
+        assert!(find_issues("decode UTF-8, hash with SHA-256", &[]).is_empty());
+        assert_eq!(find_issues("--#1, #12--", &keys), vec!["#1", "#12"]);
+    }
+
+    #[test]
+    fn groups_commits_by_type() {
+        let changelog = generate_changelog(
+            &messages(&[
+                "chore: update dependencies",
+                "fix(auth): refresh expired tokens\n\nCloses #7",
+                "feat: add releases",
+                "feat!: drop the old config format",
+                "refactor: split the parser\n\nBREAKING CHANGE: the parser is a new module",
+            ]),
+            &ChangelogOptions::default(),
+        );
+
+        assert_eq!(
+            changelog,
+            "### Breaking changes
+
+- drop the old config format
+- split the parser
+
+### Features
+
+- add releases
+
+### Bug fixes
+
+- **auth:** refresh expired tokens (#7)
+
+### Other changes
+
+- update dependencies
+"
+        );
+    }
+
+    #[test]
+    fn lists_plain_commits_and_links_issues() {
+        let options = ChangelogOptions {
+            issue_url: Some("https://jira.example.com/browse/{}".to_string()),
+            issue_keys: vec!["PROJ".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            generate_changelog(
+                &messages(&[
+                    "PROJ-1 Add login",
+                    "Fix typo (#1, #12)\n\nFixes #12, #3",
+                    "Decode UTF-8 names",
+                ]),
+                &options
+            ),
+            "- [PROJ-1](https://jira.example.com/browse/PROJ-1) Add login
+- Fix typo (#1, #12) (#3)
+- Decode UTF-8 names
+"
+        );
+        assert_eq!(
+            generate_changelog(
+                &messages(&["feat: add login"]),
+                &ChangelogOptions {
+                    group_commits: false,
+                    ..Default::default()
+                }
+            ),
+            "- feat: add login\n"
+        );
+    }
+}
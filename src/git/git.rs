@@ -1,4 +1,4 @@
-use crate::git::url::parse_url;
+use crate::git::url::{parse_url, rewrite_url};
 use eyre::{eyre, Context, ContextCompat, Result};
 use std::{
     env,
@@ -7,8 +7,12 @@ use std::{
 };
 use tracing::{debug, info, instrument};
 
+// Finds the remote to use for branches without an upstream, before falling back to origin
+type FindRemote = Box<dyn Fn(&LocalRepository) -> Option<String>>;
+
 pub struct LocalRepository {
     path: String,
+    pinned_remote: Option<FindRemote>,
 }
 
 impl LocalRepository {
@@ -22,7 +26,20 @@ impl LocalRepository {
         info!("Repository directory is {}.", path.to_string_lossy());
 
         let path = path.into_os_string().into_string().unwrap();
-        Ok(LocalRepository { path })
+        Ok(LocalRepository {
+            path,
+            pinned_remote: None,
+        })
+    }
+
+    pub fn with_pinned_remote(
+        self,
+        find: impl Fn(&LocalRepository) -> Option<String> + 'static,
+    ) -> LocalRepository {
+        LocalRepository {
+            pinned_remote: Some(Box::new(find)),
+            ..self
+        }
     }
 
     #[instrument(skip_all)]
@@ -73,6 +90,36 @@ impl LocalRepository {
         self.run(vec!["remote"], false)
     }
 
+    #[instrument(skip(self))]
+    fn get_config(self: &LocalRepository, key: &str) -> Option<String> {
+        self.run(vec!["config", key], false)
+            .ok()
+            .and_then(|lines| lines.into_iter().next())
+    }
+
+    #[instrument(skip(self))]
+    pub fn get_default_remote(self: &LocalRepository) -> Result<String> {
+        // The pinned remote is only looked up here, as it has to parse every remote
+        if let Some(remote) = self.pinned_remote.as_ref().and_then(|find| find(self)) {
+            debug!("Using pinned remote {remote}.");
+            return Ok(remote);
+        }
+
+        // Prefer origin, don't depend on the alphabetical order of remotes
+        let remotes = self.get_remotes()?;
+        if remotes.iter().any(|r| r == "origin") {
+            return Ok("origin".to_string());
+        }
+        match remotes.as_slice() {
+            [] => Err(eyre!("Repository doesn't have any remotes.")),
+            [remote] => Ok(remote.clone()),
+            _ => Err(eyre!(
+                "Repository has multiple remotes ({}), select one with --remote.",
+                remotes.join(", ")
+            )),
+        }
+    }
+
     #[instrument(skip(self))]
     pub fn set_remote(self: &LocalRepository, name: String, url: String) -> Result<()> {
         let existing_remotes = self.get_remotes()?;
@@ -85,6 +132,69 @@ impl LocalRepository {
         Ok(())
     }
 
+    #[instrument(skip(self))]
+    fn split_remote_url(
+        self: &LocalRepository,
+        remote_name: &str,
+        from: &str,
+    ) -> Result<(String, String)> {
+        // Keep the configured URL with its aliases, unless an insteadOf alias hides the path
+        let configured = self
+            .get_config(&format!("remote.{remote_name}.url"))
+            .wrap_err(eyre!("Cannot get URL for {remote_name}."))?;
+        let resolved = self.get_remote_url(remote_name)?;
+        let parts = [&configured, &resolved]
+            .into_iter()
+            .find_map(|url| url.rsplit_once(from))
+            .map(|(start, end)| (start.to_string(), end.to_string()))
+            .wrap_err(eyre!(
+                "Remote {remote_name} ({resolved}) does not point to {from}."
+            ));
+
+        parts
+    }
+
+    #[instrument(skip(self))]
+    pub fn check_move_remote(self: &LocalRepository, remote_name: &str, from: &str) -> Result<()> {
+        // Used before moving the repository on the server, as the remote can't be fixed later
+        self.split_remote_url(remote_name, from).map(|_| ())
+    }
+
+    #[instrument(skip(self))]
+    pub fn move_remote(
+        self: &LocalRepository,
+        remote_name: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<String> {
+        // Only replace the repository path, to keep the protocol and the port
+        let (start, end) = self.split_remote_url(remote_name, from)?;
+        let url = format!("{start}{to}{end}");
+        self.set_remote(remote_name.to_string(), url.clone())?;
+
+        Ok(url)
+    }
+
+    #[instrument(skip(self))]
+    pub fn rewrite_url(self: &LocalRepository, url: &str) -> String {
+        // Apply the url.<base>.insteadOf rules, remote URLs from git are already rewritten
+        let rewrites = self
+            .run(
+                vec!["config", "--get-regexp", r"^url\..*\.insteadof$"],
+                false,
+            )
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|line| {
+                let (key, prefix) = line.split_once(' ')?;
+                let base = key.strip_prefix("url.")?.strip_suffix(".insteadof")?;
+                Some((base.to_string(), prefix.to_string()))
+            })
+            .collect::<Vec<(String, String)>>();
+
+        rewrite_url(url, &rewrites)
+    }
+
     #[instrument(skip_all)]
     pub fn get_branch(self: &LocalRepository) -> Result<String> {
         let head = self
@@ -103,6 +213,7 @@ impl LocalRepository {
     pub fn get_branch_upstream(
         self: &LocalRepository,
         branch_name: Option<String>,
+        remote_name: Option<String>,
     ) -> Result<(String, Option<String>)> {
         let branch_name = if let Some(branch_name) = branch_name {
             branch_name
@@ -110,6 +221,22 @@ impl LocalRepository {
             self.get_branch()?
         };
 
+        // If the remote is given, the branch is pushed if it has a remote-tracking branch there
+        if let Some(remote_name) = remote_name {
+            let is_pushed = self
+                .run(
+                    vec![
+                        "rev-parse",
+                        "--verify",
+                        "--quiet",
+                        &format!("refs/remotes/{remote_name}/{branch_name}"),
+                    ],
+                    false,
+                )
+                .is_ok();
+            return Ok((remote_name, is_pushed.then_some(branch_name)));
+        }
+
         // Get remote name for branch
         self.run(
             vec!["config", &format!("branch.{branch_name}.remote")],
@@ -125,17 +252,10 @@ impl LocalRepository {
                     "Branch {branch_name} doesn't have an upstream branch.",
                 ))
         })
-        // If there are no remotes (branch hasn't been pushed), fallback to the list of branches
+        // If there are no remotes (branch hasn't been pushed), fallback to the default remote
         .or_else(|_| {
-            debug!("There is no remote for {branch_name}, falling back to first remote.");
-            self.get_remotes().and_then(|remotes| {
-                // Fall back to first remote
-                remotes
-                    .into_iter()
-                    .next()
-                    .map(|remote| (remote, None))
-                    .wrap_err(eyre!("Repository doesn't have any origin branches."))
-            })
+            debug!("There is no remote for {branch_name}, falling back to default remote.");
+            self.get_default_remote().map(|remote| (remote, None))
         })
     }
 
@@ -151,26 +271,53 @@ impl LocalRepository {
             .wrap_err("Cannot get commit SHA for the branch {branch}.")
     }
 
+    #[instrument(skip(self))]
+    pub fn get_remote_url(self: &LocalRepository, remote_name: &str) -> Result<String> {
+        self.run(vec!["remote", "get-url", remote_name], false)
+            .wrap_err(eyre!("Cannot get remote for {remote_name}."))?
+            .into_iter()
+            .next()
+            .wrap_err(eyre!("Cannot get URL for {remote_name}."))
+    }
+
     #[instrument(skip(self))]
     pub fn get_parsed_remote(
         self: &LocalRepository,
         branch_name: Option<String>,
+        remote_name: Option<String>,
     ) -> Result<(String, String, Option<String>)> {
-        let (remote_name, branch_name) = self.get_branch_upstream(branch_name)?;
+        let (remote_name, branch_name) = self.get_branch_upstream(branch_name, remote_name)?;
 
         // Find remote URL
-        let remote_url = self
-            .run(vec!["remote", "get-url", &remote_name], false)
-            .wrap_err(eyre!("Cannot get remote for {remote_name}."))?
-            .into_iter()
-            .next()
-            .wrap_err(eyre!("Cannot get URL for {remote_name}."))?;
+        let remote_url = self.get_remote_url(&remote_name)?;
 
         info!("Using remote {remote_name} with url {remote_url}.");
 
         parse_url(&remote_url).map(|(host, repo)| (host, repo, branch_name))
     }
 
+    #[instrument(skip(self))]
+    pub fn get_parsed_upstream(
+        self: &LocalRepository,
+        hostname: &str,
+        repo: &str,
+        upstream: &str,
+    ) -> Result<Option<String>> {
+        // In fork workflows we push to origin, but pull requests go to the upstream remote
+        if !self.get_remotes()?.iter().any(|remote| remote == upstream) {
+            return Ok(None);
+        }
+
+        let (upstream_host, upstream_repo) = parse_url(&self.get_remote_url(upstream)?)?;
+        if upstream_host != hostname || upstream_repo == repo {
+            debug!("Remote {upstream} is not an upstream of {repo}.");
+            return Ok(None);
+        }
+
+        info!("Using upstream repository {upstream_repo} for pull requests.");
+        Ok(Some(upstream_repo))
+    }
+
     #[instrument(skip(self))]
     pub fn delete_branch(self: &LocalRepository, branch_name: String) -> Result<()> {
         self.run(vec!["branch", "-d", &branch_name], false)?;
@@ -205,6 +352,40 @@ impl LocalRepository {
         Ok(messages)
     }
 
+    #[instrument(skip(self))]
+    pub fn get_commit_messages(
+        self: &LocalRepository,
+        from: Option<&str>,
+        to: &str,
+    ) -> Result<Vec<String>> {
+        let range = match from {
+            Some(from) => format!("{from}..{to}"),
+            None => to.to_string(),
+        };
+
+        // Full messages span multiple lines, separate them with a record separator
+        let lines = self
+            .run(vec!["log", &range, "--pretty=%B%x1e"], false)
+            .wrap_err(eyre!("Cannot read the commits of {range}."))?;
+
+        Ok(lines
+            .join("\n")
+            .split('\x1e')
+            .map(|message| message.trim().to_string())
+            .filter(|message| !message.is_empty())
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    pub fn get_previous_tag(self: &LocalRepository, reference: &str) -> Option<String> {
+        self.run(
+            vec!["describe", "--tags", "--abbrev=0", &format!("{reference}^")],
+            false,
+        )
+        .ok()
+        .and_then(|lines| lines.into_iter().next())
+    }
+
     #[instrument(skip(self))]
     pub fn checkout_remote_branch(
         self: &LocalRepository,
@@ -228,20 +409,307 @@ impl LocalRepository {
     }
 
     #[instrument(skip(self))]
-    pub fn clone(self: &LocalRepository, url: String, dir: Option<String>) -> Result<()> {
-        if let Some(dir) = dir {
-            if Path::new(&dir).exists() {
-                // If the path exists, we have to clone inside of it
-                self.run(vec!["clone", &url], true)
-            } else {
-                // Otherwise we have to reinitialize the repository to allow cloning into empty repo
-                LocalRepository::init(None)?.run(vec!["clone", &url, &dir], true)
+    pub fn force_push_with_lease(
+        self: &LocalRepository,
+        remote: &str,
+        branch: &str,
+        remote_branch: &str,
+    ) -> Result<()> {
+        self.run(
+            vec![
+                "push",
+                "--force-with-lease",
+                "-u",
+                remote,
+                &format!("{branch}:{remote_branch}"),
+            ],
+            true,
+        )
+        .wrap_err(eyre!(
+            "Could not force push {branch} to {remote_branch} on {remote}"
+        ))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    pub fn push_to_ref(
+        self: &LocalRepository,
+        remote: &str,
+        branch: &str,
+        target_ref: &str,
+    ) -> Result<()> {
+        self.run(
+            vec!["push", remote, &format!("{branch}:{target_ref}")],
+            true,
+        )
+        .wrap_err(eyre!("Could not push {branch} to {target_ref} on {remote}"))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    pub fn force_push_to_ref(
+        self: &LocalRepository,
+        remote: &str,
+        branch: &str,
+        target_ref: &str,
+    ) -> Result<()> {
+        self.run(
+            vec!["push", "--force", remote, &format!("{branch}:{target_ref}")],
+            true,
+        )
+        .wrap_err(eyre!(
+            "Could not force push {branch} to {target_ref} on {remote}"
+        ))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    pub fn create_tag(self: &LocalRepository, tag: &str, target: Option<String>) -> Result<()> {
+        let target = target.unwrap_or("HEAD".to_string());
+        self.run(vec!["tag", tag, &target], false)
+            .wrap_err(eyre!("Could not create tag {tag} on {target}."))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    pub fn fetch(self: &LocalRepository, remote: &str, branch: &str) -> Result<()> {
+        self.run(
+            vec![
+                "fetch",
+                remote,
+                &format!("refs/heads/{branch}:refs/remotes/{remote}/{branch}"),
+            ],
+            false,
+        )
+        .wrap_err(eyre!("Could not fetch {branch} from {remote}."))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    pub fn rebase(self: &LocalRepository, onto: &str) -> Result<()> {
+        self.run(vec!["rebase", onto], true).wrap_err(eyre!(
+            "Could not rebase on {onto}, resolve the conflicts and run git rebase --continue."
+        ))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    pub fn merge(self: &LocalRepository, reference: &str) -> Result<()> {
+        self.run(vec!["merge", "--no-edit", reference], true)
+            .wrap_err(eyre!(
+                "Could not merge {reference}, resolve the conflicts and commit the merge."
+            ))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    pub fn reset_branch(self: &LocalRepository, branch: &str, reference: &str) -> Result<()> {
+        // The checked out branch keeps the local modifications, other branches are just moved
+        if self.get_branch().ok().as_deref() == Some(branch) {
+            self.run(vec!["reset", "--keep", reference], false)
+        } else {
+            self.run(vec!["branch", "--force", branch, reference], false)
+        }
+        .wrap_err(eyre!("Could not reset {branch} to {reference}."))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    pub fn count_commits(self: &LocalRepository, from: &str, to: &str) -> Result<usize> {
+        self.run(vec!["rev-list", "--count", &format!("{from}..{to}")], false)?
+            .into_iter()
+            .next()
+            .and_then(|count| count.parse().ok())
+            .wrap_err(eyre!("Cannot count commits between {from} and {to}."))
+    }
+
+    #[instrument(skip(self))]
+    pub fn get_branches(self: &LocalRepository) -> Result<Vec<String>> {
+        self.run(
+            vec!["for-each-ref", "--format=%(refname:short)", "refs/heads"],
+            false,
+        )
+    }
+
+    #[instrument(skip(self))]
+    pub fn is_ancestor(self: &LocalRepository, ancestor: &str, descendant: &str) -> bool {
+        self.run(
+            vec!["merge-base", "--is-ancestor", ancestor, descendant],
+            false,
+        )
+        .is_ok()
+    }
+
+    #[instrument(skip(self))]
+    pub fn get_stack(self: &LocalRepository, base: &str, branch: &str) -> Result<Vec<String>> {
+        if self.count_commits(base, branch)? == 0 {
+            return Ok(vec![]);
+        }
+
+        // The stack is every local branch below or above the branch that is not in the base yet
+        let mut stack = vec![];
+        for name in self.get_branches()? {
+            if name != branch
+                && !self.is_ancestor(&name, branch)
+                && !self.is_ancestor(branch, &name)
+            {
+                continue;
+            }
+            let count = self.count_commits(base, &name)?;
+            if count > 0 {
+                stack.push((count, name));
             }
+        }
+        stack.sort();
+
+        // Each branch has to be based on the previous one
+        for pair in stack.windows(2) {
+            let ((count, parent), (child_count, child)) = (&pair[0], &pair[1]);
+            if count == child_count || !self.is_ancestor(parent, child) {
+                return Err(eyre!(
+                    "Branches {parent} and {child} are not based on each other, the stack has to be a single chain."
+                ));
+            }
+        }
+
+        Ok(stack.into_iter().map(|(_, name)| name).collect())
+    }
+
+    #[instrument(skip(self))]
+    pub fn get_change_id(self: &LocalRepository, branch_name: Option<String>) -> Result<String> {
+        let branch_name = if let Some(branch_name) = branch_name {
+            branch_name
         } else {
-            self.run(vec!["clone", &url], true)
+            self.get_branch()?
+        };
+
+        // Read the Change-Id trailer from the last commit (added by the Gerrit commit-msg hook)
+        self.run(
+            vec![
+                "log",
+                "-1",
+                "--format=%(trailers:key=Change-Id,valueonly)",
+                &branch_name,
+            ],
+            false,
+        )?
+        .into_iter()
+        .map(|line| line.trim().to_string())
+        .find(|line| !line.is_empty())
+        .wrap_err(eyre!(
+            "The last commit on {branch_name} doesn't have a Change-Id, install the commit-msg hook."
+        ))
+    }
+
+    #[instrument(skip_all)]
+    pub fn get_git_dir(self: &LocalRepository) -> Result<String> {
+        self.run(vec!["rev-parse", "--absolute-git-dir"], false)?
+            .into_iter()
+            .next()
+            .wrap_err("Cannot find the git directory.")
+    }
+
+    #[instrument(skip(self))]
+    pub fn get_root(self: &LocalRepository) -> Result<PathBuf> {
+        self.run(vec!["rev-parse", "--show-toplevel"], false)?
+            .into_iter()
+            .next()
+            .map(PathBuf::from)
+            .wrap_err("Cannot find the root of the repository.")
+    }
+
+    #[instrument(skip(self))]
+    pub fn edit_file(self: &LocalRepository, path: &str) -> Result<()> {
+        // Find the editor like git does: GIT_EDITOR, core.editor, VISUAL, EDITOR or vi
+        let editor = self
+            .run(vec!["var", "GIT_EDITOR"], false)
+            .ok()
+            .and_then(|lines| lines.into_iter().next())
+            .or_else(|| env::var("GIT_EDITOR").ok())
+            .or_else(|| env::var("EDITOR").ok())
+            .unwrap_or("vi".to_string());
+        debug!("Editing {path} with {editor}.");
+
+        // The editor can contain arguments, so it is run through the shell
+        let status = Command::new("sh")
+            .current_dir(&self.path)
+            .args(["-c", &format!("{editor} \"$@\""), &editor, path])
+            .status()
+            .wrap_err(eyre!("Cannot start the editor {editor}."))?;
+        if !status.success() {
+            return Err(eyre!("The editor {editor} exited with an error."));
         }
-        .wrap_err(eyre!("Could not clone {url}."))?;
 
         Ok(())
     }
+
+    #[instrument(skip(self))]
+    pub fn format_patch(
+        self: &LocalRepository,
+        branch_name: &str,
+        target_name: &str,
+        output_dir: &str,
+        subject_prefix: &str,
+        to: Option<String>,
+    ) -> Result<Vec<String>> {
+        let range = format!("{target_name}..{branch_name}");
+        let subject_prefix = format!("--subject-prefix={subject_prefix}");
+        let mut args = vec![
+            "format-patch",
+            "--cover-letter",
+            &subject_prefix,
+            "-o",
+            output_dir,
+            &range,
+        ];
+        let to = to.map(|to| format!("--to={to}"));
+        if let Some(to) = &to {
+            args.push(to);
+        }
+
+        // Every line of the output is the path of a patch, the first is the cover letter
+        self.run(args, false).wrap_err(eyre!(
+            "Cannot create patches from {target_name} to {branch_name}."
+        ))
+    }
+
+    #[instrument(skip(self))]
+    pub fn clone(
+        self: &LocalRepository,
+        url: String,
+        dir: Option<String>,
+    ) -> Result<LocalRepository> {
+        // Git clones into the last part of the URL without .git by default
+        let name = url
+            .trim_end_matches('/')
+            .rsplit(['/', ':'])
+            .next()
+            .map(|name| name.trim_end_matches(".git").to_string())
+            .wrap_err(eyre!("Invalid repository URL {url}."))?;
+
+        let path = match dir {
+            // If the path exists, we have to clone inside of it
+            Some(dir) if Path::new(&dir).exists() => self
+                .run(vec!["clone", &url], true)
+                .map(|_| Path::new(&dir).join(&name)),
+            // Otherwise we have to reinitialize the repository to allow cloning into empty repo
+            Some(dir) => LocalRepository::init(None)?
+                .run(vec!["clone", &url, &dir], true)
+                .map(|_| PathBuf::from(dir)),
+            None => self
+                .run(vec!["clone", &url], true)
+                .map(|_| Path::new(&self.path).join(&name)),
+        }
+        .wrap_err(eyre!("Could not clone {url}."))?;
+
+        LocalRepository::init(Some(path.to_string_lossy().to_string()))
+    }
 }
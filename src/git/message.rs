@@ -0,0 +1,76 @@
+// Everything below this line is removed, like git commit --cleanup=scissors
+const SCISSORS: &str = "# ------------------------ >8 ------------------------";
+
+/// Build a git-commit-style buffer with the title, the description and commented help lines.
+pub fn build_message(title: &str, description: &str, help: &[String]) -> String {
+    let mut message = format!("{title}\n\n{description}");
+    if !message.ends_with('\n') {
+        message.push('\n');
+    }
+    message.push_str(&format!(
+        "\n{SCISSORS}
+# Do not modify or remove the line above, everything below it is ignored.
+# Write the title of the pull request on the first line and the description below it.
+# An empty title aborts the pull request.
+#
+"
+    ));
+    for line in help {
+        if line.is_empty() {
+            message.push_str("#\n");
+        } else {
+            message.push_str(&format!("# {line}\n"));
+        }
+    }
+
+    message
+}
+
+/// Parse the buffer back into the title and the description, None if the title is empty.
+pub fn parse_message(message: &str) -> Option<(String, String)> {
+    let message = message
+        .split_once(SCISSORS)
+        .map_or(message, |(message, _)| message);
+
+    let mut lines = message.lines().skip_while(|line| line.trim().is_empty());
+    let title = lines.next()?.trim().to_string();
+    if title.is_empty() {
+        return None;
+    }
+    let description = lines.collect::<Vec<&str>>().join("\n").trim().to_string();
+
+    Some((title, description))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_message, parse_message};
+
+    #[test]
+    fn parses_edited_messages() {
+        let message = build_message(
+            "Add login",
+            "## Summary\n\n- add login\n",
+            &["Target: main".to_string(), "".to_string()],
+        );
+        assert!(message.ends_with("#\n# Target: main\n#\n"));
+        assert_eq!(
+            parse_message(&message),
+            Some((
+                "Add login".to_string(),
+                "## Summary\n\n- add login".to_string()
+            ))
+        );
+
+        assert_eq!(
+            parse_message("\n  Fix typo  \n"),
+            Some(("Fix typo".to_string(), "".to_string()))
+        );
+    }
+
+    #[test]
+    fn aborts_empty_messages() {
+        assert_eq!(parse_message(""), None);
+        assert_eq!(parse_message(&build_message("", "", &[])), None);
+    }
+}
@@ -1,3 +1,8 @@
+pub mod branch;
+pub mod changelog;
 #[allow(clippy::module_inception)]
 pub mod git;
+pub mod message;
+pub mod stack;
+pub mod template;
 pub mod url;
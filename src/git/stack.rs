@@ -0,0 +1,141 @@
+// The table is kept between these markers, so it can be replaced without touching the rest
+const STACK_START: &str = "<!-- gr-stack -->";
+const STACK_END: &str = "<!-- /gr-stack -->";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StackItem {
+    pub id: u32,
+    pub title: String,
+    pub url: String,
+    pub merged: bool,
+}
+
+/// Find the target of each branch of the stack, from the base to the tip, None for merged ones.
+///
+/// Each branch targets the closest branch below it that is not merged yet, so the pull requests
+/// above a merged one are retargeted to its parent.
+pub fn get_stack_targets(base: &str, branches: &[(&str, bool)]) -> Vec<Option<String>> {
+    let mut parent = base;
+    branches
+        .iter()
+        .map(|(name, merged)| {
+            if *merged {
+                return None;
+            }
+            let target = parent.to_string();
+            parent = name;
+            Some(target)
+        })
+        .collect()
+}
+
+/// Build the navigation table of the stack, from the base to the tip, marking the current item.
+pub fn build_stack_table(base: &str, items: &[StackItem], current: usize) -> String {
+    let mut table = format!(
+        "{STACK_START}\nStack of pull requests on `{base}`:\n\n| | Pull request | Title |\n| --- | --- | --- |\n"
+    );
+    for (i, item) in items.iter().enumerate() {
+        let marker = if i == current { "→" } else { "" };
+        let title = item.title.replace('|', "\\|");
+        let state = if item.merged { " (merged)" } else { "" };
+        table.push_str(&format!(
+            "| {marker} | [#{}]({}) | {title}{state} |\n",
+            item.id, item.url
+        ));
+    }
+    table.push_str(STACK_END);
+
+    table
+}
+
+/// Replace the navigation table in the description, or append it if there is none yet.
+pub fn replace_stack_table(description: &str, table: &str) -> String {
+    let existing = description.find(STACK_START).and_then(|start| {
+        let end = description[start..].find(STACK_END)? + start + STACK_END.len();
+        Some((start, end))
+    });
+
+    match existing {
+        Some((start, end)) => format!("{}{table}{}", &description[..start], &description[end..]),
+        None if description.trim().is_empty() => table.to_string(),
+        None => format!("{}\n\n{table}", description.trim_end()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_stack_table, get_stack_targets, replace_stack_table, StackItem};
+
+    fn items() -> Vec<StackItem> {
+        vec![
+            StackItem {
+                id: 1,
+                title: "Add login".to_string(),
+                url: "https://example.com/1".to_string(),
+                merged: true,
+            },
+            StackItem {
+                id: 2,
+                title: "Add a | b".to_string(),
+                url: "https://example.com/2".to_string(),
+                merged: false,
+            },
+        ]
+    }
+
+    #[test]
+    fn targets_the_closest_unmerged_branch() {
+        assert_eq!(
+            get_stack_targets("main", &[("a", false), ("b", false), ("c", false)]),
+            vec![
+                Some("main".to_string()),
+                Some("a".to_string()),
+                Some("b".to_string())
+            ]
+        );
+
+        // The pull requests above a merged one are retargeted to its parent
+        assert_eq!(
+            get_stack_targets("main", &[("a", true), ("b", false), ("c", false)]),
+            vec![None, Some("main".to_string()), Some("b".to_string())]
+        );
+        assert_eq!(
+            get_stack_targets("main", &[("a", false), ("b", true), ("c", false)]),
+            vec![Some("main".to_string()), None, Some("a".to_string())]
+        );
+        assert_eq!(
+            get_stack_targets("main", &[("a", true), ("b", true)]),
+            vec![None, None]
+        );
+    }
+
+    #[test]
+    fn builds_stack_tables() {
+        assert_eq!(
+            build_stack_table("main", &items(), 1),
+            "<!-- gr-stack -->
+Stack of pull requests on `main`:
+
+| | Pull request | Title |
+| --- | --- | --- |
+|  | [#1](https://example.com/1) | Add login (merged) |
+| → | [#2](https://example.com/2) | Add a \\| b |
+<!-- /gr-stack -->"
+        );
+    }
+
+    #[test]
+    fn replaces_stack_tables() {
+        let table = build_stack_table("main", &items(), 0);
+        assert_eq!(replace_stack_table("", &table), table);
+
+        let description = replace_stack_table("## Summary\n\n- add login\n", &table);
+        assert_eq!(description, format!("## Summary\n\n- add login\n\n{table}"));
+
+        let updated = build_stack_table("develop", &items()[1..], 0);
+        assert_eq!(
+            replace_stack_table(&format!("{description}\nFooter"), &updated),
+            format!("## Summary\n\n- add login\n\n{updated}\nFooter")
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use eyre::{eyre, Result};
+use std::{
+    fs::read_dir,
+    path::{Path, PathBuf},
+};
+
+// Single templates of GitHub, Gitea and GitLab, the first one found is the default
+const DEFAULT_TEMPLATES: [(&str, &str); 5] = [
+    (".github", "pull_request_template.md"),
+    (".gitea", "pull_request_template.md"),
+    ("", "pull_request_template.md"),
+    ("docs", "pull_request_template.md"),
+    (".gitlab/merge_request_templates", "default.md"),
+];
+
+// Directories of named templates
+const TEMPLATE_DIRECTORIES: [&str; 3] = [
+    ".github/PULL_REQUEST_TEMPLATE",
+    ".gitea/PULL_REQUEST_TEMPLATE",
+    ".gitlab/merge_request_templates",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PullRequestTemplate {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+// Template names are matched case-insensitively, like the forges do
+fn find_file(dir: &Path, name: &str) -> Option<PathBuf> {
+    read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.eq_ignore_ascii_case(name))
+        })
+}
+
+/// Find the pull request templates in the root of the repository.
+pub fn find_templates(root: &Path) -> Vec<PullRequestTemplate> {
+    let mut templates: Vec<PullRequestTemplate> = vec![];
+
+    if let Some(path) = DEFAULT_TEMPLATES
+        .iter()
+        .find_map(|(dir, name)| find_file(&root.join(dir), name))
+    {
+        templates.push(PullRequestTemplate {
+            name: "default".to_string(),
+            path,
+        });
+    }
+
+    for dir in TEMPLATE_DIRECTORIES {
+        let Ok(entries) = read_dir(root.join(dir)) else {
+            continue;
+        };
+        let mut named = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .extension()
+                        .is_some_and(|e| e.eq_ignore_ascii_case("md"))
+            })
+            .filter_map(|path| {
+                let name = path.file_stem()?.to_str()?.to_string();
+                Some(PullRequestTemplate { name, path })
+            })
+            .filter(|template| {
+                !templates
+                    .iter()
+                    .any(|t| t.name.eq_ignore_ascii_case(&template.name))
+            })
+            .collect::<Vec<PullRequestTemplate>>();
+        named.sort_by(|a, b| a.name.cmp(&b.name));
+        templates.append(&mut named);
+    }
+
+    templates
+}
+
+/// Select the template by name, or the default one if there is no name.
+pub fn select_template(
+    templates: &[PullRequestTemplate],
+    name: Option<&str>,
+) -> Result<Option<PullRequestTemplate>> {
+    match name {
+        Some(name) => templates
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case(name))
+            .cloned()
+            .map(Some)
+            .ok_or_else(|| {
+                let names = templates
+                    .iter()
+                    .map(|t| t.name.as_str())
+                    .collect::<Vec<&str>>();
+                if names.is_empty() {
+                    eyre!("Template {name} not found, there are no templates in the repository.")
+                } else {
+                    eyre!(
+                        "Template {name} not found, choose from {}.",
+                        names.join(", ")
+                    )
+                }
+            }),
+        None => Ok(templates
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case("default"))
+            .or(if templates.len() == 1 {
+                templates.first()
+            } else {
+                None
+            })
+            .cloned()),
+    }
+}
+
+/// Replace the placeholders like {{branch}} in the template, unknown ones are kept.
+pub fn fill_template(content: &str, values: &[(&str, &str)]) -> String {
+    let mut filled = String::new();
+    let mut rest = content;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}").map(|end| start + end) else {
+            break;
+        };
+        let key = rest[start + 2..end].trim();
+        filled.push_str(&rest[..start]);
+        match values.iter().find(|(k, _)| *k == key) {
+            Some((_, value)) => filled.push_str(value),
+            None => filled.push_str(&rest[start..end + 2]),
+        }
+        rest = &rest[end + 2..];
+    }
+    filled.push_str(rest);
+
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fill_template, find_templates, select_template};
+    use rand::{distributions::Alphanumeric, Rng};
+    use std::{
+        env::temp_dir,
+        fs::{create_dir_all, remove_dir_all, write},
+    };
+
+    #[test]
+    fn fills_placeholders() {
+        assert_eq!(
+            fill_template(
+                "## {{ branch }} into {{target}}\n\n{{commits}}{{unknown}} {{",
+                &[
+                    ("branch", "feature"),
+                    ("target", "main"),
+                    ("commits", "- a\n")
+                ]
+            ),
+            "## feature into main\n\n- a\n{{unknown}} {{"
+        );
+    }
+
+    #[test]
+    fn finds_and_selects_templates() {
+        let suffix: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(8)
+            .map(char::from)
+            .collect();
+        let root = temp_dir().join(format!("gr-templates-{suffix}"));
+        create_dir_all(root.join(".github/PULL_REQUEST_TEMPLATE")).unwrap();
+        create_dir_all(root.join(".gitlab/merge_request_templates")).unwrap();
+        write(root.join(".github/PULL_REQUEST_TEMPLATE.md"), "default").unwrap();
+        write(root.join(".github/PULL_REQUEST_TEMPLATE/bug.md"), "bug").unwrap();
+        write(
+            root.join(".gitlab/merge_request_templates/Feature.md"),
+            "feat",
+        )
+        .unwrap();
+
+        let templates = find_templates(&root);
+        let names = templates
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(names, vec!["default", "bug", "Feature"]);
+
+        let selected = select_template(&templates, None).unwrap().unwrap();
+        assert_eq!(selected.name, "default");
+        let selected = select_template(&templates, Some("feature"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(selected.name, "Feature");
+        assert!(select_template(&templates, Some("missing")).is_err());
+        assert_eq!(select_template(&templates[1..], None).unwrap(), None);
+
+        remove_dir_all(root).unwrap();
+    }
+}
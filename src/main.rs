@@ -4,12 +4,22 @@ mod utils;
 use cmd::{
     args::{Cli, Commands, PrCommands, RepoCommands},
     config::Configuration,
+    issue::issue::issue,
     login::login::login,
     pr::{
         approve::approve, close::close, create::create, get::get as get_pr, list::list,
-        merge::merge,
+        merge::merge, update_branch::update_branch,
     },
-    repo::{delete::delete, fork::fork, get::get as get_repo, new::new},
+    release::release::release,
+    repo::{
+        clone::clone, collaborators::collaborators, delete::delete, deploy_keys::deploy_keys,
+        download_archive::download_archive, edit::edit, fork::fork, get::get as get_repo,
+        hooks::hooks, list::list as list_repos, new::new, protect::protect,
+        protections::protections, rename::rename, search::search, sync::sync, transfer::transfer,
+    },
+    secret::secret::secret,
+    stack::stack::stack,
+    variable::variable::variable,
 };
 use eyre::{eyre, Result};
 use std::process;
@@ -30,15 +40,64 @@ fn run(mut args: Cli) -> Result<()> {
         Commands::Pr(PrCommands::List { .. }) => list(args, conf),
         Commands::Pr(PrCommands::Approve { .. }) => approve(args, conf),
         Commands::Pr(PrCommands::Merge { .. }) => merge(args, conf),
+        Commands::Pr(PrCommands::UpdateBranch { .. }) => update_branch(args, conf),
         Commands::Pr(PrCommands::Close { .. }) => close(args, conf),
+        Commands::Issue(..) => issue(args, conf),
         Commands::Repo(RepoCommands::New { .. }) => new(args, conf),
         Commands::Repo(RepoCommands::Fork { .. }) => fork(args, conf),
+        Commands::Repo(RepoCommands::Clone { .. }) => clone(args, conf),
         Commands::Repo(RepoCommands::Get { .. }) => get_repo(args, conf),
+        Commands::Repo(RepoCommands::List { .. }) => list_repos(args, conf),
+        Commands::Repo(RepoCommands::Search { .. }) => search(args, conf),
         Commands::Repo(RepoCommands::Open { .. }) => {
             args.command = Commands::Repo(RepoCommands::Get { open: true });
             get_repo(args, conf)
         }
+        Commands::Repo(RepoCommands::Edit { .. }) => edit(args, conf),
+        Commands::Repo(RepoCommands::Rename { .. }) => rename(args, conf),
+        Commands::Repo(RepoCommands::Transfer { .. }) => transfer(args, conf),
+        Commands::Repo(RepoCommands::Protect { .. }) => protect(args, conf),
+        Commands::Repo(RepoCommands::Protections { .. }) => protections(args, conf),
+        Commands::Repo(RepoCommands::Collaborators(..)) => collaborators(args, conf),
+        Commands::Repo(RepoCommands::Hooks(..)) => hooks(args, conf),
+        Commands::Repo(RepoCommands::DeployKeys(..)) => deploy_keys(args, conf),
+        Commands::Repo(RepoCommands::Archive { .. }) => {
+            args.command = Commands::Repo(RepoCommands::Edit {
+                name: None,
+                description: None,
+                visibility: None,
+                default_branch: None,
+                homepage: None,
+                topics: None,
+                archived: Some(true),
+                allow_merge_commit: None,
+                allow_squash_merge: None,
+                allow_rebase_merge: None,
+            });
+            edit(args, conf)
+        }
+        Commands::Repo(RepoCommands::Unarchive { .. }) => {
+            args.command = Commands::Repo(RepoCommands::Edit {
+                name: None,
+                description: None,
+                visibility: None,
+                default_branch: None,
+                homepage: None,
+                topics: None,
+                archived: Some(false),
+                allow_merge_commit: None,
+                allow_squash_merge: None,
+                allow_rebase_merge: None,
+            });
+            edit(args, conf)
+        }
+        Commands::Repo(RepoCommands::DownloadArchive { .. }) => download_archive(args, conf),
         Commands::Repo(RepoCommands::Delete { .. }) => delete(args, conf),
+        Commands::Repo(RepoCommands::Sync { .. }) => sync(args, conf),
+        Commands::Release(..) => release(args, conf),
+        Commands::Stack(..) => stack(args, conf),
+        Commands::Secret(..) => secret(args, conf),
+        Commands::Variable(..) => variable(args, conf),
         Commands::Completion { .. } => Err(eyre!("Invalid command.")),
     }
 }
@@ -1,16 +1,22 @@
 // Documentation: https://developer.atlassian.com/cloud/bitbucket/rest/intro/
 use super::common::{
-    CreatePullRequest, CreateRepository, ForkRepository, ForkedFromRepository,
-    ListPullRequestFilters, PullRequest, PullRequestState, PullRequestStateFilter, Repository,
-    RepositoryVisibility, User, VersionControl, VersionControlSettings,
+    multipart_file, save_response, ArchiveFormat, BranchProtection, BranchUpdate, Collaborator,
+    CollaboratorPermission, CreateDeployKey, CreateIssue, CreatePullRequest, CreateRelease,
+    CreateRepository, CreateWebhook, DeployKey, ForkRepository, ForkSync, ForkedFromRepository,
+    Issue, IssueState, IssueStateFilter, ListPullRequestFilters, ListRepositoryFilters,
+    PullRequest, PullRequestState, PullRequestStateFilter, Release, ReleaseAsset, Repository,
+    RepositoryVisibility, RequestError, Secret, UpdatePullRequest, UpdateRepository, User,
+    Variable, VariableScope, VersionControl, VersionControlSettings, Webhook, WebhookContentType,
+    WebhookEvent,
 };
 use eyre::{eyre, ContextCompat, Result};
 use native_tls::TlsConnector;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::{fmt::Debug, sync::Arc};
+use std::{fmt::Debug, io::Write, sync::Arc};
 use time::OffsetDateTime;
-use tracing::{info, instrument, trace};
+use tracing::{info, instrument, trace, warn};
 use ureq::{Agent, AgentBuilder, Error};
+use urlencoding::encode;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub enum BitbucketPullRequestState {
@@ -109,6 +115,20 @@ pub struct BitbucketCommit {
     pub hash: String,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BitbucketUpdateRepository {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub website: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_private: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mainbranch: Option<BitbucketBranch>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct BitbucketBranch {
     pub name: String,
@@ -163,7 +183,8 @@ pub struct BitbucketRepository {
     updated_on: OffsetDateTime,
     language: String,
     project: BitbucketRepositoryProject,
-    mainbranch: BitbucketBranch,
+    // Empty repositories don't have a main branch
+    mainbranch: Option<BitbucketBranch>,
     is_private: bool,
     parent: Option<BitbucketForkedFromRepository>,
 }
@@ -207,7 +228,7 @@ impl From<BitbucketRepository> for Repository {
                 RepositoryVisibility::Public
             },
             archived: false,
-            default_branch: mainbranch.name,
+            default_branch: mainbranch.map(|b| b.name).unwrap_or_default(),
             forks_count: 0,
             stars_count: 0,
             ssh_url: ssh_url.unwrap().to_owned(),
@@ -326,6 +347,15 @@ pub struct BitbucketCreatePullRequest {
     pub reviewers: Vec<BitbucketReviewer>,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BitbucketUpdatePullRequest {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination: Option<BitbucketCreateRevision>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct BitbucketMergePullRequest {
     pub close_source_branch: bool,
@@ -371,6 +401,236 @@ pub struct BitbucketPaginated<T> {
     pub values: Vec<T>,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BitbucketBranchRestriction {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<u32>,
+    pub kind: String,
+    pub branch_match_kind: String,
+    #[serde(default)]
+    pub pattern: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BitbucketUserPermission {
+    pub permission: String,
+    pub user: BitbucketUser,
+}
+
+impl From<BitbucketUserPermission> for Collaborator {
+    fn from(permission: BitbucketUserPermission) -> Collaborator {
+        Collaborator {
+            user: permission.user.into(),
+            permission: match permission.permission.as_str() {
+                "admin" => CollaboratorPermission::Admin,
+                "write" => CollaboratorPermission::Write,
+                _ => CollaboratorPermission::Read,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BitbucketUpdatePermission {
+    pub permission: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BitbucketWebhook {
+    pub uuid: String,
+    pub url: String,
+    pub active: bool,
+    pub events: Vec<String>,
+}
+
+impl From<BitbucketWebhook> for Webhook {
+    fn from(webhook: BitbucketWebhook) -> Webhook {
+        let BitbucketWebhook {
+            uuid,
+            url,
+            active,
+            events,
+        } = webhook;
+        let mut webhook_events: Vec<WebhookEvent> = vec![];
+        for event in events {
+            let event = match event.split_once(':') {
+                Some(("repo", "push")) => WebhookEvent::Push,
+                Some(("pullrequest", _)) => WebhookEvent::PullRequest,
+                Some(("issue", _)) => WebhookEvent::Issue,
+                _ => continue,
+            };
+            if !webhook_events.contains(&event) {
+                webhook_events.push(event);
+            }
+        }
+        Webhook {
+            id: uuid,
+            url,
+            events: webhook_events,
+            content_type: WebhookContentType::Json,
+            active,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BitbucketCreateWebhook {
+    pub description: String,
+    pub url: String,
+    pub active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    pub events: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BitbucketAccessKey {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<u32>,
+    pub key: String,
+    pub label: String,
+}
+
+impl From<BitbucketAccessKey> for DeployKey {
+    fn from(key: BitbucketAccessKey) -> DeployKey {
+        let BitbucketAccessKey { id, key, label } = key;
+        DeployKey {
+            id: id.map(|id| id.to_string()).unwrap_or_default(),
+            title: label,
+            key,
+            // Access keys can only be used to read the repository
+            read_only: true,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BitbucketPipelineVariable {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+    pub key: String,
+    #[serde(default)]
+    pub value: String,
+    pub secured: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BitbucketTagTarget {
+    pub hash: String,
+    #[serde(with = "time::serde::iso8601")]
+    pub date: OffsetDateTime,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BitbucketTag {
+    pub name: String,
+    pub message: Option<String>,
+    pub target: BitbucketTagTarget,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BitbucketCreateTagTarget {
+    pub hash: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BitbucketCreateTag {
+    pub name: String,
+    pub target: BitbucketCreateTagTarget,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub enum BitbucketIssueState {
+    #[serde(rename = "new")]
+    New,
+    #[serde(rename = "open")]
+    Open,
+    #[serde(rename = "on hold")]
+    OnHold,
+    #[serde(rename = "resolved")]
+    Resolved,
+    #[serde(rename = "invalid")]
+    Invalid,
+    #[serde(rename = "duplicate")]
+    Duplicate,
+    #[serde(rename = "wontfix")]
+    Wontfix,
+    #[serde(rename = "closed")]
+    Closed,
+}
+
+impl From<BitbucketIssueState> for IssueState {
+    fn from(state: BitbucketIssueState) -> IssueState {
+        match state {
+            BitbucketIssueState::New | BitbucketIssueState::Open | BitbucketIssueState::OnHold => {
+                IssueState::Open
+            }
+            _ => IssueState::Closed,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BitbucketIssueContent {
+    pub raw: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BitbucketIssue {
+    pub id: u32,
+    pub state: BitbucketIssueState,
+    pub title: String,
+    pub content: BitbucketIssueContent,
+    pub kind: String,
+    pub links: BitbucketPullRequestLinks,
+    #[serde(with = "time::serde::iso8601")]
+    pub created_on: OffsetDateTime,
+    #[serde(with = "time::serde::iso8601")]
+    pub updated_on: OffsetDateTime,
+    pub reporter: Option<BitbucketUser>,
+}
+
+impl From<BitbucketIssue> for Issue {
+    fn from(issue: BitbucketIssue) -> Issue {
+        let BitbucketIssue {
+            id,
+            state,
+            title,
+            content,
+            kind,
+            links,
+            created_on,
+            updated_on,
+            reporter,
+        } = issue;
+        Issue {
+            id,
+            state: state.into(),
+            title,
+            description: content.raw,
+            // Bitbucket has no labels, the kind of the issue is the closest to them
+            labels: vec![kind],
+            url: links.html.href,
+            created_at: created_on,
+            updated_at: updated_on,
+            author: reporter.map(|r| r.into()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BitbucketCreateIssue {
+    pub title: String,
+    pub content: BitbucketIssueContent,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BitbucketUpdateIssue {
+    pub state: BitbucketIssueState,
+}
+
 #[derive(Debug)]
 pub struct Bitbucket {
     settings: VersionControlSettings,
@@ -385,11 +645,38 @@ impl Bitbucket {
     }
 
     #[instrument(skip_all)]
+    fn get_pull_request_url(&self, url: &str) -> String {
+        // In fork workflows the pull requests live on the upstream repository
+        let repo = self.settings.upstream.as_ref().unwrap_or(&self.repo);
+        format!("/repositories/{repo}{url}")
+    }
+
     fn call<T: DeserializeOwned, U: Serialize + Debug>(
         &self,
         method: &str,
         url: &str,
         body: Option<U>,
+    ) -> Result<T> {
+        self.send(method, url, body, false)
+    }
+
+    // Secrets, variables and webhooks with secrets are kept out of the traces
+    fn call_secret<T: DeserializeOwned, U: Serialize + Debug>(
+        &self,
+        method: &str,
+        url: &str,
+        body: Option<U>,
+    ) -> Result<T> {
+        self.send(method, url, body, true)
+    }
+
+    #[instrument(skip_all)]
+    fn send<T: DeserializeOwned, U: Serialize + Debug>(
+        &self,
+        method: &str,
+        url: &str,
+        body: Option<U>,
+        secret: bool,
     ) -> Result<T> {
         let url = format!("https://api.bitbucket.org/2.0{url}");
 
@@ -408,12 +695,81 @@ impl Bitbucket {
             &format!("Basic {}", base64::encode(format!("{username}:{password}"))),
         );
         let result = if let Some(body) = &body {
-            trace!("Sending body: {}.", serde_json::to_string(&body)?);
+            if secret {
+                trace!("Sending body: <redacted>.");
+            } else {
+                trace!("Sending body: {}.", serde_json::to_string(&body)?);
+            }
             request.send_json(body)
         } else {
             request.call()
         };
 
+        match result {
+            Ok(result) => {
+                let status = result.status();
+                let mut t = result.into_string()?;
+
+                info!(
+                    "Received response with response code {} with body size {}.",
+                    status,
+                    t.len()
+                );
+                if !secret {
+                    trace!("Response body: {t}.");
+                }
+
+                // Somewhat hacky, if the response is empty, return null
+                if t.is_empty() {
+                    t = "null".to_string();
+                }
+
+                let t: T = serde_json::from_str(&t)?;
+                Ok(t)
+            }
+            Err(Error::Status(status, result)) => {
+                let t = result.into_string()?;
+
+                info!(
+                    "Received response with response code {} with body size {}.",
+                    status,
+                    t.len()
+                );
+                Err(RequestError { status, body: t }.into())
+            }
+            Err(Error::Transport(_)) => Err(eyre!("Sending data failed.")),
+        }
+    }
+
+    #[instrument(skip(self, content))]
+    fn upload<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        content_type: &str,
+        content: &[u8],
+    ) -> Result<T> {
+        let url = format!("https://api.bitbucket.org/2.0{url}");
+
+        info!("Uploading {} bytes to {url}.", content.len());
+
+        let (username, password) = self
+            .settings
+            .auth
+            .split_once(':')
+            .wrap_err("Authentication has to contain a username and a token.")?;
+
+        trace!("Authenticating with username '{username}' and token '{password}'.");
+
+        let result = self
+            .client
+            .request("POST", &url)
+            .set(
+                "Authorization",
+                &format!("Basic {}", base64::encode(format!("{username}:{password}"))),
+            )
+            .set("Content-Type", content_type)
+            .send_bytes(content);
+
         match result {
             Ok(result) => {
                 let status = result.status();
@@ -442,17 +798,85 @@ impl Bitbucket {
                     status,
                     t.len()
                 );
-                Err(eyre!("Request failed (response: {}).", t))
+                Err(RequestError { status, body: t }.into())
+            }
+            Err(Error::Transport(_)) => Err(eyre!("Sending data failed.")),
+        }
+    }
+
+    #[instrument(skip(self, writer))]
+    fn download(
+        &self,
+        url: &str,
+        writer: &mut dyn Write,
+        expected_size: Option<u64>,
+    ) -> Result<u64> {
+        info!("Downloading {url}.");
+
+        let (username, password) = self
+            .settings
+            .auth
+            .split_once(':')
+            .wrap_err("Authentication has to contain a username and a token.")?;
+
+        trace!("Authenticating with username '{username}' and token '{password}'.");
+
+        let result = self
+            .client
+            .get(url)
+            .set(
+                "Authorization",
+                &format!("Basic {}", base64::encode(format!("{username}:{password}"))),
+            )
+            .call();
+
+        match result {
+            Ok(response) => save_response(response, writer, expected_size),
+            Err(Error::Status(status, result)) => {
+                let t = result.into_string()?;
+
+                info!(
+                    "Received response with response code {} with body size {}.",
+                    status,
+                    t.len()
+                );
+                Err(RequestError { status, body: t }.into())
             }
             Err(Error::Transport(_)) => Err(eyre!("Sending data failed.")),
         }
     }
 
+    // Bitbucket has no releases, tags are used instead with the files in downloads
+    #[instrument(skip_all)]
+    fn tag_into_release(&self, tag: BitbucketTag) -> Release {
+        Release {
+            id: tag.name.clone(),
+            url: format!(
+                "https://bitbucket.org/{}/src/{}",
+                self.repo,
+                encode(&tag.name)
+            ),
+            name: tag.name.clone(),
+            tag: tag.name,
+            description: tag.message.unwrap_or_default().trim().to_string(),
+            draft: false,
+            prerelease: false,
+            created_at: tag.target.date,
+            assets: vec![],
+        }
+    }
+
     #[instrument(skip(self))]
-    fn call_paginated<T: DeserializeOwned>(&self, url: &str, params: &str) -> Result<Vec<T>> {
+    fn call_paginated<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        params: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<T>> {
+        let limit = limit.unwrap_or(usize::MAX);
         let mut collected_values: Vec<T> = vec![];
         let mut i = 1;
-        loop {
+        while collected_values.len() < limit {
             info!("Reading page {}.", i);
 
             let mut page: BitbucketPaginated<T> = self.call(
@@ -469,6 +893,7 @@ impl Bitbucket {
 
             i += 1;
         }
+        collected_values.truncate(limit);
         Ok(collected_values)
     }
 
@@ -479,7 +904,7 @@ impl Bitbucket {
             .split_once('/')
             .wrap_err(eyre!("Repo URL is malformed: {}", &self.repo))?;
         let members: Vec<BitbucketMembership> =
-            self.call_paginated(&format!("/workspaces/{workspace}/members"), "")?;
+            self.call_paginated(&format!("/workspaces/{workspace}/members"), "", None)?;
 
         Ok(members
             .into_iter()
@@ -487,6 +912,70 @@ impl Bitbucket {
             .filter(|u| usernames.contains(&u.nickname))
             .collect())
     }
+
+    #[instrument(skip(self))]
+    fn get_workspace_user(&self, username: &str) -> Result<BitbucketUser> {
+        self.get_workspace_users(vec![username.to_string()])?
+            .into_iter()
+            .next()
+            .wrap_err(eyre!("User {username} is not a member of the workspace."))
+    }
+
+    #[instrument(skip_all)]
+    fn get_scope_url(&self, scope: &VariableScope, url: &str) -> String {
+        match scope {
+            VariableScope::Repository => {
+                self.get_repository_url(&format!("/pipelines_config{url}"))
+            }
+            VariableScope::Organization(workspace) => {
+                format!("/workspaces/{workspace}/pipelines-config{url}")
+            }
+        }
+    }
+
+    #[instrument(skip_all)]
+    fn set_pipeline_variable(
+        &self,
+        scope: &VariableScope,
+        key: String,
+        value: String,
+        secured: bool,
+    ) -> Result<()> {
+        // Update the variable if it exists already, otherwise create it
+        let url = self.get_scope_url(scope, "/variables");
+        let variables: Vec<BitbucketPipelineVariable> = self.call_paginated(&url, "", None)?;
+        let variable = BitbucketPipelineVariable {
+            uuid: None,
+            key,
+            value,
+            secured,
+        };
+        let _: BitbucketPipelineVariable = match variables
+            .into_iter()
+            .find(|v| v.key == variable.key)
+        {
+            Some(BitbucketPipelineVariable {
+                uuid: Some(uuid), ..
+            }) => self.call_secret("PUT", &format!("{url}/{}", encode(&uuid)), Some(variable))?,
+            _ => self.call_secret("POST", &url, Some(variable))?,
+        };
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    fn delete_pipeline_variable(&self, scope: &VariableScope, key: &str) -> Result<()> {
+        let url = self.get_scope_url(scope, "/variables");
+        let variables: Vec<BitbucketPipelineVariable> = self.call_paginated(&url, "", None)?;
+        let uuid = variables
+            .into_iter()
+            .find(|v| v.key == key)
+            .and_then(|v| v.uuid)
+            .wrap_err(eyre!("Variable {key} cannot be found."))?;
+        self.call::<(), i32>("DELETE", &format!("{url}/{}", encode(&uuid)), None)?;
+
+        Ok(())
+    }
 }
 
 impl VersionControl for Bitbucket {
@@ -513,23 +1002,40 @@ impl VersionControl for Bitbucket {
             Ok(())
         }
     }
+
+    #[instrument(skip_all)]
+    fn get_current_user(&self) -> Result<User> {
+        let user: BitbucketUser = self.call("GET", "/user", None as Option<i32>)?;
+
+        Ok(user.into())
+    }
+
+    #[instrument(skip(self))]
+    fn requires_pushed_branch(&self) -> Result<bool> {
+        Ok(true)
+    }
+
+    #[instrument(skip(self))]
+    fn tracks_pull_requests(&self) -> Result<bool> {
+        Ok(true)
+    }
+
     #[instrument(skip(self))]
     fn create_pr(&self, mut pr: CreatePullRequest) -> Result<PullRequest> {
+        if !pr.labels.is_empty() {
+            warn!("Labels are not supported by Bitbucket, skipping them.");
+        }
         let reviewers = self.get_workspace_users(pr.reviewers.clone())?;
         pr.reviewers = reviewers.into_iter().map(|r| r.uuid).collect();
 
-        let mut url = self.get_repository_url("/pullrequests");
+        let url = self.get_pull_request_url("/pullrequests");
         let mut bitbucket_pr = BitbucketCreatePullRequest::from(pr);
-        if self.settings.fork {
-            let repo = self.get_repository()?;
-            if let Some(forked) = repo.forked_from {
-                url = format!("/repositories/{}/pullrequests", forked.full_name);
-                bitbucket_pr.source = BitbucketCreateRevision {
-                    repository: Some(BitbucketRevisionRepository {
-                        full_name: repo.full_name,
-                    }),
-                    ..bitbucket_pr.source
-                }
+        if self.settings.upstream.is_some() {
+            bitbucket_pr.source = BitbucketCreateRevision {
+                repository: Some(BitbucketRevisionRepository {
+                    full_name: self.repo.clone(),
+                }),
+                ..bitbucket_pr.source
             }
         }
 
@@ -537,11 +1043,40 @@ impl VersionControl for Bitbucket {
 
         Ok(new_pr.into())
     }
+
+    #[instrument(skip(self))]
+    fn update_pr(&self, id: u32, pr: UpdatePullRequest) -> Result<PullRequest> {
+        let UpdatePullRequest {
+            title,
+            description,
+            target,
+        } = pr;
+
+        // Bitbucket requires the title on every update
+        let title = match title {
+            Some(title) => title,
+            None => self.get_pr_by_id(id)?.title,
+        };
+        let pr: BitbucketPullRequest = self.call(
+            "PUT",
+            &self.get_pull_request_url(&format!("/pullrequests/{id}")),
+            Some(BitbucketUpdatePullRequest {
+                title,
+                description,
+                destination: target.map(|name| BitbucketCreateRevision {
+                    branch: BitbucketBranch { name },
+                    repository: None,
+                }),
+            }),
+        )?;
+
+        Ok(pr.into())
+    }
     #[instrument(skip(self))]
     fn get_pr_by_id(&self, id: u32) -> Result<PullRequest> {
         let pr: BitbucketPullRequest = self.call(
             "GET",
-            &self.get_repository_url(&format!("/pullrequests/{id}")),
+            &self.get_pull_request_url(&format!("/pullrequests/{id}")),
             None as Option<u32>,
         )?;
 
@@ -549,11 +1084,30 @@ impl VersionControl for Bitbucket {
     }
     #[instrument(skip(self))]
     fn get_pr_by_branch(&self, branch: &str) -> Result<PullRequest> {
-        let prs: Vec<BitbucketPullRequest> =
-            self.call_paginated(&self.get_repository_url("/pullrequests"), "")?;
+        // Only open pull requests are returned by default, merged ones are needed for stacks
+        let prs: Vec<BitbucketPullRequest> = self.call_paginated(
+            &self.get_pull_request_url("/pullrequests"),
+            "&state=OPEN&state=MERGED",
+            None,
+        )?;
 
+        let mut prs = prs
+            .into_iter()
+            .filter(|pr| {
+                // Skip pull requests from other forks with the same branch name
+                pr.source.branch.name == branch
+                    && pr
+                        .source
+                        .repository
+                        .as_ref()
+                        .is_none_or(|r| r.full_name == self.repo)
+            })
+            .collect::<Vec<BitbucketPullRequest>>();
+
+        // Prefer the open pull request, if the branch was reused after a merge
+        prs.sort_by_key(|pr| !matches!(pr.state, BitbucketPullRequestState::Open));
         prs.into_iter()
-            .find(|pr| pr.source.branch.name == branch)
+            .next()
             .map(|pr| pr.into())
             .wrap_err(eyre!("Pull request on branch {branch} not found."))
     }
@@ -565,8 +1119,11 @@ impl VersionControl for Bitbucket {
             PullRequestStateFilter::Merged => "&state=MERGED",
             PullRequestStateFilter::Locked | PullRequestStateFilter::All => "",
         };
-        let prs: Vec<BitbucketPullRequest> =
-            self.call_paginated(&self.get_repository_url("/pullrequests"), state_param)?;
+        let prs: Vec<BitbucketPullRequest> = self.call_paginated(
+            &self.get_pull_request_url("/pullrequests"),
+            state_param,
+            None,
+        )?;
 
         Ok(prs.into_iter().map(|pr| pr.into()).collect())
     }
@@ -574,7 +1131,7 @@ impl VersionControl for Bitbucket {
     fn approve_pr(&self, id: u32) -> Result<()> {
         let _: BitbucketApproval = self.call(
             "POST",
-            &self.get_repository_url(&format!("/pullrequests/{id}/approve")),
+            &self.get_pull_request_url(&format!("/pullrequests/{id}/approve")),
             None as Option<i32>,
         )?;
 
@@ -584,7 +1141,7 @@ impl VersionControl for Bitbucket {
     fn close_pr(&self, id: u32) -> Result<PullRequest> {
         let pr: BitbucketPullRequest = self.call(
             "POST",
-            &self.get_repository_url(&format!("/pullrequests/{id}/decline")),
+            &self.get_pull_request_url(&format!("/pullrequests/{id}/decline")),
             None as Option<i32>,
         )?;
 
@@ -594,7 +1151,7 @@ impl VersionControl for Bitbucket {
     fn merge_pr(&self, id: u32, close_source_branch: bool) -> Result<PullRequest> {
         let pr: BitbucketPullRequest = self.call(
             "POST",
-            &self.get_repository_url(&format!("/pullrequests/{id}/merge")),
+            &self.get_pull_request_url(&format!("/pullrequests/{id}/merge")),
             Some(BitbucketMergePullRequest {
                 close_source_branch,
             }),
@@ -603,6 +1160,71 @@ impl VersionControl for Bitbucket {
         Ok(pr.into())
     }
 
+    #[instrument(skip(self))]
+    fn update_pr_branch(&self, _: u32, _: &str, _: bool) -> Result<BranchUpdate> {
+        // Bitbucket has no API to update branches, rebase or merge them locally
+        Ok(BranchUpdate::Unsupported)
+    }
+
+    #[instrument(skip(self))]
+    fn create_issue(&self, issue: CreateIssue) -> Result<Issue> {
+        let CreateIssue { title, description } = issue;
+        let issue: BitbucketIssue = self.call(
+            "POST",
+            &self.get_repository_url("/issues"),
+            Some(BitbucketCreateIssue {
+                title,
+                content: BitbucketIssueContent { raw: description },
+            }),
+        )?;
+
+        Ok(issue.into())
+    }
+
+    #[instrument(skip(self))]
+    fn get_issue(&self, id: u32) -> Result<Issue> {
+        let issue: BitbucketIssue = self.call(
+            "GET",
+            &self.get_repository_url(&format!("/issues/{id}")),
+            None as Option<i32>,
+        )?;
+
+        Ok(issue.into())
+    }
+
+    #[instrument(skip(self))]
+    fn list_issues(&self, state: IssueStateFilter) -> Result<Vec<Issue>> {
+        let filter = match state {
+            IssueStateFilter::Open => {
+                Some(r#"state = "new" OR state = "open" OR state = "on hold""#)
+            }
+            IssueStateFilter::Closed => {
+                Some(r#"state != "new" AND state != "open" AND state != "on hold""#)
+            }
+            IssueStateFilter::All => None,
+        };
+        let params = filter
+            .map(|filter| format!("&q={}", encode(filter)))
+            .unwrap_or_default();
+        let issues: Vec<BitbucketIssue> =
+            self.call_paginated(&self.get_repository_url("/issues"), &params, None)?;
+
+        Ok(issues.into_iter().map(|issue| issue.into()).collect())
+    }
+
+    #[instrument(skip(self))]
+    fn close_issue(&self, id: u32) -> Result<Issue> {
+        let issue: BitbucketIssue = self.call(
+            "PUT",
+            &self.get_repository_url(&format!("/issues/{id}")),
+            Some(BitbucketUpdateIssue {
+                state: BitbucketIssueState::Closed,
+            }),
+        )?;
+
+        Ok(issue.into())
+    }
+
     #[instrument(skip_all)]
     fn get_repository(&self) -> Result<Repository> {
         let repo =
@@ -611,6 +1233,38 @@ impl VersionControl for Bitbucket {
         Ok(repo.into())
     }
 
+    #[instrument(skip(self))]
+    fn list_repositories(
+        &self,
+        owner: Option<&str>,
+        filters: ListRepositoryFilters,
+    ) -> Result<Vec<Repository>> {
+        let repos: Vec<BitbucketRepository> = if let Some(workspace) = owner {
+            self.call_paginated(&format!("/repositories/{workspace}"), "", None)?
+        } else {
+            self.call_paginated("/repositories", "&role=owner", None)?
+        };
+
+        Ok(repos
+            .into_iter()
+            .map(Repository::from)
+            .filter(|repo| filters.matches(repo))
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    fn search_repositories(&self, query: &str, limit: usize) -> Result<Vec<Repository>> {
+        // Bitbucket can't search globally, only in the repositories of the user
+        let filter = encode(&format!("name ~ \"{}\"", query.replace('"', "\\\""))).to_string();
+        let repos: Vec<BitbucketRepository> = self.call_paginated(
+            "/repositories",
+            &format!("&role=member&q={filter}"),
+            Some(limit),
+        )?;
+
+        Ok(repos.into_iter().map(|repo| repo.into()).collect())
+    }
+
     #[instrument(skip_all)]
     fn create_repository(&self, repo: CreateRepository) -> Result<Repository> {
         // TODO: make it work with user
@@ -655,10 +1309,517 @@ impl VersionControl for Bitbucket {
         Ok(new_repo.into())
     }
 
+    #[instrument(skip_all)]
+    fn update_repository(&self, repo: UpdateRepository) -> Result<Repository> {
+        let UpdateRepository {
+            name,
+            description,
+            visibility,
+            default_branch,
+            homepage,
+            topics,
+            archived,
+            allow_merge_commit,
+            allow_squash_merge,
+            allow_rebase_merge,
+        } = repo;
+        if topics.is_some() {
+            return Err(eyre!("Setting topics is not supported by Bitbucket."));
+        }
+        if archived.is_some() {
+            return Err(eyre!(
+                "Archiving repositories is not supported by Bitbucket."
+            ));
+        }
+        if allow_merge_commit.is_some()
+            || allow_squash_merge.is_some()
+            || allow_rebase_merge.is_some()
+        {
+            return Err(eyre!(
+                "Changing merge methods is not supported by Bitbucket."
+            ));
+        }
+
+        let repo: BitbucketRepository = self.call(
+            "PUT",
+            &self.get_repository_url(""),
+            Some(BitbucketUpdateRepository {
+                name,
+                description,
+                website: homepage,
+                is_private: visibility.map(|visibility| visibility != RepositoryVisibility::Public),
+                mainbranch: default_branch.map(|name| BitbucketBranch { name }),
+            }),
+        )?;
+
+        Ok(repo.into())
+    }
+
+    #[instrument(skip_all)]
+    fn transfer_repository(&self, _: &str) -> Result<Repository> {
+        Err(eyre!(
+            "Transferring repositories is not supported by Bitbucket."
+        ))
+    }
+
     #[instrument(skip_all)]
     fn delete_repository(&self) -> Result<()> {
-        self.call("DELETE", &self.get_repository_url(""), None as Option<i32>)?;
+        self.call::<(), _>("DELETE", &self.get_repository_url(""), None as Option<i32>)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn sync_fork(&self, _: &str) -> Result<ForkSync> {
+        // Bitbucket has no API to sync forks, fetch and push the upstream branch locally
+        Ok(ForkSync::Unsupported)
+    }
+
+    #[instrument(skip(self, writer))]
+    fn download_archive(
+        &self,
+        reference: &str,
+        format: ArchiveFormat,
+        writer: &mut dyn Write,
+    ) -> Result<u64> {
+        // There is no API for archives, but the website accepts the same authentication
+        self.download(
+            &format!(
+                "https://bitbucket.org/{}/get/{}.{}",
+                self.repo,
+                encode(reference),
+                format.extension()
+            ),
+            writer,
+            None,
+        )
+    }
+
+    #[instrument(skip_all)]
+    fn list_deploy_keys(&self) -> Result<Vec<DeployKey>> {
+        let keys: Vec<BitbucketAccessKey> =
+            self.call_paginated(&self.get_repository_url("/deploy-keys"), "", None)?;
+
+        Ok(keys.into_iter().map(|k| k.into()).collect())
+    }
+
+    #[instrument(skip_all)]
+    fn add_deploy_key(&self, key: CreateDeployKey) -> Result<DeployKey> {
+        let CreateDeployKey {
+            title,
+            key,
+            read_only,
+        } = key;
+        if !read_only {
+            return Err(eyre!(
+                "Read-write deploy keys are not supported by Bitbucket."
+            ));
+        }
+
+        let key: BitbucketAccessKey = self.call(
+            "POST",
+            &self.get_repository_url("/deploy-keys"),
+            Some(BitbucketAccessKey {
+                id: None,
+                key,
+                label: title,
+            }),
+        )?;
+
+        Ok(key.into())
+    }
+
+    #[instrument(skip_all)]
+    fn remove_deploy_key(&self, id: &str) -> Result<()> {
+        self.call::<(), i32>(
+            "DELETE",
+            &self.get_repository_url(&format!("/deploy-keys/{id}")),
+            None,
+        )?;
 
         Ok(())
     }
+
+    #[instrument(skip_all)]
+    fn list_branch_protections(&self) -> Result<Vec<BranchProtection>> {
+        let restrictions: Vec<BitbucketBranchRestriction> =
+            self.call_paginated(&self.get_repository_url("/branch-restrictions"), "", None)?;
+
+        // Bitbucket has a restriction per kind, collect them for every branch pattern
+        let mut protections: Vec<BranchProtection> = vec![];
+        for restriction in restrictions {
+            if restriction.branch_match_kind != "glob" {
+                continue;
+            }
+            let index = match protections
+                .iter()
+                .position(|p| p.branch == restriction.pattern)
+            {
+                Some(index) => index,
+                None => {
+                    protections.push(BranchProtection {
+                        branch: restriction.pattern.clone(),
+                        allow_force_push: true,
+                        ..Default::default()
+                    });
+                    protections.len() - 1
+                }
+            };
+            match restriction.kind.as_str() {
+                "require_approvals_to_merge" => {
+                    protections[index].required_approvals = restriction.value.unwrap_or_default()
+                }
+                "force" => protections[index].allow_force_push = false,
+                _ => {}
+            }
+        }
+
+        Ok(protections)
+    }
+
+    #[instrument(skip_all)]
+    fn protect_branch(&self, protection: BranchProtection) -> Result<BranchProtection> {
+        if !protection.required_status_checks.is_empty() {
+            return Err(eyre!(
+                "Required status checks by name are not supported by Bitbucket."
+            ));
+        }
+        if protection.linear_history {
+            return Err(eyre!(
+                "Requiring linear history is not supported by Bitbucket."
+            ));
+        }
+
+        let url = self.get_repository_url("/branch-restrictions");
+        let existing: Vec<BitbucketBranchRestriction> = self
+            .call_paginated(
+                &url,
+                &format!("&pattern={}", encode(&protection.branch)),
+                None,
+            )?
+            .into_iter()
+            .filter(|r: &BitbucketBranchRestriction| {
+                r.branch_match_kind == "glob" && r.pattern == protection.branch
+            })
+            .collect();
+
+        // Create, update or delete every restriction, so running it again gives the same result
+        let desired = [
+            (
+                "require_approvals_to_merge",
+                protection.required_approvals > 0,
+                Some(protection.required_approvals),
+            ),
+            ("force", !protection.allow_force_push, None),
+        ];
+        for (kind, enabled, value) in desired {
+            let restriction = BitbucketBranchRestriction {
+                id: None,
+                kind: kind.to_string(),
+                branch_match_kind: "glob".to_string(),
+                pattern: protection.branch.clone(),
+                value,
+            };
+            match (existing.iter().find(|r| r.kind == kind), enabled) {
+                (Some(BitbucketBranchRestriction { id: Some(id), .. }), true) => {
+                    self.call::<BitbucketBranchRestriction, _>(
+                        "PUT",
+                        &format!("{url}/{id}"),
+                        Some(restriction),
+                    )?;
+                }
+                (Some(BitbucketBranchRestriction { id: Some(id), .. }), false) => {
+                    self.call::<(), i32>("DELETE", &format!("{url}/{id}"), None)?;
+                }
+                (None, true) => {
+                    self.call::<BitbucketBranchRestriction, _>("POST", &url, Some(restriction))?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(protection)
+    }
+
+    #[instrument(skip_all)]
+    fn list_collaborators(&self) -> Result<Vec<Collaborator>> {
+        let permissions: Vec<BitbucketUserPermission> = self.call_paginated(
+            &self.get_repository_url("/permissions-config/users"),
+            "",
+            None,
+        )?;
+
+        Ok(permissions.into_iter().map(|p| p.into()).collect())
+    }
+
+    #[instrument(skip_all)]
+    fn add_collaborator(&self, username: &str, permission: CollaboratorPermission) -> Result<()> {
+        let user = self.get_workspace_user(username)?;
+        let _: BitbucketUserPermission = self.call(
+            "PUT",
+            &self.get_repository_url(&format!("/permissions-config/users/{}", encode(&user.uuid))),
+            Some(BitbucketUpdatePermission {
+                permission: match permission {
+                    CollaboratorPermission::Read => "read",
+                    CollaboratorPermission::Write => "write",
+                    CollaboratorPermission::Admin => "admin",
+                }
+                .to_string(),
+            }),
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn remove_collaborator(&self, username: &str) -> Result<()> {
+        let user = self.get_workspace_user(username)?;
+        self.call::<(), i32>(
+            "DELETE",
+            &self.get_repository_url(&format!("/permissions-config/users/{}", encode(&user.uuid))),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn list_webhooks(&self) -> Result<Vec<Webhook>> {
+        let webhooks: Vec<BitbucketWebhook> =
+            self.call_paginated(&self.get_repository_url("/hooks"), "", None)?;
+
+        Ok(webhooks.into_iter().map(|w| w.into()).collect())
+    }
+
+    #[instrument(skip_all)]
+    fn create_webhook(&self, webhook: CreateWebhook) -> Result<Webhook> {
+        let CreateWebhook {
+            url,
+            secret,
+            content_type,
+            events,
+        } = webhook;
+        if content_type == WebhookContentType::Form {
+            return Err(eyre!(
+                "Sending webhooks as forms is not supported by Bitbucket."
+            ));
+        }
+
+        let mut bitbucket_events: Vec<&str> = vec![];
+        for event in events {
+            match event {
+                // Tags are sent with the push event
+                WebhookEvent::Push | WebhookEvent::Tag => bitbucket_events.push("repo:push"),
+                WebhookEvent::PullRequest => bitbucket_events.extend([
+                    "pullrequest:created",
+                    "pullrequest:updated",
+                    "pullrequest:fulfilled",
+                    "pullrequest:rejected",
+                ]),
+                WebhookEvent::Issue => bitbucket_events.extend(["issue:created", "issue:updated"]),
+                WebhookEvent::Release => {
+                    return Err(eyre!("Release webhooks are not supported by Bitbucket."))
+                }
+            }
+        }
+        bitbucket_events.sort();
+        bitbucket_events.dedup();
+
+        let webhook: BitbucketWebhook = self.call_secret(
+            "POST",
+            &self.get_repository_url("/hooks"),
+            Some(BitbucketCreateWebhook {
+                description: url.clone(),
+                url,
+                active: true,
+                secret,
+                events: bitbucket_events
+                    .into_iter()
+                    .map(|e| e.to_string())
+                    .collect(),
+            }),
+        )?;
+
+        Ok(webhook.into())
+    }
+
+    #[instrument(skip_all)]
+    fn delete_webhook(&self, id: &str) -> Result<()> {
+        self.call::<(), i32>(
+            "DELETE",
+            &self.get_repository_url(&format!("/hooks/{}", encode(id))),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn test_webhook(&self, _: &str) -> Result<()> {
+        Err(eyre!("Testing webhooks is not supported by Bitbucket."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_secrets(&self, scope: &VariableScope) -> Result<Vec<Secret>> {
+        // Secrets are the secured pipeline variables
+        let variables: Vec<BitbucketPipelineVariable> =
+            self.call_paginated(&self.get_scope_url(scope, "/variables"), "", None)?;
+
+        Ok(variables
+            .into_iter()
+            .filter(|v| v.secured)
+            .map(|v| Secret { name: v.key })
+            .collect())
+    }
+
+    #[instrument(skip_all)]
+    fn set_secret(&self, scope: &VariableScope, name: &str, value: &str) -> Result<()> {
+        self.set_pipeline_variable(scope, name.to_string(), value.to_string(), true)
+    }
+
+    #[instrument(skip_all)]
+    fn delete_secret(&self, scope: &VariableScope, name: &str) -> Result<()> {
+        self.delete_pipeline_variable(scope, name)
+    }
+
+    #[instrument(skip_all)]
+    fn list_variables(&self, scope: &VariableScope) -> Result<Vec<Variable>> {
+        let variables: Vec<BitbucketPipelineVariable> =
+            self.call_paginated(&self.get_scope_url(scope, "/variables"), "", None)?;
+
+        Ok(variables
+            .into_iter()
+            .filter(|v| !v.secured)
+            .map(|v| Variable {
+                name: v.key,
+                value: v.value,
+                protected: false,
+                masked: false,
+            })
+            .collect())
+    }
+
+    #[instrument(skip_all)]
+    fn set_variable(&self, scope: &VariableScope, variable: Variable) -> Result<()> {
+        let Variable {
+            name,
+            value,
+            protected,
+            masked,
+        } = variable;
+        if protected || masked {
+            return Err(eyre!(
+                "Protected and masked variables are not supported by Bitbucket, use a secret."
+            ));
+        }
+
+        self.set_pipeline_variable(scope, name, value, false)
+    }
+
+    #[instrument(skip_all)]
+    fn delete_variable(&self, scope: &VariableScope, name: &str) -> Result<()> {
+        self.delete_pipeline_variable(scope, name)
+    }
+
+    #[instrument(skip_all)]
+    fn create_release(&self, release: CreateRelease) -> Result<Release> {
+        let CreateRelease {
+            tag,
+            target,
+            name,
+            description,
+            draft,
+            prerelease,
+        } = release;
+        if draft {
+            return Err(eyre!("Draft releases are not supported by Bitbucket."));
+        }
+        if prerelease {
+            return Err(eyre!("Marking prereleases is not supported by Bitbucket."));
+        }
+        if name.is_some() || !description.is_empty() {
+            return Err(eyre!(
+                "Release names and notes are not supported by Bitbucket."
+            ));
+        }
+
+        // Use the existing tag, or create it on the given commit
+        let url = self.get_repository_url("/refs/tags");
+        let tag: BitbucketTag =
+            match self.call::<BitbucketTag, i32>("GET", &format!("{url}/{}", encode(&tag)), None) {
+                Ok(tag) => tag,
+                Err(_) => {
+                    let target = target.wrap_err(eyre!(
+                        "Tag {tag} doesn't exist, push it first or give a target commit."
+                    ))?;
+                    self.call(
+                        "POST",
+                        &url,
+                        Some(BitbucketCreateTag {
+                            name: tag,
+                            target: BitbucketCreateTagTarget { hash: target },
+                        }),
+                    )?
+                }
+            };
+
+        Ok(self.tag_into_release(tag))
+    }
+
+    #[instrument(skip_all)]
+    fn get_release(&self, tag: &str) -> Result<Release> {
+        let tag: BitbucketTag = self.call(
+            "GET",
+            &self.get_repository_url(&format!("/refs/tags/{}", encode(tag))),
+            None as Option<i32>,
+        )?;
+
+        Ok(self.tag_into_release(tag))
+    }
+
+    #[instrument(skip_all)]
+    fn list_releases(&self) -> Result<Vec<Release>> {
+        let tags: Vec<BitbucketTag> = self.call_paginated(
+            &self.get_repository_url("/refs/tags"),
+            "&sort=-target.date",
+            None,
+        )?;
+
+        Ok(tags.into_iter().map(|t| self.tag_into_release(t)).collect())
+    }
+
+    #[instrument(skip_all)]
+    fn delete_release(&self, _: &str) -> Result<()> {
+        Err(eyre!("Deleting releases is not supported by Bitbucket."))
+    }
+
+    #[instrument(skip(self, content))]
+    fn upload_release_asset(&self, _: &str, name: &str, content: &[u8]) -> Result<ReleaseAsset> {
+        // Files are not attached to tags, they are added to the repository downloads
+        let (content_type, body) = multipart_file("files", name, content);
+        self.upload::<()>(&self.get_repository_url("/downloads"), &content_type, &body)?;
+
+        Ok(ReleaseAsset {
+            id: name.to_string(),
+            name: name.to_string(),
+            url: format!(
+                "https://bitbucket.org/{}/downloads/{}",
+                self.repo,
+                encode(name)
+            ),
+            size: Some(content.len() as u64),
+        })
+    }
+
+    #[instrument(skip(self, writer))]
+    fn download_release_asset(&self, asset: &ReleaseAsset, writer: &mut dyn Write) -> Result<u64> {
+        self.download(
+            &format!(
+                "https://api.bitbucket.org/2.0{}",
+                self.get_repository_url(&format!("/downloads/{}", encode(&asset.name)))
+            ),
+            writer,
+            asset.size,
+        )
+    }
 }
@@ -1,11 +1,19 @@
 use eyre::{eyre, Result};
 use open::that as open_in_browser;
 use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display},
+    io::{self, Write},
+};
 use time::OffsetDateTime;
-use tracing::warn;
+use tracing::{info, warn};
+use ureq::Response;
 
 use crate::formatters::formatter::{Formatter, FormatterType};
-use crate::vcs::{bitbucket::Bitbucket, gitea::Gitea, github::GitHub, gitlab::GitLab};
+use crate::vcs::{
+    bitbucket::Bitbucket, gerrit::Gerrit, gitea::Gitea, github::GitHub, gitlab::GitLab, gogs::Gogs,
+    sourcehut::SourceHut,
+};
 
 #[derive(Debug, Default, Deserialize, Serialize)]
 pub struct User {
@@ -13,6 +21,131 @@ pub struct User {
     pub username: String,
 }
 
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CollaboratorPermission {
+    Read,
+    Write,
+    Admin,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Collaborator {
+    pub user: User,
+    pub permission: CollaboratorPermission,
+}
+
+#[derive(Debug, Clone)]
+pub enum VariableScope {
+    Repository,
+    Organization(String),
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Secret {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Variable {
+    pub name: String,
+    pub value: String,
+    pub protected: bool,
+    pub masked: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DeployKey {
+    pub id: String,
+    pub title: String,
+    pub key: String,
+    pub read_only: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateDeployKey {
+    pub title: String,
+    pub key: String,
+    pub read_only: bool,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    Push,
+    PullRequest,
+    Tag,
+    Release,
+    Issue,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookContentType {
+    #[default]
+    Json,
+    Form,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Webhook {
+    pub id: String,
+    pub url: String,
+    pub events: Vec<WebhookEvent>,
+    pub content_type: WebhookContentType,
+    pub active: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateWebhook {
+    pub url: String,
+    pub secret: Option<String>,
+    pub content_type: WebhookContentType,
+    pub events: Vec<WebhookEvent>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ReleaseAsset {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    pub size: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Release {
+    pub id: String,
+    pub tag: String,
+    pub name: String,
+    pub description: String,
+    pub draft: bool,
+    pub prerelease: bool,
+    #[serde(with = "time::serde::iso8601")]
+    pub created_at: OffsetDateTime,
+    pub url: String,
+    pub assets: Vec<ReleaseAsset>,
+}
+
+impl Release {
+    pub fn print(&self, in_browser: bool, formatter_type: FormatterType) {
+        // Open in browser if open is true
+        if in_browser && open_in_browser(&self.url).is_ok() {
+            return;
+        }
+        print!("{}", self.show(formatter_type));
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateRelease {
+    pub tag: String,
+    pub target: Option<String>,
+    pub name: Option<String>,
+    pub description: String,
+    pub draft: bool,
+    pub prerelease: bool,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub enum PullRequestState {
     Open,
@@ -60,6 +193,14 @@ pub struct CreatePullRequest {
     pub target: Option<String>,
     pub close_source_branch: bool,
     pub reviewers: Vec<String>,
+    pub labels: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct UpdatePullRequest {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub target: Option<String>,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize)]
@@ -85,6 +226,51 @@ pub struct ListPullRequestFilters {
     pub state: PullRequestStateFilter,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub enum IssueState {
+    Open,
+    Closed,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Issue {
+    pub id: u32,
+    pub state: IssueState,
+    pub title: String,
+    pub description: String,
+    pub labels: Vec<String>,
+    pub url: String,
+    #[serde(with = "time::serde::iso8601")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::iso8601")]
+    pub updated_at: OffsetDateTime,
+    pub author: Option<User>,
+}
+
+impl Issue {
+    pub fn print(&self, in_browser: bool, formatter_type: FormatterType) {
+        // Open in browser if open is true
+        if in_browser && open_in_browser(&self.url).is_ok() {
+            return;
+        }
+        print!("{}", self.show(formatter_type));
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateIssue {
+    pub title: String,
+    pub description: String,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub enum IssueStateFilter {
+    #[default]
+    Open,
+    Closed,
+    All,
+}
+
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub enum RepositoryVisibility {
     Public,
@@ -130,6 +316,31 @@ impl Repository {
     }
 }
 
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct BranchProtection {
+    pub branch: String,
+    pub required_approvals: u32,
+    pub required_status_checks: Vec<String>,
+    pub allow_force_push: bool,
+    pub linear_history: bool,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct ListRepositoryFilters {
+    pub archived: bool,
+    pub visibility: Option<RepositoryVisibility>,
+}
+
+impl ListRepositoryFilters {
+    pub fn matches(&self, repo: &Repository) -> bool {
+        (!self.archived || repo.archived)
+            && self
+                .visibility
+                .as_ref()
+                .is_none_or(|visibility| visibility == &repo.visibility)
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct CreateRepository {
     pub name: String,
@@ -142,19 +353,99 @@ pub struct CreateRepository {
     pub license: Option<String>,
 }
 
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct UpdateRepository {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub visibility: Option<RepositoryVisibility>,
+    pub default_branch: Option<String>,
+    pub homepage: Option<String>,
+    pub topics: Option<Vec<String>>,
+    pub archived: Option<bool>,
+    pub allow_merge_commit: Option<bool>,
+    pub allow_squash_merge: Option<bool>,
+    pub allow_rebase_merge: Option<bool>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ForkRepository {
     pub name: Option<String>,
     pub organization: Option<String>,
 }
 
+#[derive(Debug, Default, Clone, Copy, Deserialize, Serialize)]
+pub enum ArchiveFormat {
+    #[default]
+    TarGz,
+    Zip,
+}
+
+impl ArchiveFormat {
+    pub fn extension(&self) -> &str {
+        match self {
+            ArchiveFormat::TarGz => "tar.gz",
+            ArchiveFormat::Zip => "zip",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum ForkSync {
+    FastForward,
+    Merge,
+    UpToDate,
+    // The forge has no API to sync forks, it has to be done locally
+    Unsupported,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum BranchUpdate {
+    Updated,
+    // The forge has no API to update the branch this way, it has to be done locally
+    Unsupported,
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct VersionControlSettings {
     pub auth: String,
     pub vcs_type: Option<String>,
     pub default_branch: Option<String>,
-    pub fork: bool,
+    pub upstream: Option<String>,
+    pub sendmail: Option<String>,
+    pub mailing_list: Option<String>,
+}
+
+/// A request that the server answered with an error status
+#[derive(Debug)]
+pub struct RequestError {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Request failed (response: {}).", self.body)
+    }
 }
+
+impl std::error::Error for RequestError {}
+
+/// Check if the request failed with this status, e.g. to handle missing resources.
+pub fn is_status(error: &eyre::Report, status: u16) -> bool {
+    error
+        .downcast_ref::<RequestError>()
+        .is_some_and(|error| error.status == status)
+}
+
+// Turn a missing resource into None, but keep every other error
+pub fn found<T>(result: Result<T>) -> Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(err) if is_status(&err, 404) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
 pub trait VersionControl {
     fn init(hostname: String, repo: String, settings: VersionControlSettings) -> Self
     where
@@ -163,21 +454,130 @@ pub trait VersionControl {
     // Login
     fn login_url(&self) -> String;
     fn validate_token(&self, token: &str) -> Result<()>;
+    fn get_current_user(&self) -> Result<User>;
 
     // Pull requests
+    // Whether the branch has to be pushed before creating a pull request from it
+    fn requires_pushed_branch(&self) -> Result<bool>;
+    // Whether created pull requests are kept on the server, so they can be opened and merged
+    fn tracks_pull_requests(&self) -> Result<bool>;
     fn create_pr(&self, pr: CreatePullRequest) -> Result<PullRequest>;
+    fn update_pr(&self, id: u32, pr: UpdatePullRequest) -> Result<PullRequest>;
     fn get_pr_by_id(&self, id: u32) -> Result<PullRequest>;
     fn get_pr_by_branch(&self, branch: &str) -> Result<PullRequest>;
     fn list_prs(&self, filters: ListPullRequestFilters) -> Result<Vec<PullRequest>>;
     fn approve_pr(&self, id: u32) -> Result<()>;
     fn close_pr(&self, id: u32) -> Result<PullRequest>;
     fn merge_pr(&self, id: u32, delete_source_branch: bool) -> Result<PullRequest>;
+    fn update_pr_branch(&self, id: u32, head_sha: &str, rebase: bool) -> Result<BranchUpdate>;
+
+    // Issues
+    fn create_issue(&self, issue: CreateIssue) -> Result<Issue>;
+    fn get_issue(&self, id: u32) -> Result<Issue>;
+    fn list_issues(&self, state: IssueStateFilter) -> Result<Vec<Issue>>;
+    fn close_issue(&self, id: u32) -> Result<Issue>;
 
     // Repositories
     fn get_repository(&self) -> Result<Repository>;
+    fn list_repositories(
+        &self,
+        owner: Option<&str>,
+        filters: ListRepositoryFilters,
+    ) -> Result<Vec<Repository>>;
+    fn search_repositories(&self, query: &str, limit: usize) -> Result<Vec<Repository>>;
     fn create_repository(&self, repo: CreateRepository) -> Result<Repository>;
     fn fork_repository(&self, repo: ForkRepository) -> Result<Repository>;
+    fn update_repository(&self, repo: UpdateRepository) -> Result<Repository>;
+    fn transfer_repository(&self, owner: &str) -> Result<Repository>;
     fn delete_repository(&self) -> Result<()>;
+    fn sync_fork(&self, branch: &str) -> Result<ForkSync>;
+    fn download_archive(
+        &self,
+        reference: &str,
+        format: ArchiveFormat,
+        writer: &mut dyn Write,
+    ) -> Result<u64>;
+
+    // Deploy keys
+    fn list_deploy_keys(&self) -> Result<Vec<DeployKey>>;
+    fn add_deploy_key(&self, key: CreateDeployKey) -> Result<DeployKey>;
+    fn remove_deploy_key(&self, id: &str) -> Result<()>;
+
+    // Branch protections
+    fn list_branch_protections(&self) -> Result<Vec<BranchProtection>>;
+    fn protect_branch(&self, protection: BranchProtection) -> Result<BranchProtection>;
+
+    // Collaborators
+    fn list_collaborators(&self) -> Result<Vec<Collaborator>>;
+    fn add_collaborator(&self, username: &str, permission: CollaboratorPermission) -> Result<()>;
+    fn remove_collaborator(&self, username: &str) -> Result<()>;
+
+    // Webhooks
+    fn list_webhooks(&self) -> Result<Vec<Webhook>>;
+    fn create_webhook(&self, webhook: CreateWebhook) -> Result<Webhook>;
+    fn delete_webhook(&self, id: &str) -> Result<()>;
+    fn test_webhook(&self, id: &str) -> Result<()>;
+
+    // CI secrets and variables
+    fn list_secrets(&self, scope: &VariableScope) -> Result<Vec<Secret>>;
+    fn set_secret(&self, scope: &VariableScope, name: &str, value: &str) -> Result<()>;
+    fn delete_secret(&self, scope: &VariableScope, name: &str) -> Result<()>;
+    fn list_variables(&self, scope: &VariableScope) -> Result<Vec<Variable>>;
+    fn set_variable(&self, scope: &VariableScope, variable: Variable) -> Result<()>;
+    fn delete_variable(&self, scope: &VariableScope, name: &str) -> Result<()>;
+
+    // Releases
+    fn create_release(&self, release: CreateRelease) -> Result<Release>;
+    fn get_release(&self, tag: &str) -> Result<Release>;
+    fn list_releases(&self) -> Result<Vec<Release>>;
+    fn delete_release(&self, tag: &str) -> Result<()>;
+    fn upload_release_asset(&self, tag: &str, name: &str, content: &[u8]) -> Result<ReleaseAsset>;
+    fn download_release_asset(&self, asset: &ReleaseAsset, writer: &mut dyn Write) -> Result<u64>;
+}
+
+// Stream a response body into the writer, returns the number of bytes written
+pub(crate) fn save_response(
+    response: Response,
+    writer: &mut dyn Write,
+    expected_size: Option<u64>,
+) -> Result<u64> {
+    // The length is only reliable if the body is not decompressed on the fly
+    let expected_size = expected_size.or_else(|| {
+        response
+            .header("Content-Length")
+            .filter(|_| response.header("Content-Encoding").is_none())
+            .and_then(|length| length.parse().ok())
+    });
+
+    let size = io::copy(&mut response.into_reader(), writer)?;
+    writer.flush()?;
+
+    info!("Downloaded {size} bytes.");
+
+    match expected_size {
+        Some(expected_size) if expected_size != size => Err(eyre!(
+            "Download is incomplete, received {size} bytes instead of {expected_size}."
+        )),
+        _ => Ok(size),
+    }
+}
+
+// Build a multipart/form-data body with a single file, returns the content type and the body
+pub(crate) fn multipart_file(field: &str, filename: &str, content: &[u8]) -> (String, Vec<u8>) {
+    let boundary = format!(
+        "gr-boundary-{}",
+        OffsetDateTime::now_utc().unix_timestamp_nanos()
+    );
+
+    let mut body = format!(
+        "--{boundary}\r\nContent-Disposition: form-data; name=\"{field}\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+        filename.replace('"', "")
+    )
+    .into_bytes();
+    body.extend_from_slice(content);
+    body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+
+    (format!("multipart/form-data; boundary={boundary}"), body)
 }
 
 pub fn init_vcs(
@@ -191,6 +591,9 @@ pub fn init_vcs(
             "bitbucket" => Ok(Box::new(Bitbucket::init(hostname, repo, settings))),
             "gitlab" => Ok(Box::new(GitLab::init(hostname, repo, settings))),
             "gitea" => Ok(Box::new(Gitea::init(hostname, repo, settings))),
+            "gogs" => Ok(Box::new(Gogs::init(hostname, repo, settings))),
+            "gerrit" => Ok(Box::new(Gerrit::init(hostname, repo, settings))),
+            "sourcehut" => Ok(Box::new(SourceHut::init(hostname, repo, settings))),
             _ => Err(eyre!("Server type {vcs_type} not found.")),
         }
     } else {
@@ -198,6 +601,7 @@ pub fn init_vcs(
             "github.com" => Ok(Box::new(GitHub::init(hostname, repo, settings))),
             "bitbucket.org" => Ok(Box::new(Bitbucket::init(hostname, repo, settings))),
             "gitlab.com" => Ok(Box::new(GitLab::init(hostname, repo, settings))),
+            "git.sr.ht" => Ok(Box::new(SourceHut::init(hostname, repo, settings))),
             _ => {
                 // Take some guesses what the host might be
                 if hostname.contains("github") {
@@ -213,6 +617,16 @@ pub fn init_vcs(
                         "Assuming the host to be Gitea (if it is incorrect, add --type at login)."
                     );
                     Ok(Box::new(Gitea::init(hostname, repo, settings)))
+                } else if hostname.contains("gogs") {
+                    warn!(
+                        "Assuming the host to be Gogs (if it is incorrect, add --type at login)."
+                    );
+                    Ok(Box::new(Gogs::init(hostname, repo, settings)))
+                } else if hostname.contains("gerrit") {
+                    warn!(
+                        "Assuming the host to be Gerrit (if it is incorrect, add --type at login)."
+                    );
+                    Ok(Box::new(Gerrit::init(hostname, repo, settings)))
                 }
                 // Probably there should more detections down the line
                 else {
@@ -0,0 +1,840 @@
+// Documentation: https://gerrit-review.googlesource.com/Documentation/rest-api.html
+use super::common::{
+    ArchiveFormat, BranchProtection, BranchUpdate, Collaborator, CollaboratorPermission,
+    CreateDeployKey, CreateIssue, CreatePullRequest, CreateRelease, CreateRepository,
+    CreateWebhook, DeployKey, ForkRepository, ForkSync, Issue, IssueStateFilter,
+    ListPullRequestFilters, ListRepositoryFilters, PullRequest, PullRequestState,
+    PullRequestStateFilter, PullRequestUserFilter, Release, ReleaseAsset, Repository,
+    RepositoryVisibility, RequestError, Secret, UpdatePullRequest, UpdateRepository, User,
+    Variable, VariableScope, VersionControl, VersionControlSettings, Webhook,
+};
+use crate::git::git::LocalRepository;
+use eyre::{eyre, ContextCompat, Result};
+use native_tls::TlsConnector;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{collections::HashMap, fmt::Debug, io::Write, iter::once, sync::Arc};
+use time::OffsetDateTime;
+use tracing::{info, instrument, trace};
+use ureq::{Agent, AgentBuilder, Error};
+use urlencoding::encode;
+
+// Gerrit prefixes every JSON response with this to prevent XSSI attacks
+const XSSI_PREFIX: &str = ")]}'";
+
+// Gerrit timestamps are in UTC, in the format of "2013-02-01 09:59:32.126000000"
+mod gerrit_timestamp {
+    use serde::{
+        de::Error as DeError, ser::Error as SerError, Deserialize, Deserializer, Serializer,
+    };
+    use time::{format_description, OffsetDateTime, PrimitiveDateTime};
+
+    const FORMAT: &str = "[year]-[month]-[day] [hour]:[minute]:[second].[subsecond]";
+
+    pub fn serialize<S: Serializer>(
+        date: &OffsetDateTime,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let format = format_description::parse(FORMAT).map_err(S::Error::custom)?;
+        let date = date.format(&format).map_err(S::Error::custom)?;
+        serializer.serialize_str(&date)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<OffsetDateTime, D::Error> {
+        let date = String::deserialize(deserializer)?;
+        let format = format_description::parse(FORMAT).map_err(D::Error::custom)?;
+        PrimitiveDateTime::parse(&date, &format)
+            .map(|d| d.assume_utc())
+            .map_err(D::Error::custom)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GerritAccount {
+    #[serde(rename = "_account_id")]
+    pub account_id: u32,
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub username: Option<String>,
+}
+
+impl From<GerritAccount> for User {
+    fn from(user: GerritAccount) -> User {
+        let GerritAccount {
+            account_id,
+            name,
+            email,
+            username,
+        } = user;
+        User {
+            id: account_id.to_string(),
+            username: username.or(email).or(name).unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub enum GerritChangeStatus {
+    #[serde(rename = "NEW")]
+    New,
+    #[serde(rename = "MERGED")]
+    Merged,
+    #[serde(rename = "ABANDONED")]
+    Abandoned,
+}
+
+impl From<GerritChangeStatus> for PullRequestState {
+    fn from(status: GerritChangeStatus) -> PullRequestState {
+        match status {
+            GerritChangeStatus::New => PullRequestState::Open,
+            GerritChangeStatus::Merged => PullRequestState::Merged,
+            GerritChangeStatus::Abandoned => PullRequestState::Closed,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GerritParentCommit {
+    pub commit: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GerritCommit {
+    pub message: String,
+    pub parents: Vec<GerritParentCommit>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GerritRevision {
+    #[serde(rename = "_number")]
+    pub number: u32,
+    #[serde(rename = "ref")]
+    pub reference: String,
+    pub commit: Option<GerritCommit>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GerritChange {
+    pub id: String,
+    pub project: String,
+    pub branch: String,
+    pub topic: Option<String>,
+    pub change_id: String,
+    pub subject: String,
+    pub status: GerritChangeStatus,
+    #[serde(with = "gerrit_timestamp")]
+    pub created: OffsetDateTime,
+    #[serde(with = "gerrit_timestamp")]
+    pub updated: OffsetDateTime,
+    #[serde(rename = "_number")]
+    pub number: u32,
+    pub owner: GerritAccount,
+    pub submitter: Option<GerritAccount>,
+    pub current_revision: Option<String>,
+    #[serde(default)]
+    pub revisions: HashMap<String, GerritRevision>,
+    #[serde(default)]
+    pub reviewers: HashMap<String, Vec<GerritAccount>>,
+}
+
+impl GerritChange {
+    fn into_pull_request(self, hostname: &str) -> PullRequest {
+        let GerritChange {
+            project,
+            branch,
+            topic,
+            change_id,
+            subject,
+            status,
+            created,
+            updated,
+            number,
+            owner,
+            submitter,
+            current_revision,
+            mut revisions,
+            mut reviewers,
+            ..
+        } = self;
+        let commit = current_revision
+            .as_ref()
+            .and_then(|sha| revisions.remove(sha))
+            .and_then(|revision| revision.commit);
+        // The description is the commit message without the subject line
+        let description = commit
+            .as_ref()
+            .and_then(|c| c.message.split_once('\n'))
+            .map(|(_, body)| body.trim().to_string())
+            .unwrap_or_default();
+        let target_sha = commit
+            .and_then(|c| c.parents.into_iter().next())
+            .map(|p| p.commit)
+            .unwrap_or_default();
+        PullRequest {
+            id: number,
+            state: status.into(),
+            title: subject,
+            description,
+            // We push with the local branch as topic, so we can find it later
+            source: topic.unwrap_or(change_id),
+            target: branch,
+            source_sha: current_revision.unwrap_or_default(),
+            target_sha,
+            url: format!("https://{hostname}/c/{project}/+/{number}"),
+            created_at: created,
+            updated_at: updated,
+            author: owner.into(),
+            closed_by: submitter.map(|s| s.into()),
+            reviewers: reviewers
+                .remove("REVIEWER")
+                .map(|rs| rs.into_iter().map(|r| r.into()).collect()),
+            delete_source_branch: false,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GerritReview {
+    pub labels: HashMap<String, i32>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GerritReviewResult {
+    pub labels: Option<HashMap<String, i32>>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub enum GerritProjectState {
+    #[serde(rename = "ACTIVE")]
+    Active,
+    #[serde(rename = "READ_ONLY")]
+    ReadOnly,
+    #[serde(rename = "HIDDEN")]
+    Hidden,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GerritProject {
+    pub id: String,
+    // The name is omitted when listing projects, the keys of the map are the names
+    #[serde(default)]
+    pub name: String,
+    pub parent: Option<String>,
+    pub description: Option<String>,
+    pub state: Option<GerritProjectState>,
+}
+
+impl GerritProject {
+    fn into_repository(self, hostname: &str, default_branch: String) -> Repository {
+        let GerritProject {
+            name,
+            description,
+            state,
+            ..
+        } = self;
+
+        Repository {
+            name: name.rsplit('/').next().unwrap_or(&name).to_string(),
+            full_name: name.clone(),
+            owner: None,
+            html_url: format!("https://{hostname}/admin/repos/{name}"),
+            ssh_url: format!("ssh://{hostname}:29418/{name}"),
+            https_url: format!("https://{hostname}/{name}"),
+            description: description.unwrap_or_default(),
+            // Gerrit doesn't store when the project was created or updated
+            created_at: OffsetDateTime::UNIX_EPOCH,
+            updated_at: OffsetDateTime::UNIX_EPOCH,
+            // Visibility is handled with access rights, it cannot be queried simply
+            visibility: RepositoryVisibility::Public,
+            archived: matches!(state, Some(GerritProjectState::ReadOnly)),
+            default_branch,
+            forks_count: 0,
+            stars_count: 0,
+            forked_from: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GerritCreateProject {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branches: Option<Vec<String>>,
+    pub create_empty_commit: bool,
+}
+
+#[derive(Debug)]
+pub struct Gerrit {
+    settings: VersionControlSettings,
+    client: Agent,
+    hostname: String,
+    repo: String,
+}
+
+impl Gerrit {
+    #[instrument(skip_all)]
+    fn get_change_url(&self, id: u32, url: &str) -> String {
+        format!("/changes/{}~{id}{url}", encode(&self.repo))
+    }
+
+    #[instrument(skip_all)]
+    fn get_project_url(&self, url: &str) -> String {
+        format!("/projects/{}{}", encode(&self.repo), url)
+    }
+
+    #[instrument(skip_all)]
+    fn call<T: DeserializeOwned, U: Serialize + Debug>(
+        &self,
+        method: &str,
+        url: &str,
+        body: Option<U>,
+    ) -> Result<T> {
+        // Authenticated endpoints are under the /a/ prefix
+        let url = format!("https://{}/a{}", self.hostname, url);
+
+        info!("Calling with {method} on {url}.");
+
+        let (username, password) = self
+            .settings
+            .auth
+            .split_once(':')
+            .wrap_err("Authentication has to contain a username and an HTTP password.")?;
+
+        trace!("Authenticating with username '{username}' and password '{password}'.");
+
+        let request = self
+            .client
+            .request(method, &url)
+            .set(
+                "Authorization",
+                &format!("Basic {}", base64::encode(format!("{username}:{password}"))),
+            )
+            .set("Content-Type", "application/json");
+        let result = if let Some(body) = &body {
+            trace!("Sending body: {}.", serde_json::to_string(&body)?);
+            request.send_json(body)
+        } else {
+            request.call()
+        };
+
+        match result {
+            Ok(result) => {
+                let status = result.status();
+                let t = result.into_string()?;
+
+                info!(
+                    "Received response with response code {} with body size {}.",
+                    status,
+                    t.len()
+                );
+                trace!("Response body: {t}.");
+
+                // Strip the XSSI prefix, if the response is empty, return null
+                let mut t = t.strip_prefix(XSSI_PREFIX).unwrap_or(&t).trim().to_string();
+                if t.is_empty() {
+                    t = "null".to_string();
+                }
+
+                let t: T = serde_json::from_str(&t)?;
+                Ok(t)
+            }
+            Err(Error::Status(status, result)) => {
+                let t = result.into_string()?;
+
+                info!(
+                    "Received response with response code {} with body size {}.",
+                    status,
+                    t.len()
+                );
+                Err(RequestError {
+                    status,
+                    body: t.trim().to_string(),
+                }
+                .into())
+            }
+            Err(Error::Transport(_)) => Err(eyre!("Sending data failed.")),
+        }
+    }
+
+    #[instrument(skip(self))]
+    fn query_changes(&self, query: &str) -> Result<Vec<GerritChange>> {
+        self.call(
+            "GET",
+            &format!(
+                "/changes/?q={query}&o=CURRENT_REVISION&o=CURRENT_COMMIT&o=DETAILED_ACCOUNTS&o=DETAILED_LABELS"
+            ),
+            None as Option<i32>,
+        )
+    }
+
+    #[instrument(skip(self))]
+    fn list_projects(&self, params: &str, limit: usize) -> Result<Vec<Repository>> {
+        let mut repos: Vec<Repository> = vec![];
+        let mut i = 0;
+        while repos.len() < limit {
+            info!("Reading page {}.", i + 1);
+
+            let page: HashMap<String, GerritProject> = self.call(
+                "GET",
+                &format!("/projects/?d&n=100&S={}{params}", i * 100),
+                None as Option<i32>,
+            )?;
+
+            if page.is_empty() {
+                break;
+            }
+
+            // The HEAD of the projects is not returned when listing
+            let mut page = page
+                .into_iter()
+                .map(|(name, project)| {
+                    GerritProject { name, ..project }.into_repository(&self.hostname, String::new())
+                })
+                .collect::<Vec<Repository>>();
+            page.sort_by(|a, b| a.full_name.cmp(&b.full_name));
+            repos.append(&mut page);
+
+            i += 1;
+        }
+        repos.truncate(limit);
+
+        Ok(repos)
+    }
+
+    #[instrument(skip_all)]
+    fn get_default_branch(&self) -> Result<String> {
+        let head: String = self.call("GET", &self.get_project_url("/HEAD"), None as Option<i32>)?;
+
+        Ok(head.trim_start_matches("refs/heads/").to_string())
+    }
+}
+
+impl VersionControl for Gerrit {
+    #[instrument(skip_all)]
+    fn init(hostname: String, repo: String, settings: VersionControlSettings) -> Self {
+        let client = AgentBuilder::new()
+            .tls_connector(Arc::new(TlsConnector::new().unwrap()))
+            .build();
+        Gerrit {
+            settings,
+            client,
+            hostname,
+            repo,
+        }
+    }
+
+    #[instrument(skip_all)]
+    fn login_url(&self) -> String {
+        format!("https://{}/settings/#HTTPCredentials", self.hostname)
+    }
+
+    #[instrument(skip_all)]
+    fn validate_token(&self, token: &str) -> Result<()> {
+        if !token.contains(':') {
+            Err(eyre!("Enter your Gerrit username and the HTTP password, separated with a colon (user:password)."))
+        } else {
+            Ok(())
+        }
+    }
+
+    #[instrument(skip_all)]
+    fn get_current_user(&self) -> Result<User> {
+        let account: GerritAccount = self.call("GET", "/accounts/self", None as Option<i32>)?;
+
+        Ok(account.into())
+    }
+
+    #[instrument(skip(self))]
+    fn requires_pushed_branch(&self) -> Result<bool> {
+        // Changes are pushed to the magic branch on create
+        Ok(false)
+    }
+
+    #[instrument(skip(self))]
+    fn tracks_pull_requests(&self) -> Result<bool> {
+        Ok(true)
+    }
+
+    #[instrument(skip(self))]
+    fn create_pr(&self, pr: CreatePullRequest) -> Result<PullRequest> {
+        let CreatePullRequest {
+            source,
+            target,
+            reviewers,
+            labels,
+            ..
+        } = pr;
+        let target = match target.or(self.settings.default_branch.clone()) {
+            Some(target) => target,
+            None => self.get_default_branch()?,
+        };
+        info!("Using {target} as target branch.");
+
+        // Changes are created by pushing the commits to the magic branch, the title and
+        // the description are always coming from the commit message
+        let repository = LocalRepository::init(None)?;
+        let (remote, _) = repository.get_branch_upstream(Some(source.clone()), None)?;
+        let options = once(format!("topic={source}"))
+            .chain(reviewers.iter().map(|r| format!("r={r}")))
+            // Gerrit has no labels like the other forges, hashtags are the closest
+            .chain(labels.iter().map(|l| format!("hashtag={l}")))
+            .collect::<Vec<String>>()
+            .join(",");
+        repository.push_to_ref(&remote, &source, &format!("refs/for/{target}%{options}"))?;
+
+        self.get_pr_by_branch(&source)
+    }
+
+    #[instrument(skip(self))]
+    fn update_pr(&self, _: u32, _: UpdatePullRequest) -> Result<PullRequest> {
+        Err(eyre!("Updating pull requests is not supported by Gerrit."))
+    }
+
+    #[instrument(skip(self))]
+    fn get_pr_by_id(&self, id: u32) -> Result<PullRequest> {
+        let change: GerritChange = self.call(
+            "GET",
+            &self.get_change_url(
+                id,
+                "?o=CURRENT_REVISION&o=CURRENT_COMMIT&o=DETAILED_ACCOUNTS&o=DETAILED_LABELS",
+            ),
+            None as Option<i32>,
+        )?;
+
+        Ok(change.into_pull_request(&self.hostname))
+    }
+
+    #[instrument(skip(self))]
+    fn get_pr_by_branch(&self, branch: &str) -> Result<PullRequest> {
+        // Changes are identified by the Change-Id in the last commit of the branch
+        let repository = LocalRepository::init(None)?;
+        let change_id = repository.get_change_id(Some(branch.to_string()))?;
+
+        let changes = self.query_changes(&format!(
+            "change:{change_id}+project:{}",
+            encode(&self.repo)
+        ))?;
+
+        changes
+            .into_iter()
+            .next()
+            .map(|change| change.into_pull_request(&self.hostname))
+            .wrap_err(eyre!("Change on branch {branch} not found."))
+    }
+
+    #[instrument(skip(self))]
+    fn list_prs(&self, filters: ListPullRequestFilters) -> Result<Vec<PullRequest>> {
+        let owner_param = match filters.author {
+            PullRequestUserFilter::Me => "+owner:self",
+            PullRequestUserFilter::All => "",
+        };
+        let status_param = match filters.state {
+            PullRequestStateFilter::Open => "+status:open",
+            PullRequestStateFilter::Closed => "+status:abandoned",
+            PullRequestStateFilter::Merged => "+status:merged",
+            PullRequestStateFilter::Locked | PullRequestStateFilter::All => "",
+        };
+        let changes = self.query_changes(&format!(
+            "project:{}{owner_param}{status_param}",
+            encode(&self.repo)
+        ))?;
+
+        Ok(changes
+            .into_iter()
+            .map(|change| change.into_pull_request(&self.hostname))
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    fn approve_pr(&self, id: u32) -> Result<()> {
+        let _: GerritReviewResult = self.call(
+            "POST",
+            &self.get_change_url(id, "/revisions/current/review"),
+            Some(GerritReview {
+                labels: HashMap::from([("Code-Review".to_string(), 2)]),
+            }),
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    fn close_pr(&self, id: u32) -> Result<PullRequest> {
+        let _: GerritChange = self.call(
+            "POST",
+            &self.get_change_url(id, "/abandon"),
+            None as Option<i32>,
+        )?;
+
+        self.get_pr_by_id(id)
+    }
+
+    #[instrument(skip(self))]
+    fn merge_pr(&self, id: u32, _: bool) -> Result<PullRequest> {
+        let _: GerritChange = self.call(
+            "POST",
+            &self.get_change_url(id, "/submit"),
+            None as Option<i32>,
+        )?;
+
+        self.get_pr_by_id(id)
+    }
+
+    #[instrument(skip(self))]
+    fn update_pr_branch(&self, id: u32, _: &str, rebase: bool) -> Result<BranchUpdate> {
+        if !rebase {
+            return Err(eyre!(
+                "Gerrit changes can only be rebased, pass the --rebase argument."
+            ));
+        }
+
+        let _: GerritChange = self.call(
+            "POST",
+            &self.get_change_url(id, "/rebase"),
+            None as Option<i32>,
+        )?;
+
+        Ok(BranchUpdate::Updated)
+    }
+
+    #[instrument(skip(self))]
+    fn create_issue(&self, _: CreateIssue) -> Result<Issue> {
+        Err(eyre!("Issues are not supported by Gerrit."))
+    }
+
+    #[instrument(skip(self))]
+    fn get_issue(&self, _: u32) -> Result<Issue> {
+        Err(eyre!("Issues are not supported by Gerrit."))
+    }
+
+    #[instrument(skip(self))]
+    fn list_issues(&self, _: IssueStateFilter) -> Result<Vec<Issue>> {
+        Err(eyre!("Issues are not supported by Gerrit."))
+    }
+
+    #[instrument(skip(self))]
+    fn close_issue(&self, _: u32) -> Result<Issue> {
+        Err(eyre!("Issues are not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn get_repository(&self) -> Result<Repository> {
+        let project: GerritProject =
+            self.call("GET", &self.get_project_url(""), None as Option<i32>)?;
+        let default_branch = self.get_default_branch()?;
+
+        Ok(project.into_repository(&self.hostname, default_branch))
+    }
+
+    #[instrument(skip(self))]
+    fn list_repositories(
+        &self,
+        owner: Option<&str>,
+        filters: ListRepositoryFilters,
+    ) -> Result<Vec<Repository>> {
+        // Projects don't have owners, but they are usually grouped by a path prefix
+        let params = owner
+            .map(|owner| format!("&p={}", encode(&format!("{owner}/"))))
+            .unwrap_or_default();
+        let repos = self.list_projects(&params, usize::MAX)?;
+
+        Ok(repos
+            .into_iter()
+            .filter(|repo| filters.matches(repo))
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    fn search_repositories(&self, query: &str, limit: usize) -> Result<Vec<Repository>> {
+        self.list_projects(&format!("&m={}", encode(query)), limit)
+    }
+
+    #[instrument(skip_all)]
+    fn create_repository(&self, repo: CreateRepository) -> Result<Repository> {
+        let CreateRepository {
+            name,
+            organization,
+            description,
+            init,
+            default_branch,
+            ..
+        } = repo;
+        let name = match organization {
+            Some(org) => format!("{org}/{name}"),
+            None => name,
+        };
+
+        let _: GerritProject = self.call(
+            "PUT",
+            &format!("/projects/{}", encode(&name)),
+            Some(GerritCreateProject {
+                description,
+                branches: default_branch.map(|b| vec![b]),
+                create_empty_commit: init,
+            }),
+        )?;
+
+        Gerrit::init(self.hostname.clone(), name, self.settings.clone()).get_repository()
+    }
+
+    #[instrument(skip_all)]
+    fn fork_repository(&self, _: ForkRepository) -> Result<Repository> {
+        Err(eyre!("Forking repositories is not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn update_repository(&self, _: UpdateRepository) -> Result<Repository> {
+        Err(eyre!("Updating repositories is not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn transfer_repository(&self, _: &str) -> Result<Repository> {
+        Err(eyre!(
+            "Transferring repositories is not supported by Gerrit."
+        ))
+    }
+
+    #[instrument(skip_all)]
+    fn delete_repository(&self) -> Result<()> {
+        // Deleting projects requires the delete-project plugin to be installed
+        self.call::<(), _>(
+            "POST",
+            &self.get_project_url("/delete-project~delete"),
+            None as Option<i32>,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn sync_fork(&self, _: &str) -> Result<ForkSync> {
+        Err(eyre!("Syncing forks is not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn download_archive(&self, _: &str, _: ArchiveFormat, _: &mut dyn Write) -> Result<u64> {
+        Err(eyre!("Downloading archives is not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_deploy_keys(&self) -> Result<Vec<DeployKey>> {
+        Err(eyre!("Deploy keys are not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn add_deploy_key(&self, _: CreateDeployKey) -> Result<DeployKey> {
+        Err(eyre!("Deploy keys are not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn remove_deploy_key(&self, _: &str) -> Result<()> {
+        Err(eyre!("Deploy keys are not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_branch_protections(&self) -> Result<Vec<BranchProtection>> {
+        Err(eyre!("Branch protections are not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn protect_branch(&self, _: BranchProtection) -> Result<BranchProtection> {
+        Err(eyre!("Branch protections are not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_collaborators(&self) -> Result<Vec<Collaborator>> {
+        Err(eyre!("Managing collaborators is not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn add_collaborator(&self, _: &str, _: CollaboratorPermission) -> Result<()> {
+        Err(eyre!("Managing collaborators is not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn remove_collaborator(&self, _: &str) -> Result<()> {
+        Err(eyre!("Managing collaborators is not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_webhooks(&self) -> Result<Vec<Webhook>> {
+        Err(eyre!("Managing webhooks is not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn create_webhook(&self, _: CreateWebhook) -> Result<Webhook> {
+        Err(eyre!("Managing webhooks is not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn delete_webhook(&self, _: &str) -> Result<()> {
+        Err(eyre!("Managing webhooks is not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn test_webhook(&self, _: &str) -> Result<()> {
+        Err(eyre!("Managing webhooks is not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_secrets(&self, _: &VariableScope) -> Result<Vec<Secret>> {
+        Err(eyre!("CI secrets are not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn set_secret(&self, _: &VariableScope, _: &str, _: &str) -> Result<()> {
+        Err(eyre!("CI secrets are not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn delete_secret(&self, _: &VariableScope, _: &str) -> Result<()> {
+        Err(eyre!("CI secrets are not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_variables(&self, _: &VariableScope) -> Result<Vec<Variable>> {
+        Err(eyre!("CI variables are not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn set_variable(&self, _: &VariableScope, _: Variable) -> Result<()> {
+        Err(eyre!("CI variables are not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn delete_variable(&self, _: &VariableScope, _: &str) -> Result<()> {
+        Err(eyre!("CI variables are not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn create_release(&self, _: CreateRelease) -> Result<Release> {
+        Err(eyre!("Releases are not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn get_release(&self, _: &str) -> Result<Release> {
+        Err(eyre!("Releases are not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_releases(&self) -> Result<Vec<Release>> {
+        Err(eyre!("Releases are not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn delete_release(&self, _: &str) -> Result<()> {
+        Err(eyre!("Releases are not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn upload_release_asset(&self, _: &str, _: &str, _: &[u8]) -> Result<ReleaseAsset> {
+        Err(eyre!("Releases are not supported by Gerrit."))
+    }
+
+    #[instrument(skip_all)]
+    fn download_release_asset(&self, _: &ReleaseAsset, _: &mut dyn Write) -> Result<u64> {
+        Err(eyre!("Releases are not supported by Gerrit."))
+    }
+}
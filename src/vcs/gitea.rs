@@ -1,16 +1,25 @@
 // Documentation: https://codeberg.org/api/swagger
 use super::common::{
-    CreatePullRequest, CreateRepository, ForkRepository, ForkedFromRepository,
-    ListPullRequestFilters, PullRequest, PullRequestState, PullRequestStateFilter, Repository,
-    RepositoryVisibility, User, VersionControl, VersionControlSettings,
+    found, is_status, multipart_file, save_response, ArchiveFormat, BranchProtection, BranchUpdate,
+    Collaborator, CollaboratorPermission, CreateDeployKey, CreateIssue, CreatePullRequest,
+    CreateRelease, CreateRepository, CreateWebhook, DeployKey, ForkRepository, ForkSync,
+    ForkedFromRepository, Issue, IssueState, IssueStateFilter, ListPullRequestFilters,
+    ListRepositoryFilters, PullRequest, PullRequestState, PullRequestStateFilter, Release,
+    ReleaseAsset, Repository, RepositoryVisibility, RequestError, Secret, UpdatePullRequest,
+    UpdateRepository, User, Variable, VariableScope, VersionControl, VersionControlSettings,
+    Webhook, WebhookContentType, WebhookEvent,
 };
 use eyre::{eyre, ContextCompat, Result};
 use native_tls::TlsConnector;
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::{fmt::Debug, sync::Arc};
+use serde::{
+    de::{DeserializeOwned, IgnoredAny},
+    Deserialize, Serialize,
+};
+use std::{fmt::Debug, io::Write, sync::Arc};
 use time::OffsetDateTime;
 use tracing::{info, instrument, trace};
 use ureq::{Agent, AgentBuilder, Error};
+use urlencoding::encode;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct GiteaUser {
@@ -131,6 +140,53 @@ struct GiteaCreateRepository {
     gitignores: Option<String>,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaSearchRepositories {
+    data: Vec<GiteaRepository>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaMergeUpstream {
+    branch: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaMergeUpstreamResult {
+    merge_type: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaUpdateRepository {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    website: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    private: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    archived: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allow_merge_commits: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allow_squash_merge: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allow_rebase: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaTransferRepository {
+    new_owner: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaRepositoryTopics {
+    topics: Vec<String>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 struct GiteaForkRepository {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -266,6 +322,16 @@ pub struct GiteaCreatePullRequestReviewers {
     reviewers: Vec<String>,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GiteaAddLabels {
+    labels: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GiteaLabel {
+    pub name: String,
+}
+
 #[derive(Debug, Default, Deserialize, Serialize)]
 pub struct GiteaUpdatePullRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -298,6 +364,324 @@ pub struct GiteaMergePullRequest {
     pub do_this: GiteaMergePullRequestDo,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaBranchProtection {
+    #[serde(default)]
+    branch_name: String,
+    #[serde(default)]
+    rule_name: String,
+    required_approvals: u32,
+    enable_status_check: bool,
+    status_check_contexts: Option<Vec<String>>,
+    #[serde(default)]
+    enable_force_push: bool,
+}
+
+impl From<GiteaBranchProtection> for BranchProtection {
+    fn from(protection: GiteaBranchProtection) -> BranchProtection {
+        let GiteaBranchProtection {
+            branch_name,
+            rule_name,
+            required_approvals,
+            enable_status_check,
+            status_check_contexts,
+            enable_force_push,
+        } = protection;
+        BranchProtection {
+            // Older Gitea versions only have the branch name
+            branch: if rule_name.is_empty() {
+                branch_name
+            } else {
+                rule_name
+            },
+            required_approvals,
+            required_status_checks: status_check_contexts
+                .filter(|_| enable_status_check)
+                .unwrap_or_default(),
+            allow_force_push: enable_force_push,
+            linear_history: false,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaUpdateBranchProtection {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    branch_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_name: Option<String>,
+    required_approvals: u32,
+    enable_status_check: bool,
+    status_check_contexts: Vec<String>,
+    enable_force_push: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaCollaboratorPermission {
+    permission: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaAddCollaborator {
+    permission: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaWebhookConfig {
+    url: String,
+    #[serde(default)]
+    content_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    secret: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaWebhook {
+    id: u32,
+    active: bool,
+    events: Vec<String>,
+    config: GiteaWebhookConfig,
+}
+
+impl From<GiteaWebhook> for Webhook {
+    fn from(webhook: GiteaWebhook) -> Webhook {
+        let GiteaWebhook {
+            id,
+            active,
+            events,
+            config,
+        } = webhook;
+        Webhook {
+            id: id.to_string(),
+            url: config.url,
+            events: events
+                .iter()
+                .filter_map(|event| match event.as_str() {
+                    "push" => Some(WebhookEvent::Push),
+                    "pull_request" => Some(WebhookEvent::PullRequest),
+                    "create" => Some(WebhookEvent::Tag),
+                    "release" => Some(WebhookEvent::Release),
+                    "issues" => Some(WebhookEvent::Issue),
+                    _ => None,
+                })
+                .collect(),
+            content_type: match config.content_type.as_str() {
+                "form" => WebhookContentType::Form,
+                _ => WebhookContentType::Json,
+            },
+            active,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaCreateWebhook {
+    #[serde(rename = "type")]
+    hook_type: String,
+    active: bool,
+    events: Vec<String>,
+    config: GiteaWebhookConfig,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaDeployKey {
+    id: u32,
+    title: String,
+    key: String,
+    read_only: bool,
+}
+
+impl From<GiteaDeployKey> for DeployKey {
+    fn from(key: GiteaDeployKey) -> DeployKey {
+        let GiteaDeployKey {
+            id,
+            title,
+            key,
+            read_only,
+        } = key;
+        DeployKey {
+            id: id.to_string(),
+            title,
+            key,
+            read_only,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaCreateDeployKey {
+    title: String,
+    key: String,
+    read_only: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaSecret {
+    name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaUpdateSecret {
+    data: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaVariable {
+    name: String,
+    data: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaUpdateVariable {
+    value: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaReleaseAsset {
+    id: u32,
+    name: String,
+    size: u64,
+    browser_download_url: String,
+}
+
+impl From<GiteaReleaseAsset> for ReleaseAsset {
+    fn from(asset: GiteaReleaseAsset) -> ReleaseAsset {
+        ReleaseAsset {
+            id: asset.id.to_string(),
+            name: asset.name,
+            url: asset.browser_download_url,
+            size: Some(asset.size),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaRelease {
+    id: u32,
+    tag_name: String,
+    name: Option<String>,
+    body: Option<String>,
+    draft: bool,
+    prerelease: bool,
+    #[serde(with = "time::serde::iso8601")]
+    created_at: OffsetDateTime,
+    html_url: String,
+    assets: Vec<GiteaReleaseAsset>,
+}
+
+impl From<GiteaRelease> for Release {
+    fn from(release: GiteaRelease) -> Release {
+        let GiteaRelease {
+            id,
+            tag_name,
+            name,
+            body,
+            draft,
+            prerelease,
+            created_at,
+            html_url,
+            assets,
+        } = release;
+        Release {
+            id: id.to_string(),
+            name: name.filter(|n| !n.is_empty()).unwrap_or(tag_name.clone()),
+            tag: tag_name,
+            description: body.unwrap_or_default(),
+            draft,
+            prerelease,
+            created_at,
+            url: html_url,
+            assets: assets.into_iter().map(|a| a.into()).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GiteaCreateRelease {
+    tag_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_commitish: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    body: String,
+    draft: bool,
+    prerelease: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub enum GiteaIssueState {
+    #[serde(rename = "open")]
+    Open,
+    #[serde(rename = "closed")]
+    Closed,
+}
+
+impl From<GiteaIssueState> for IssueState {
+    fn from(state: GiteaIssueState) -> IssueState {
+        match state {
+            GiteaIssueState::Open => IssueState::Open,
+            GiteaIssueState::Closed => IssueState::Closed,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GiteaIssue {
+    pub number: u32,
+    pub state: GiteaIssueState,
+    pub title: String,
+    pub body: String,
+    pub labels: Vec<GiteaLabel>,
+    pub html_url: String,
+    #[serde(with = "time::serde::iso8601")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::iso8601")]
+    pub updated_at: OffsetDateTime,
+    pub user: GiteaUser,
+    // Issues of Gitea include pull requests, they are told apart by this field
+    #[serde(skip_serializing)]
+    pub pull_request: Option<IgnoredAny>,
+}
+
+impl From<GiteaIssue> for Issue {
+    fn from(issue: GiteaIssue) -> Issue {
+        let GiteaIssue {
+            number,
+            state,
+            title,
+            body,
+            labels,
+            html_url,
+            created_at,
+            updated_at,
+            user,
+            ..
+        } = issue;
+        Issue {
+            id: number,
+            state: state.into(),
+            title,
+            description: body,
+            labels: labels.into_iter().map(|l| l.name).collect(),
+            url: html_url,
+            created_at,
+            updated_at,
+            author: Some(user.into()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GiteaCreateIssue {
+    title: String,
+    body: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GiteaUpdateIssue {
+    state: GiteaIssueState,
+}
+
 #[derive(Debug)]
 pub struct Gitea {
     hostname: String,
@@ -311,12 +695,39 @@ impl Gitea {
     fn get_repository_url(&self, url: &str) -> String {
         format!("/repos/{}{}", self.repo, url)
     }
+
     #[instrument(skip_all)]
+    fn get_pull_request_url(&self, url: &str) -> String {
+        // In fork workflows the pull requests live on the upstream repository
+        let repo = self.settings.upstream.as_ref().unwrap_or(&self.repo);
+        format!("/repos/{repo}{url}")
+    }
     fn call<T: DeserializeOwned, U: Serialize + Debug>(
         &self,
         method: &str,
         url: &str,
         body: Option<U>,
+    ) -> Result<T> {
+        self.send(method, url, body, false)
+    }
+
+    // Secrets, variables and webhooks with secrets are kept out of the traces
+    fn call_secret<T: DeserializeOwned, U: Serialize + Debug>(
+        &self,
+        method: &str,
+        url: &str,
+        body: Option<U>,
+    ) -> Result<T> {
+        self.send(method, url, body, true)
+    }
+
+    #[instrument(skip_all)]
+    fn send<T: DeserializeOwned, U: Serialize + Debug>(
+        &self,
+        method: &str,
+        url: &str,
+        body: Option<U>,
+        secret: bool,
     ) -> Result<T> {
         let url = format!("https://{}/api/v1{}", self.hostname, url);
 
@@ -332,7 +743,11 @@ impl Gitea {
             .set("Authorization", &format!("token {token}"))
             .set("Content-Type", "application/json");
         let result = if let Some(body) = &body {
-            trace!("Sending body: {}.", serde_json::to_string(&body)?);
+            if secret {
+                trace!("Sending body: <redacted>.");
+            } else {
+                trace!("Sending body: {}.", serde_json::to_string(&body)?);
+            }
             request.send_json(body)
         } else {
             request.call()
@@ -348,7 +763,9 @@ impl Gitea {
                     status,
                     t.len()
                 );
-                trace!("Response body: {t}.");
+                if !secret {
+                    trace!("Response body: {t}.");
+                }
 
                 // Somewhat hacky, if the response is empty, return null
                 if t.is_empty() {
@@ -366,7 +783,7 @@ impl Gitea {
                     status,
                     t.len()
                 );
-                Err(eyre!("Request failed (response: {}).", t))
+                Err(RequestError { status, body: t }.into())
             }
             Err(Error::Transport(_)) => Err(eyre!("Sending data failed.")),
         }
@@ -378,17 +795,36 @@ impl Gitea {
     }
 
     #[instrument(skip(self))]
-    fn call_paginated<T: DeserializeOwned>(&self, url: &str, params: &str) -> Result<Vec<T>> {
+    fn call_paginated<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        params: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<T>> {
+        self.call_paginated_with(url, params, limit, |page: Vec<T>| page)
+    }
+
+    // Some endpoints wrap the page in an object, read the values from it with items
+    #[instrument(skip(self, items))]
+    fn call_paginated_with<P: DeserializeOwned, T>(
+        &self,
+        url: &str,
+        params: &str,
+        limit: Option<usize>,
+        items: impl Fn(P) -> Vec<T>,
+    ) -> Result<Vec<T>> {
+        let limit = limit.unwrap_or(usize::MAX);
         let mut collected_values: Vec<T> = vec![];
         let mut i = 1;
-        loop {
+        while collected_values.len() < limit {
             info!("Reading page {}.", i);
 
-            let mut page: Vec<T> = self.call(
+            let page: P = self.call(
                 "GET",
                 &format!("{url}?page={i}{params}"),
                 None as Option<i32>,
             )?;
+            let mut page = items(page);
 
             if page.is_empty() {
                 break;
@@ -398,80 +834,241 @@ impl Gitea {
 
             i += 1;
         }
+        collected_values.truncate(limit);
         Ok(collected_values)
     }
-}
-
-impl VersionControl for Gitea {
-    #[instrument(skip_all)]
-    fn init(hostname: String, repo: String, settings: VersionControlSettings) -> Self {
-        let client = AgentBuilder::new()
-            .tls_connector(Arc::new(TlsConnector::new().unwrap()))
-            .build();
-        Gitea {
-            hostname,
-            settings,
-            client,
-            repo,
-        }
-    }
 
-    #[instrument(skip_all)]
-    fn login_url(&self) -> String {
-        format!("https://{}/user/settings/applications", self.hostname)
-    }
+    #[instrument(skip(self, content))]
+    fn upload<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        content_type: &str,
+        content: &[u8],
+    ) -> Result<T> {
+        let url = format!("https://{}/api/v1{}", self.hostname, url);
 
-    #[instrument(skip_all)]
-    fn validate_token(&self, token: &str) -> Result<()> {
-        if token.len() != 40 {
-            Err(eyre!("Your Gitea token has to be 40 characters long."))
-        } else {
-            Ok(())
-        }
-    }
+        info!("Uploading {} bytes to {url}.", content.len());
 
-    #[instrument(skip(self))]
-    fn create_pr(&self, mut pr: CreatePullRequest) -> Result<PullRequest> {
-        let reviewers = pr.reviewers.clone();
-        pr.target = pr.target.or(self.settings.default_branch.clone());
-        if pr.target.is_none() {
-            let GiteaRepository { default_branch, .. } = self.get_repository_data()?;
-            pr.target = Some(default_branch);
-        }
+        let token = &self.settings.auth;
 
-        let mut url = self.get_repository_url("/pulls");
-        let mut gitea_pr = GiteaCreatePullRequest::from(pr);
-        if self.settings.fork {
-            let repo = self.get_repository()?;
-            if let Some(forked) = repo.forked_from {
-                url = format!("/repos/{}/pulls", forked.full_name);
-
-                // For some reason Gitea only works with specifying head to username:branch
-                let namespace = repo
-                    .full_name
-                    .split('/')
-                    .next()
-                    .wrap_err(eyre!("Invalid repository name {}.", repo.full_name))?;
-                gitea_pr.head = format!("{}:{}", namespace, gitea_pr.head);
-            }
-        }
+        trace!("Authenticating with token '{token}'.");
 
-        let new_pr: GiteaPullRequest = self.call("POST", &url, Some(gitea_pr))?;
+        let result = self
+            .client
+            .request("POST", &url)
+            .set("Authorization", &format!("token {token}"))
+            .set("Content-Type", content_type)
+            .send_bytes(content);
 
-        let _: Vec<GiteaPullRequestReview> = self.call(
+        match result {
+            Ok(result) => {
+                let status = result.status();
+                let mut t = result.into_string()?;
+
+                info!(
+                    "Received response with response code {} with body size {}.",
+                    status,
+                    t.len()
+                );
+                trace!("Response body: {t}.");
+
+                // Somewhat hacky, if the response is empty, return null
+                if t.is_empty() {
+                    t = "null".to_string();
+                }
+
+                let t: T = serde_json::from_str(&t)?;
+                Ok(t)
+            }
+            Err(Error::Status(status, result)) => {
+                let t = result.into_string()?;
+
+                info!(
+                    "Received response with response code {} with body size {}.",
+                    status,
+                    t.len()
+                );
+                Err(RequestError { status, body: t }.into())
+            }
+            Err(Error::Transport(_)) => Err(eyre!("Sending data failed.")),
+        }
+    }
+
+    #[instrument(skip(self, writer))]
+    fn download(
+        &self,
+        url: &str,
+        writer: &mut dyn Write,
+        expected_size: Option<u64>,
+    ) -> Result<u64> {
+        info!("Downloading {url}.");
+
+        let token = &self.settings.auth;
+
+        trace!("Authenticating with token '{token}'.");
+
+        let result = self
+            .client
+            .get(url)
+            .set("Authorization", &format!("token {token}"))
+            .call();
+
+        match result {
+            Ok(response) => save_response(response, writer, expected_size),
+            Err(Error::Status(status, result)) => {
+                let t = result.into_string()?;
+
+                info!(
+                    "Received response with response code {} with body size {}.",
+                    status,
+                    t.len()
+                );
+                Err(RequestError { status, body: t }.into())
+            }
+            Err(Error::Transport(_)) => Err(eyre!("Sending data failed.")),
+        }
+    }
+
+    #[instrument(skip_all)]
+    fn get_release_data(&self, tag: &str) -> Result<GiteaRelease> {
+        self.call::<GiteaRelease, i32>(
+            "GET",
+            &self.get_repository_url(&format!("/releases/tags/{}", encode(tag))),
+            None,
+        )
+        // Draft releases cannot be found by their tag, only in the list
+        .or_else(|err| {
+            let releases: Vec<GiteaRelease> =
+                self.call_paginated(&self.get_repository_url("/releases"), "", None)?;
+            releases
+                .into_iter()
+                .find(|release| release.draft && release.tag_name == tag)
+                .ok_or(err)
+        })
+    }
+
+    #[instrument(skip_all)]
+    fn get_scope_url(&self, scope: &VariableScope, url: &str) -> String {
+        match scope {
+            VariableScope::Repository => self.get_repository_url(url),
+            VariableScope::Organization(org) => format!("/orgs/{org}{url}"),
+        }
+    }
+}
+
+impl VersionControl for Gitea {
+    #[instrument(skip_all)]
+    fn init(hostname: String, repo: String, settings: VersionControlSettings) -> Self {
+        let client = AgentBuilder::new()
+            .tls_connector(Arc::new(TlsConnector::new().unwrap()))
+            .build();
+        Gitea {
+            hostname,
+            settings,
+            client,
+            repo,
+        }
+    }
+
+    #[instrument(skip_all)]
+    fn login_url(&self) -> String {
+        format!("https://{}/user/settings/applications", self.hostname)
+    }
+
+    #[instrument(skip_all)]
+    fn validate_token(&self, token: &str) -> Result<()> {
+        if token.len() != 40 {
+            Err(eyre!("Your Gitea token has to be 40 characters long."))
+        } else {
+            Ok(())
+        }
+    }
+
+    #[instrument(skip_all)]
+    fn get_current_user(&self) -> Result<User> {
+        let user: GiteaUser = self.call("GET", "/user", None as Option<i32>)?;
+
+        Ok(user.into())
+    }
+
+    #[instrument(skip(self))]
+    fn requires_pushed_branch(&self) -> Result<bool> {
+        Ok(true)
+    }
+
+    #[instrument(skip(self))]
+    fn tracks_pull_requests(&self) -> Result<bool> {
+        Ok(true)
+    }
+
+    #[instrument(skip(self))]
+    fn create_pr(&self, mut pr: CreatePullRequest) -> Result<PullRequest> {
+        let reviewers = pr.reviewers.clone();
+        let labels = pr.labels.clone();
+        pr.target = pr.target.or(self.settings.default_branch.clone());
+        if pr.target.is_none() {
+            let GiteaRepository { default_branch, .. } = self.get_repository_data()?;
+            pr.target = Some(default_branch);
+        }
+
+        let url = self.get_pull_request_url("/pulls");
+        let mut gitea_pr = GiteaCreatePullRequest::from(pr);
+        if self.settings.upstream.is_some() {
+            // For some reason Gitea only works with specifying head to username:branch
+            let namespace = self
+                .repo
+                .split('/')
+                .next()
+                .wrap_err(eyre!("Invalid repository name {}.", self.repo))?;
+            gitea_pr.head = format!("{}:{}", namespace, gitea_pr.head);
+        }
+
+        let new_pr: GiteaPullRequest = self.call("POST", &url, Some(gitea_pr))?;
+
+        let _: Vec<GiteaPullRequestReview> = self.call(
             "POST",
             &format!("{}/{}/requested_reviewers", url, new_pr.number),
             Some(GiteaCreatePullRequestReviewers { reviewers }),
         )?;
 
+        // Pull requests share the labels of the issues
+        if !labels.is_empty() {
+            let _: Vec<GiteaLabel> = self.call(
+                "POST",
+                &self.get_pull_request_url(&format!("/issues/{}/labels", new_pr.number)),
+                Some(GiteaAddLabels { labels }),
+            )?;
+        }
+
         Ok(new_pr.into())
     }
 
+    #[instrument(skip(self))]
+    fn update_pr(&self, id: u32, pr: UpdatePullRequest) -> Result<PullRequest> {
+        let UpdatePullRequest {
+            title,
+            description,
+            target,
+        } = pr;
+        let pr: GiteaPullRequest = self.call(
+            "PATCH",
+            &self.get_pull_request_url(&format!("/pulls/{id}")),
+            Some(GiteaUpdatePullRequest {
+                title,
+                body: description,
+                base: target,
+                ..GiteaUpdatePullRequest::default()
+            }),
+        )?;
+
+        Ok(pr.into())
+    }
+
     #[instrument(skip(self))]
     fn get_pr_by_id(&self, id: u32) -> Result<PullRequest> {
         let pr: GiteaPullRequest = self.call(
             "GET",
-            &self.get_repository_url(&format!("/pulls/{id}")),
+            &self.get_pull_request_url(&format!("/pulls/{id}")),
             None as Option<i32>,
         )?;
 
@@ -481,7 +1078,7 @@ impl VersionControl for Gitea {
     #[instrument(skip(self))]
     fn get_pr_by_branch(&self, branch: &str) -> Result<PullRequest> {
         let prs: Vec<GiteaPullRequest> =
-            self.call_paginated(&self.get_repository_url("/pulls"), "&state=all")?;
+            self.call_paginated(&self.get_pull_request_url("/pulls"), "&state=all", None)?;
 
         prs.into_iter()
             .find(|pr| pr.head.branch == branch)
@@ -500,7 +1097,7 @@ impl VersionControl for Gitea {
         };
         let prs: Vec<GiteaPullRequest> = self.call(
             "GET",
-            &self.get_repository_url(&format!("/pulls?state={state}")),
+            &self.get_pull_request_url(&format!("/pulls?state={state}")),
             None as Option<i32>,
         )?;
 
@@ -509,9 +1106,9 @@ impl VersionControl for Gitea {
 
     #[instrument(skip(self))]
     fn approve_pr(&self, id: u32) -> Result<()> {
-        self.call(
+        self.call::<(), _>(
             "POST",
-            &self.get_repository_url(&format!("/pulls/{id}/reviews")),
+            &self.get_pull_request_url(&format!("/pulls/{id}/reviews")),
             Some(GiteaCreatePullRequestReview {
                 event: GiteaCreatePullRequestReviewEvent::Approve,
                 body: None,
@@ -529,7 +1126,7 @@ impl VersionControl for Gitea {
         };
         let pr: GiteaPullRequest = self.call(
             "PATCH",
-            &self.get_repository_url(&format!("/pulls/{id}")),
+            &self.get_pull_request_url(&format!("/pulls/{id}")),
             Some(closing),
         )?;
 
@@ -538,9 +1135,9 @@ impl VersionControl for Gitea {
 
     #[instrument(skip(self))]
     fn merge_pr(&self, id: u32, _: bool) -> Result<PullRequest> {
-        self.call(
+        self.call::<(), _>(
             "POST",
-            &self.get_repository_url(&format!("/pulls/{id}/merge")),
+            &self.get_pull_request_url(&format!("/pulls/{id}/merge")),
             Some(GiteaMergePullRequest {
                 do_this: GiteaMergePullRequestDo::Merge,
             }),
@@ -549,6 +1146,79 @@ impl VersionControl for Gitea {
         self.get_pr_by_id(id)
     }
 
+    #[instrument(skip(self))]
+    fn update_pr_branch(&self, id: u32, _: &str, rebase: bool) -> Result<BranchUpdate> {
+        let style = if rebase { "rebase" } else { "merge" };
+        self.call::<(), _>(
+            "POST",
+            &self.get_pull_request_url(&format!("/pulls/{id}/update?style={style}")),
+            None as Option<i32>,
+        )?;
+
+        Ok(BranchUpdate::Updated)
+    }
+
+    #[instrument(skip(self))]
+    fn create_issue(&self, issue: CreateIssue) -> Result<Issue> {
+        let CreateIssue { title, description } = issue;
+        let issue: GiteaIssue = self.call(
+            "POST",
+            &self.get_repository_url("/issues"),
+            Some(GiteaCreateIssue {
+                title,
+                body: description,
+            }),
+        )?;
+
+        Ok(issue.into())
+    }
+
+    #[instrument(skip(self))]
+    fn get_issue(&self, id: u32) -> Result<Issue> {
+        let issue: GiteaIssue = self.call(
+            "GET",
+            &self.get_repository_url(&format!("/issues/{id}")),
+            None as Option<i32>,
+        )?;
+        if issue.pull_request.is_some() {
+            return Err(eyre!("#{id} is a pull request, not an issue."));
+        }
+
+        Ok(issue.into())
+    }
+
+    #[instrument(skip(self))]
+    fn list_issues(&self, state: IssueStateFilter) -> Result<Vec<Issue>> {
+        let state = match state {
+            IssueStateFilter::Open => "open",
+            IssueStateFilter::Closed => "closed",
+            IssueStateFilter::All => "all",
+        };
+        let issues: Vec<GiteaIssue> = self.call_paginated(
+            &self.get_repository_url("/issues"),
+            &format!("&type=issues&state={state}"),
+            None,
+        )?;
+
+        Ok(issues.into_iter().map(|issue| issue.into()).collect())
+    }
+
+    #[instrument(skip(self))]
+    fn close_issue(&self, id: u32) -> Result<Issue> {
+        // Pull requests share the numbers of issues, don't close one by mistake
+        self.get_issue(id)?;
+
+        let issue: GiteaIssue = self.call(
+            "PATCH",
+            &self.get_repository_url(&format!("/issues/{id}")),
+            Some(GiteaUpdateIssue {
+                state: GiteaIssueState::Closed,
+            }),
+        )?;
+
+        Ok(issue.into())
+    }
+
     #[instrument(skip_all)]
     fn get_repository(&self) -> Result<Repository> {
         let repo = self.get_repository_data()?;
@@ -556,6 +1226,45 @@ impl VersionControl for Gitea {
         Ok(repo.into())
     }
 
+    #[instrument(skip(self))]
+    fn list_repositories(
+        &self,
+        owner: Option<&str>,
+        filters: ListRepositoryFilters,
+    ) -> Result<Vec<Repository>> {
+        let repos: Vec<GiteaRepository> = if let Some(owner) = owner {
+            self.call_paginated(&format!("/orgs/{owner}/repos"), "&limit=50", None)
+                .or_else(|err| {
+                    // Fall back to the user, if there is no organization with this name
+                    if is_status(&err, 404) {
+                        self.call_paginated(&format!("/users/{owner}/repos"), "&limit=50", None)
+                    } else {
+                        Err(err)
+                    }
+                })?
+        } else {
+            self.call_paginated("/user/repos", "&limit=50", None)?
+        };
+
+        Ok(repos
+            .into_iter()
+            .map(Repository::from)
+            .filter(|repo| filters.matches(repo))
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    fn search_repositories(&self, query: &str, limit: usize) -> Result<Vec<Repository>> {
+        let repos = self.call_paginated_with(
+            "/repos/search",
+            &format!("&q={}&limit=50", encode(query)),
+            Some(limit),
+            |page: GiteaSearchRepositories| page.data,
+        )?;
+
+        Ok(repos.into_iter().map(|repo| repo.into()).collect())
+    }
+
     #[instrument(skip_all)]
     fn create_repository(&self, repo: CreateRepository) -> Result<Repository> {
         let CreateRepository {
@@ -599,10 +1308,496 @@ impl VersionControl for Gitea {
         Ok(new_repo.into())
     }
 
+    #[instrument(skip_all)]
+    fn update_repository(&self, repo: UpdateRepository) -> Result<Repository> {
+        let UpdateRepository {
+            name,
+            description,
+            visibility,
+            default_branch,
+            homepage,
+            topics,
+            archived,
+            allow_merge_commit,
+            allow_squash_merge,
+            allow_rebase_merge,
+        } = repo;
+
+        // Topics have to be replaced separately, before the repository may be renamed
+        if let Some(topics) = topics {
+            self.call::<(), _>(
+                "PUT",
+                &self.get_repository_url("/topics"),
+                Some(GiteaRepositoryTopics { topics }),
+            )?;
+        }
+
+        let repo: GiteaRepository = self.call(
+            "PATCH",
+            &self.get_repository_url(""),
+            Some(GiteaUpdateRepository {
+                name,
+                description,
+                website: homepage,
+                private: visibility.map(|visibility| visibility != RepositoryVisibility::Public),
+                default_branch,
+                archived,
+                allow_merge_commits: allow_merge_commit,
+                allow_squash_merge,
+                allow_rebase: allow_rebase_merge,
+            }),
+        )?;
+
+        Ok(repo.into())
+    }
+
+    #[instrument(skip_all)]
+    fn transfer_repository(&self, owner: &str) -> Result<Repository> {
+        let repo: GiteaRepository = self.call(
+            "POST",
+            &self.get_repository_url("/transfer"),
+            Some(GiteaTransferRepository {
+                new_owner: owner.to_string(),
+            }),
+        )?;
+
+        Ok(repo.into())
+    }
+
     #[instrument(skip_all)]
     fn delete_repository(&self) -> Result<()> {
-        self.call("DELETE", &self.get_repository_url(""), None as Option<i32>)?;
+        self.call::<(), _>("DELETE", &self.get_repository_url(""), None as Option<i32>)?;
 
         Ok(())
     }
+
+    #[instrument(skip(self))]
+    fn sync_fork(&self, branch: &str) -> Result<ForkSync> {
+        let result: GiteaMergeUpstreamResult = self
+            .call(
+                "POST",
+                &self.get_repository_url("/merge-upstream"),
+                Some(GiteaMergeUpstream {
+                    branch: branch.to_string(),
+                }),
+            )
+            .map_err(|err| {
+                // Only a conflict means the branches diverged, other errors are reported as is
+                if is_status(&err, 409) {
+                    err.wrap_err(eyre!(
+                        "Cannot sync {branch} with upstream, the branches have conflicting changes (use --force to reset it)."
+                    ))
+                } else {
+                    err
+                }
+            })?;
+
+        match result.merge_type.as_str() {
+            "fast-forward" => Ok(ForkSync::FastForward),
+            "merge" => Ok(ForkSync::Merge),
+            _ => Ok(ForkSync::UpToDate),
+        }
+    }
+
+    #[instrument(skip(self, writer))]
+    fn download_archive(
+        &self,
+        reference: &str,
+        format: ArchiveFormat,
+        writer: &mut dyn Write,
+    ) -> Result<u64> {
+        self.download(
+            &format!(
+                "https://{}/api/v1{}",
+                self.hostname,
+                self.get_repository_url(&format!(
+                    "/archive/{}.{}",
+                    encode(reference),
+                    format.extension()
+                ))
+            ),
+            writer,
+            None,
+        )
+    }
+
+    #[instrument(skip_all)]
+    fn list_deploy_keys(&self) -> Result<Vec<DeployKey>> {
+        let keys: Vec<GiteaDeployKey> =
+            self.call_paginated(&self.get_repository_url("/keys"), "", None)?;
+
+        Ok(keys.into_iter().map(|k| k.into()).collect())
+    }
+
+    #[instrument(skip_all)]
+    fn add_deploy_key(&self, key: CreateDeployKey) -> Result<DeployKey> {
+        let CreateDeployKey {
+            title,
+            key,
+            read_only,
+        } = key;
+        let key: GiteaDeployKey = self.call(
+            "POST",
+            &self.get_repository_url("/keys"),
+            Some(GiteaCreateDeployKey {
+                title,
+                key,
+                read_only,
+            }),
+        )?;
+
+        Ok(key.into())
+    }
+
+    #[instrument(skip_all)]
+    fn remove_deploy_key(&self, id: &str) -> Result<()> {
+        self.call::<(), i32>(
+            "DELETE",
+            &self.get_repository_url(&format!("/keys/{id}")),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn list_branch_protections(&self) -> Result<Vec<BranchProtection>> {
+        let protections: Vec<GiteaBranchProtection> = self.call(
+            "GET",
+            &self.get_repository_url("/branch_protections"),
+            None as Option<i32>,
+        )?;
+
+        Ok(protections.into_iter().map(|p| p.into()).collect())
+    }
+
+    #[instrument(skip_all)]
+    fn protect_branch(&self, protection: BranchProtection) -> Result<BranchProtection> {
+        let BranchProtection {
+            branch,
+            required_approvals,
+            required_status_checks,
+            allow_force_push,
+            linear_history,
+        } = protection;
+        if linear_history {
+            return Err(eyre!("Requiring linear history is not supported by Gitea."));
+        }
+
+        let url = self.get_repository_url(&format!("/branch_protections/{}", encode(&branch)));
+        let mut update = GiteaUpdateBranchProtection {
+            branch_name: None,
+            rule_name: None,
+            required_approvals,
+            enable_status_check: !required_status_checks.is_empty(),
+            status_check_contexts: required_status_checks,
+            enable_force_push: allow_force_push,
+        };
+
+        // Update the protection if it exists already, otherwise create it
+        let exists = found(self.call::<GiteaBranchProtection, i32>("GET", &url, None))?.is_some();
+        let protection: GiteaBranchProtection = if exists {
+            self.call("PATCH", &url, Some(update))?
+        } else {
+            update.branch_name = Some(branch.clone());
+            update.rule_name = Some(branch);
+            self.call(
+                "POST",
+                &self.get_repository_url("/branch_protections"),
+                Some(update),
+            )?
+        };
+
+        Ok(protection.into())
+    }
+
+    #[instrument(skip_all)]
+    fn list_collaborators(&self) -> Result<Vec<Collaborator>> {
+        let users: Vec<GiteaUser> =
+            self.call_paginated(&self.get_repository_url("/collaborators"), "", None)?;
+
+        // The list doesn't contain the permissions, query them for every user
+        users
+            .into_iter()
+            .map(|user| {
+                let permission: GiteaCollaboratorPermission = self.call(
+                    "GET",
+                    &self.get_repository_url(&format!("/collaborators/{}/permission", user.login)),
+                    None as Option<i32>,
+                )?;
+
+                Ok(Collaborator {
+                    user: user.into(),
+                    permission: match permission.permission.as_str() {
+                        "admin" | "owner" => CollaboratorPermission::Admin,
+                        "write" => CollaboratorPermission::Write,
+                        _ => CollaboratorPermission::Read,
+                    },
+                })
+            })
+            .collect()
+    }
+
+    #[instrument(skip_all)]
+    fn add_collaborator(&self, username: &str, permission: CollaboratorPermission) -> Result<()> {
+        self.call::<(), _>(
+            "PUT",
+            &self.get_repository_url(&format!("/collaborators/{username}")),
+            Some(GiteaAddCollaborator {
+                permission: match permission {
+                    CollaboratorPermission::Read => "read",
+                    CollaboratorPermission::Write => "write",
+                    CollaboratorPermission::Admin => "admin",
+                }
+                .to_string(),
+            }),
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn remove_collaborator(&self, username: &str) -> Result<()> {
+        self.call::<(), i32>(
+            "DELETE",
+            &self.get_repository_url(&format!("/collaborators/{username}")),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn list_webhooks(&self) -> Result<Vec<Webhook>> {
+        let webhooks: Vec<GiteaWebhook> =
+            self.call_paginated(&self.get_repository_url("/hooks"), "", None)?;
+
+        Ok(webhooks.into_iter().map(|w| w.into()).collect())
+    }
+
+    #[instrument(skip_all)]
+    fn create_webhook(&self, webhook: CreateWebhook) -> Result<Webhook> {
+        let CreateWebhook {
+            url,
+            secret,
+            content_type,
+            events,
+        } = webhook;
+        let webhook: GiteaWebhook = self.call_secret(
+            "POST",
+            &self.get_repository_url("/hooks"),
+            Some(GiteaCreateWebhook {
+                hook_type: "gitea".to_string(),
+                active: true,
+                // Tags are sent with the create event (that also includes branches)
+                events: events
+                    .into_iter()
+                    .map(|event| {
+                        match event {
+                            WebhookEvent::Push => "push",
+                            WebhookEvent::PullRequest => "pull_request",
+                            WebhookEvent::Tag => "create",
+                            WebhookEvent::Release => "release",
+                            WebhookEvent::Issue => "issues",
+                        }
+                        .to_string()
+                    })
+                    .collect(),
+                config: GiteaWebhookConfig {
+                    url,
+                    content_type: match content_type {
+                        WebhookContentType::Json => "json",
+                        WebhookContentType::Form => "form",
+                    }
+                    .to_string(),
+                    secret,
+                },
+            }),
+        )?;
+
+        Ok(webhook.into())
+    }
+
+    #[instrument(skip_all)]
+    fn delete_webhook(&self, id: &str) -> Result<()> {
+        self.call::<(), i32>(
+            "DELETE",
+            &self.get_repository_url(&format!("/hooks/{id}")),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn test_webhook(&self, id: &str) -> Result<()> {
+        self.call::<(), i32>(
+            "POST",
+            &self.get_repository_url(&format!("/hooks/{id}/tests")),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn list_secrets(&self, scope: &VariableScope) -> Result<Vec<Secret>> {
+        let secrets: Vec<GiteaSecret> =
+            self.call_paginated(&self.get_scope_url(scope, "/actions/secrets"), "", None)?;
+
+        Ok(secrets
+            .into_iter()
+            .map(|s| Secret { name: s.name })
+            .collect())
+    }
+
+    #[instrument(skip_all)]
+    fn set_secret(&self, scope: &VariableScope, name: &str, value: &str) -> Result<()> {
+        // Secrets are created or updated with the same call
+        self.call_secret::<Option<IgnoredAny>, _>(
+            "PUT",
+            &self.get_scope_url(scope, &format!("/actions/secrets/{name}")),
+            Some(GiteaUpdateSecret {
+                data: value.to_string(),
+            }),
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn delete_secret(&self, scope: &VariableScope, name: &str) -> Result<()> {
+        self.call::<(), i32>(
+            "DELETE",
+            &self.get_scope_url(scope, &format!("/actions/secrets/{name}")),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn list_variables(&self, scope: &VariableScope) -> Result<Vec<Variable>> {
+        let variables: Vec<GiteaVariable> =
+            self.call_paginated(&self.get_scope_url(scope, "/actions/variables"), "", None)?;
+
+        Ok(variables
+            .into_iter()
+            .map(|v| Variable {
+                name: v.name,
+                value: v.data,
+                protected: false,
+                masked: false,
+            })
+            .collect())
+    }
+
+    #[instrument(skip_all)]
+    fn set_variable(&self, scope: &VariableScope, variable: Variable) -> Result<()> {
+        let Variable {
+            name,
+            value,
+            protected,
+            masked,
+        } = variable;
+        if protected || masked {
+            return Err(eyre!(
+                "Protected and masked variables are not supported by Gitea, use a secret."
+            ));
+        }
+
+        // Update the variable if it exists already, otherwise create it
+        let url = self.get_scope_url(scope, &format!("/actions/variables/{name}"));
+        let exists = found(self.call_secret::<GiteaVariable, i32>("GET", &url, None))?.is_some();
+        self.call_secret::<Option<IgnoredAny>, _>(
+            if exists { "PUT" } else { "POST" },
+            &url,
+            Some(GiteaUpdateVariable { value }),
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn delete_variable(&self, scope: &VariableScope, name: &str) -> Result<()> {
+        self.call::<Option<IgnoredAny>, i32>(
+            "DELETE",
+            &self.get_scope_url(scope, &format!("/actions/variables/{name}")),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn create_release(&self, release: CreateRelease) -> Result<Release> {
+        let CreateRelease {
+            tag,
+            target,
+            name,
+            description,
+            draft,
+            prerelease,
+        } = release;
+        let release: GiteaRelease = self.call(
+            "POST",
+            &self.get_repository_url("/releases"),
+            Some(GiteaCreateRelease {
+                tag_name: tag,
+                target_commitish: target,
+                name,
+                body: description,
+                draft,
+                prerelease,
+            }),
+        )?;
+
+        Ok(release.into())
+    }
+
+    #[instrument(skip_all)]
+    fn get_release(&self, tag: &str) -> Result<Release> {
+        Ok(self.get_release_data(tag)?.into())
+    }
+
+    #[instrument(skip_all)]
+    fn list_releases(&self) -> Result<Vec<Release>> {
+        let releases: Vec<GiteaRelease> =
+            self.call_paginated(&self.get_repository_url("/releases"), "&limit=50", None)?;
+
+        Ok(releases.into_iter().map(|r| r.into()).collect())
+    }
+
+    #[instrument(skip_all)]
+    fn delete_release(&self, tag: &str) -> Result<()> {
+        self.call::<(), i32>(
+            "DELETE",
+            &self.get_repository_url(&format!("/releases/tags/{}", encode(tag))),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self, content))]
+    fn upload_release_asset(&self, tag: &str, name: &str, content: &[u8]) -> Result<ReleaseAsset> {
+        let release = self.get_release_data(tag)?;
+        let (content_type, body) = multipart_file("attachment", name, content);
+        let asset: GiteaReleaseAsset = self.upload(
+            &self.get_repository_url(&format!(
+                "/releases/{}/assets?name={}",
+                release.id,
+                encode(name)
+            )),
+            &content_type,
+            &body,
+        )?;
+
+        Ok(asset.into())
+    }
+
+    #[instrument(skip(self, writer))]
+    fn download_release_asset(&self, asset: &ReleaseAsset, writer: &mut dyn Write) -> Result<u64> {
+        self.download(&asset.url, writer, asset.size)
+    }
 }
@@ -1,16 +1,26 @@
 // Documentation: https://docs.github.com/en/rest/quickstart
 use super::common::{
-    CreatePullRequest, CreateRepository, ForkRepository, ForkedFromRepository,
-    ListPullRequestFilters, PullRequest, PullRequestState, PullRequestStateFilter, Repository,
-    RepositoryVisibility, User, VersionControl, VersionControlSettings,
+    found, is_status, save_response, ArchiveFormat, BranchProtection, BranchUpdate, Collaborator,
+    CollaboratorPermission, CreateDeployKey, CreateIssue, CreatePullRequest, CreateRelease,
+    CreateRepository, CreateWebhook, DeployKey, ForkRepository, ForkSync, ForkedFromRepository,
+    Issue, IssueState, IssueStateFilter, ListPullRequestFilters, ListRepositoryFilters,
+    PullRequest, PullRequestState, PullRequestStateFilter, Release, ReleaseAsset, Repository,
+    RepositoryVisibility, RequestError, Secret, UpdatePullRequest, UpdateRepository, User,
+    Variable, VariableScope, VersionControl, VersionControlSettings, Webhook, WebhookContentType,
+    WebhookEvent,
 };
+use crypto_box::{aead::OsRng, PublicKey};
 use eyre::{eyre, ContextCompat, Result};
 use native_tls::TlsConnector;
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::{fmt::Debug, sync::Arc};
+use serde::{
+    de::{DeserializeOwned, IgnoredAny},
+    Deserialize, Serialize,
+};
+use std::{fmt::Debug, io::Write, sync::Arc};
 use time::OffsetDateTime;
 use tracing::{info, instrument, trace};
 use ureq::{Agent, AgentBuilder, Error};
+use urlencoding::encode;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct GitHubUser {
@@ -63,6 +73,48 @@ struct GitHubCreateRepository {
     license_template: Option<String>,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubMergeUpstream {
+    branch: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubMergeUpstreamResult {
+    merge_type: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubUpdateRepository {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    homepage: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    visibility: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    archived: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allow_merge_commit: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allow_squash_merge: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allow_rebase_merge: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubTransferRepository {
+    new_owner: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubRepositoryTopics {
+    names: Vec<String>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 struct GitHubForkRepository {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -263,6 +315,16 @@ pub struct GitHubCreatePullRequestReviewers {
     reviewers: Vec<String>,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GitHubAddLabels {
+    labels: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GitHubLabel {
+    pub name: String,
+}
+
 #[derive(Debug, Default, Deserialize, Serialize)]
 pub struct GitHubUpdatePullRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -275,12 +337,403 @@ pub struct GitHubUpdatePullRequest {
     pub state: Option<GitHubPullRequestState>,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GitHubUpdatePullRequestBranch {
+    message: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GitHubUpdatePullRequestBranchHead {
+    expected_head_sha: String,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct GitHubPullRequestMerged {
     merged: bool,
     message: String,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubBranch {
+    name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubRequiredStatusChecks {
+    #[serde(default)]
+    strict: bool,
+    contexts: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubRequiredReviews {
+    #[serde(default)]
+    required_approving_review_count: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubEnabledSetting {
+    enabled: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubBranchProtection {
+    required_status_checks: Option<GitHubRequiredStatusChecks>,
+    required_pull_request_reviews: Option<GitHubRequiredReviews>,
+    allow_force_pushes: Option<GitHubEnabledSetting>,
+    required_linear_history: Option<GitHubEnabledSetting>,
+}
+
+impl GitHubBranchProtection {
+    fn into_branch_protection(self, branch: String) -> BranchProtection {
+        BranchProtection {
+            branch,
+            required_approvals: self
+                .required_pull_request_reviews
+                .map(|reviews| reviews.required_approving_review_count)
+                .unwrap_or_default(),
+            required_status_checks: self
+                .required_status_checks
+                .map(|checks| checks.contexts)
+                .unwrap_or_default(),
+            allow_force_push: self.allow_force_pushes.is_some_and(|s| s.enabled),
+            linear_history: self.required_linear_history.is_some_and(|s| s.enabled),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubUpdateBranchProtection {
+    required_status_checks: Option<GitHubRequiredStatusChecks>,
+    enforce_admins: bool,
+    required_pull_request_reviews: Option<GitHubRequiredReviews>,
+    restrictions: Option<()>,
+    allow_force_pushes: bool,
+    required_linear_history: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubCollaboratorPermissions {
+    admin: bool,
+    push: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubCollaborator {
+    id: u32,
+    login: String,
+    permissions: GitHubCollaboratorPermissions,
+}
+
+impl From<GitHubCollaborator> for Collaborator {
+    fn from(collaborator: GitHubCollaborator) -> Collaborator {
+        let GitHubCollaborator {
+            id,
+            login,
+            permissions,
+        } = collaborator;
+        Collaborator {
+            user: GitHubUser { id, login }.into(),
+            permission: if permissions.admin {
+                CollaboratorPermission::Admin
+            } else if permissions.push {
+                CollaboratorPermission::Write
+            } else {
+                CollaboratorPermission::Read
+            },
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubAddCollaborator {
+    permission: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubWebhookConfig {
+    url: String,
+    #[serde(default)]
+    content_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    secret: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubWebhook {
+    id: u32,
+    active: bool,
+    events: Vec<String>,
+    config: GitHubWebhookConfig,
+}
+
+impl From<GitHubWebhook> for Webhook {
+    fn from(webhook: GitHubWebhook) -> Webhook {
+        let GitHubWebhook {
+            id,
+            active,
+            events,
+            config,
+        } = webhook;
+        Webhook {
+            id: id.to_string(),
+            url: config.url,
+            events: events
+                .iter()
+                .filter_map(|event| match event.as_str() {
+                    "push" => Some(WebhookEvent::Push),
+                    "pull_request" => Some(WebhookEvent::PullRequest),
+                    "create" => Some(WebhookEvent::Tag),
+                    "release" => Some(WebhookEvent::Release),
+                    "issues" => Some(WebhookEvent::Issue),
+                    _ => None,
+                })
+                .collect(),
+            content_type: match config.content_type.as_str() {
+                "form" => WebhookContentType::Form,
+                _ => WebhookContentType::Json,
+            },
+            active,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubCreateWebhook {
+    name: String,
+    active: bool,
+    events: Vec<String>,
+    config: GitHubWebhookConfig,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubDeployKey {
+    id: u32,
+    title: String,
+    key: String,
+    read_only: bool,
+}
+
+impl From<GitHubDeployKey> for DeployKey {
+    fn from(key: GitHubDeployKey) -> DeployKey {
+        let GitHubDeployKey {
+            id,
+            title,
+            key,
+            read_only,
+        } = key;
+        DeployKey {
+            id: id.to_string(),
+            title,
+            key,
+            read_only,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubCreateDeployKey {
+    title: String,
+    key: String,
+    read_only: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubCountedList<T> {
+    total_count: usize,
+    #[serde(alias = "secrets", alias = "variables")]
+    items: Vec<T>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubSecret {
+    name: String,
+    visibility: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubSecretPublicKey {
+    key_id: String,
+    key: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubUpdateSecret {
+    encrypted_value: String,
+    key_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    visibility: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubVariable {
+    name: String,
+    value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    visibility: Option<String>,
+}
+
+// GitHub only accepts secrets encrypted with a libsodium sealed box
+fn seal_secret(public_key: &str, value: &str) -> Result<String> {
+    let public_key = base64::decode(public_key)
+        .ok()
+        .and_then(|key| PublicKey::from_slice(&key).ok())
+        .wrap_err("The public key for secrets is malformed.")?;
+    let sealed = public_key
+        .seal(&mut OsRng, value.as_bytes())
+        .map_err(|_| eyre!("Cannot encrypt the secret."))?;
+
+    Ok(base64::encode(sealed))
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubReleaseAsset {
+    id: u32,
+    name: String,
+    size: u64,
+    browser_download_url: String,
+}
+
+impl From<GitHubReleaseAsset> for ReleaseAsset {
+    fn from(asset: GitHubReleaseAsset) -> ReleaseAsset {
+        ReleaseAsset {
+            id: asset.id.to_string(),
+            name: asset.name,
+            url: asset.browser_download_url,
+            size: Some(asset.size),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubRelease {
+    id: u32,
+    tag_name: String,
+    name: Option<String>,
+    body: Option<String>,
+    draft: bool,
+    prerelease: bool,
+    #[serde(with = "time::serde::iso8601")]
+    created_at: OffsetDateTime,
+    html_url: String,
+    upload_url: String,
+    assets: Vec<GitHubReleaseAsset>,
+}
+
+impl From<GitHubRelease> for Release {
+    fn from(release: GitHubRelease) -> Release {
+        let GitHubRelease {
+            id,
+            tag_name,
+            name,
+            body,
+            draft,
+            prerelease,
+            created_at,
+            html_url,
+            assets,
+            ..
+        } = release;
+        Release {
+            id: id.to_string(),
+            name: name.filter(|n| !n.is_empty()).unwrap_or(tag_name.clone()),
+            tag: tag_name,
+            description: body.unwrap_or_default(),
+            draft,
+            prerelease,
+            created_at,
+            url: html_url,
+            assets: assets.into_iter().map(|a| a.into()).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitHubCreateRelease {
+    tag_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_commitish: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    body: String,
+    draft: bool,
+    prerelease: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub enum GitHubIssueState {
+    #[serde(rename = "open")]
+    Open,
+    #[serde(rename = "closed")]
+    Closed,
+}
+
+impl From<GitHubIssueState> for IssueState {
+    fn from(state: GitHubIssueState) -> IssueState {
+        match state {
+            GitHubIssueState::Open => IssueState::Open,
+            GitHubIssueState::Closed => IssueState::Closed,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GitHubIssue {
+    pub number: u32,
+    pub state: GitHubIssueState,
+    pub title: String,
+    pub body: Option<String>,
+    pub labels: Vec<GitHubLabel>,
+    pub html_url: String,
+    #[serde(with = "time::serde::iso8601")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::iso8601")]
+    pub updated_at: OffsetDateTime,
+    pub user: GitHubUser,
+    // Issues of GitHub include pull requests, they are filtered by this field
+    #[serde(skip_serializing)]
+    pub pull_request: Option<IgnoredAny>,
+}
+
+impl From<GitHubIssue> for Issue {
+    fn from(issue: GitHubIssue) -> Issue {
+        let GitHubIssue {
+            number,
+            state,
+            title,
+            body,
+            labels,
+            html_url,
+            created_at,
+            updated_at,
+            user,
+            ..
+        } = issue;
+        Issue {
+            id: number,
+            state: state.into(),
+            title,
+            description: body.unwrap_or_default(),
+            labels: labels.into_iter().map(|l| l.name).collect(),
+            url: html_url,
+            created_at,
+            updated_at,
+            author: Some(user.into()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GitHubCreateIssue {
+    title: String,
+    body: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GitHubUpdateIssue {
+    state: GitHubIssueState,
+}
+
 #[derive(Debug)]
 pub struct GitHub {
     settings: VersionControlSettings,
@@ -294,19 +747,50 @@ impl GitHub {
     fn get_repository_url(&self, url: &str) -> String {
         format!("/repos/{}{}", self.repo, url)
     }
+
+    #[instrument(skip_all)]
+    fn get_api_url(&self, url: &str) -> String {
+        // Base URL is api.github.com or /api/v3, see https://stackoverflow.com/a/50612869
+        match self.hostname.as_str() {
+            "github.com" => format!("https://api.github.com{url}"),
+            hostname => format!("https://{hostname}/api/v3{url}"),
+        }
+    }
+
     #[instrument(skip_all)]
+    fn get_pull_request_url(&self, url: &str) -> String {
+        // In fork workflows the pull requests live on the upstream repository
+        let repo = self.settings.upstream.as_ref().unwrap_or(&self.repo);
+        format!("/repos/{repo}{url}")
+    }
     fn call<T: DeserializeOwned, U: Serialize + Debug>(
         &self,
         method: &str,
         url: &str,
         body: Option<U>,
     ) -> Result<T> {
-        // Base URL is api.github.com or /api/v3, see https://stackoverflow.com/a/50612869
-        let hostname = match self.hostname.as_str() {
-            "github.com" => "api.github.com".to_string(),
-            hostname => format!("{hostname}/api/v3"),
-        };
-        let url = format!("https://{}{}", hostname, url);
+        self.send(method, url, body, false)
+    }
+
+    // Secrets, variables and webhooks with secrets are kept out of the traces
+    fn call_secret<T: DeserializeOwned, U: Serialize + Debug>(
+        &self,
+        method: &str,
+        url: &str,
+        body: Option<U>,
+    ) -> Result<T> {
+        self.send(method, url, body, true)
+    }
+
+    #[instrument(skip_all)]
+    fn send<T: DeserializeOwned, U: Serialize + Debug>(
+        &self,
+        method: &str,
+        url: &str,
+        body: Option<U>,
+        secret: bool,
+    ) -> Result<T> {
+        let url = self.get_api_url(url);
 
         info!("Calling with {method} on {url}.");
 
@@ -321,7 +805,11 @@ impl GitHub {
             .set("Authorization", &format!("Bearer {}", token))
             .set("Content-Type", "application/json");
         let result = if let Some(body) = &body {
-            trace!("Sending body: {}.", serde_json::to_string(&body)?);
+            if secret {
+                trace!("Sending body: <redacted>.");
+            } else {
+                trace!("Sending body: {}.", serde_json::to_string(&body)?);
+            }
             request.send_json(body)
         } else {
             request.call()
@@ -337,7 +825,9 @@ impl GitHub {
                     status,
                     t.len()
                 );
-                trace!("Response body: {t}.");
+                if !secret {
+                    trace!("Response body: {t}.");
+                }
 
                 // Somewhat hacky, if the response is empty, return null
                 if t.is_empty() {
@@ -355,22 +845,207 @@ impl GitHub {
                     status,
                     t.len()
                 );
-                Err(eyre!("Request failed (response: {}).", t))
+                Err(RequestError { status, body: t }.into())
             }
             Err(Error::Transport(_)) => Err(eyre!("Sending data failed.")),
         }
     }
 
+    #[instrument(skip(self))]
+    fn call_paginated<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        params: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<T>> {
+        let limit = limit.unwrap_or(usize::MAX);
+        let mut collected_values: Vec<T> = vec![];
+        let mut i = 1;
+        while collected_values.len() < limit {
+            info!("Reading page {}.", i);
+
+            let mut page: Vec<T> = self.call(
+                "GET",
+                &format!("{url}?page={i}&per_page=100{params}"),
+                None as Option<i32>,
+            )?;
+
+            if page.is_empty() {
+                break;
+            }
+
+            collected_values.append(&mut page);
+
+            i += 1;
+        }
+        collected_values.truncate(limit);
+        Ok(collected_values)
+    }
+
     #[instrument(skip_all)]
     fn get_repository_data(&self) -> Result<GitHubRepository> {
         self.call::<GitHubRepository, i32>("GET", &self.get_repository_url(""), None)
     }
-}
 
-impl VersionControl for GitHub {
     #[instrument(skip_all)]
-    fn init(hostname: String, repo: String, settings: VersionControlSettings) -> Self {
-        let client = AgentBuilder::new()
+    fn get_scope_url(&self, scope: &VariableScope, url: &str) -> String {
+        match scope {
+            VariableScope::Repository => self.get_repository_url(url),
+            VariableScope::Organization(org) => format!("/orgs/{org}{url}"),
+        }
+    }
+
+    #[instrument(skip(self, content))]
+    fn upload<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        content_type: &str,
+        content: &[u8],
+    ) -> Result<T> {
+        info!("Uploading {} bytes to {url}.", content.len());
+
+        let token = &self.settings.auth;
+
+        trace!("Authenticating with token '{token}'.");
+
+        let result = self
+            .client
+            .request("POST", url)
+            .set("User-Agent", "gr")
+            .set("Authorization", &format!("Bearer {}", token))
+            .set("Content-Type", content_type)
+            .send_bytes(content);
+
+        match result {
+            Ok(result) => {
+                let status = result.status();
+                let mut t = result.into_string()?;
+
+                info!(
+                    "Received response with response code {} with body size {}.",
+                    status,
+                    t.len()
+                );
+                trace!("Response body: {t}.");
+
+                // Somewhat hacky, if the response is empty, return null
+                if t.is_empty() {
+                    t = "null".to_string();
+                }
+
+                let t: T = serde_json::from_str(&t)?;
+                Ok(t)
+            }
+            Err(Error::Status(status, result)) => {
+                let t = result.into_string()?;
+
+                info!(
+                    "Received response with response code {} with body size {}.",
+                    status,
+                    t.len()
+                );
+                Err(RequestError { status, body: t }.into())
+            }
+            Err(Error::Transport(_)) => Err(eyre!("Sending data failed.")),
+        }
+    }
+
+    #[instrument(skip(self, writer))]
+    fn download(
+        &self,
+        url: &str,
+        writer: &mut dyn Write,
+        expected_size: Option<u64>,
+    ) -> Result<u64> {
+        info!("Downloading {url}.");
+
+        let token = &self.settings.auth;
+
+        trace!("Authenticating with token '{token}'.");
+
+        // The authorization is not forwarded, if the download is redirected to a storage
+        let result = self
+            .client
+            .get(url)
+            .set("User-Agent", "gr")
+            .set("Authorization", &format!("Bearer {}", token))
+            .set("Accept", "application/octet-stream")
+            .call();
+
+        match result {
+            Ok(response) => save_response(response, writer, expected_size),
+            Err(Error::Status(status, result)) => {
+                let t = result.into_string()?;
+
+                info!(
+                    "Received response with response code {} with body size {}.",
+                    status,
+                    t.len()
+                );
+                Err(RequestError { status, body: t }.into())
+            }
+            Err(Error::Transport(_)) => Err(eyre!("Sending data failed.")),
+        }
+    }
+
+    #[instrument(skip_all)]
+    fn get_release_data(&self, tag: &str) -> Result<GitHubRelease> {
+        self.call::<GitHubRelease, i32>(
+            "GET",
+            &self.get_repository_url(&format!("/releases/tags/{}", encode(tag))),
+            None,
+        )
+        // Draft releases cannot be found by their tag, only in the list
+        .or_else(|err| {
+            let releases: Vec<GitHubRelease> =
+                self.call_paginated(&self.get_repository_url("/releases"), "", None)?;
+            releases
+                .into_iter()
+                .find(|release| release.draft && release.tag_name == tag)
+                .ok_or(err)
+        })
+    }
+
+    #[instrument(skip_all)]
+    fn call_paginated_list<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        params: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<T>> {
+        let limit = limit.unwrap_or(usize::MAX);
+        let mut collected_values: Vec<T> = vec![];
+        let mut i = 1;
+        while collected_values.len() < limit {
+            info!("Reading page {}.", i);
+
+            let mut page: GitHubCountedList<T> = self.call(
+                "GET",
+                &format!("{url}?page={i}&per_page=100{params}"),
+                None as Option<i32>,
+            )?;
+
+            if page.items.is_empty() {
+                break;
+            }
+
+            collected_values.append(&mut page.items);
+
+            if collected_values.len() >= page.total_count {
+                break;
+            }
+
+            i += 1;
+        }
+        collected_values.truncate(limit);
+        Ok(collected_values)
+    }
+}
+
+impl VersionControl for GitHub {
+    #[instrument(skip_all)]
+    fn init(hostname: String, repo: String, settings: VersionControlSettings) -> Self {
+        let client = AgentBuilder::new()
             .tls_connector(Arc::new(TlsConnector::new().unwrap()))
             .build();
 
@@ -400,23 +1075,37 @@ impl VersionControl for GitHub {
         }
     }
 
+    #[instrument(skip_all)]
+    fn get_current_user(&self) -> Result<User> {
+        let user: GitHubUser = self.call("GET", "/user", None as Option<i32>)?;
+
+        Ok(user.into())
+    }
+
+    #[instrument(skip(self))]
+    fn requires_pushed_branch(&self) -> Result<bool> {
+        Ok(true)
+    }
+
+    #[instrument(skip(self))]
+    fn tracks_pull_requests(&self) -> Result<bool> {
+        Ok(true)
+    }
+
     #[instrument(skip(self))]
     fn create_pr(&self, mut pr: CreatePullRequest) -> Result<PullRequest> {
         let reviewers = pr.reviewers.clone();
+        let labels = pr.labels.clone();
         pr.target = pr.target.or(self.settings.default_branch.clone());
         if pr.target.is_none() {
             let GitHubRepository { default_branch, .. } = self.get_repository_data()?;
             pr.target = Some(default_branch);
         }
 
-        let mut url = self.get_repository_url("/pulls");
+        let url = self.get_pull_request_url("/pulls");
         let mut github_pr = GitHubCreatePullRequest::from(pr);
-        if self.settings.fork {
-            let repo = self.get_repository()?;
-            if let Some(forked) = repo.forked_from {
-                url = format!("/repos/{}/pulls", forked.full_name);
-                github_pr.head_repo = Some(repo.full_name);
-            }
+        if self.settings.upstream.is_some() {
+            github_pr.head_repo = Some(self.repo.clone());
         }
 
         let new_pr: GitHubPullRequest = self.call("POST", &url, Some(github_pr))?;
@@ -427,14 +1116,44 @@ impl VersionControl for GitHub {
             Some(GitHubCreatePullRequestReviewers { reviewers }),
         )?;
 
+        // Pull requests share the labels of the issues
+        if !labels.is_empty() {
+            let _: Vec<GitHubLabel> = self.call(
+                "POST",
+                &self.get_pull_request_url(&format!("/issues/{}/labels", new_pr.number)),
+                Some(GitHubAddLabels { labels }),
+            )?;
+        }
+
         Ok(new_pr.into())
     }
 
+    #[instrument(skip(self))]
+    fn update_pr(&self, id: u32, pr: UpdatePullRequest) -> Result<PullRequest> {
+        let UpdatePullRequest {
+            title,
+            description,
+            target,
+        } = pr;
+        let pr: GitHubPullRequest = self.call(
+            "PATCH",
+            &self.get_pull_request_url(&format!("/pulls/{id}")),
+            Some(GitHubUpdatePullRequest {
+                title,
+                body: description,
+                base: target,
+                ..GitHubUpdatePullRequest::default()
+            }),
+        )?;
+
+        Ok(pr.into())
+    }
+
     #[instrument(skip(self))]
     fn get_pr_by_id(&self, id: u32) -> Result<PullRequest> {
         let pr: GitHubPullRequest = self.call(
             "GET",
-            &self.get_repository_url(&format!("/pulls/{id}")),
+            &self.get_pull_request_url(&format!("/pulls/{id}")),
             None as Option<i32>,
         )?;
 
@@ -451,7 +1170,7 @@ impl VersionControl for GitHub {
 
         let prs: Vec<GitHubPullRequest> = self.call(
             "GET",
-            &self.get_repository_url(&format!("/pulls?state=all&head={head}:{branch}")),
+            &self.get_pull_request_url(&format!("/pulls?state=all&head={head}:{branch}")),
             None as Option<i32>,
         )?;
 
@@ -472,7 +1191,7 @@ impl VersionControl for GitHub {
         };
         let prs: Vec<GitHubPullRequest> = self.call(
             "GET",
-            &self.get_repository_url(&format!("/pulls?state={state}")),
+            &self.get_pull_request_url(&format!("/pulls?state={state}")),
             None as Option<i32>,
         )?;
 
@@ -481,9 +1200,9 @@ impl VersionControl for GitHub {
 
     #[instrument(skip(self))]
     fn approve_pr(&self, id: u32) -> Result<()> {
-        self.call(
+        self.call::<(), _>(
             "POST",
-            &self.get_repository_url(&format!("/pulls/{id}/reviews")),
+            &self.get_pull_request_url(&format!("/pulls/{id}/reviews")),
             Some(GitHubCreatePullRequestReview {
                 event: GitHubCreatePullRequestReviewEvent::Approve,
                 body: None,
@@ -501,7 +1220,7 @@ impl VersionControl for GitHub {
         };
         let pr: GitHubPullRequest = self.call(
             "PATCH",
-            &self.get_repository_url(&format!("/pulls/{id}")),
+            &self.get_pull_request_url(&format!("/pulls/{id}")),
             Some(closing),
         )?;
 
@@ -512,13 +1231,97 @@ impl VersionControl for GitHub {
     fn merge_pr(&self, id: u32, _: bool) -> Result<PullRequest> {
         let _: GitHubPullRequestMerged = self.call(
             "PUT",
-            &self.get_repository_url(&format!("/pulls/{id}/merge")),
+            &self.get_pull_request_url(&format!("/pulls/{id}/merge")),
             None as Option<i32>,
         )?;
 
         self.get_pr_by_id(id)
     }
 
+    #[instrument(skip(self))]
+    fn update_pr_branch(&self, id: u32, head_sha: &str, rebase: bool) -> Result<BranchUpdate> {
+        // GitHub can only merge the target branch with the API
+        if rebase {
+            return Ok(BranchUpdate::Unsupported);
+        }
+
+        let _: GitHubUpdatePullRequestBranch = self.call(
+            "PUT",
+            &self.get_pull_request_url(&format!("/pulls/{id}/update-branch")),
+            // Fail if the branch changed since it was checked, instead of merging into it
+            Some(GitHubUpdatePullRequestBranchHead {
+                expected_head_sha: head_sha.to_string(),
+            }),
+        )?;
+
+        Ok(BranchUpdate::Updated)
+    }
+
+    #[instrument(skip(self))]
+    fn create_issue(&self, issue: CreateIssue) -> Result<Issue> {
+        let CreateIssue { title, description } = issue;
+        let issue: GitHubIssue = self.call(
+            "POST",
+            &self.get_repository_url("/issues"),
+            Some(GitHubCreateIssue {
+                title,
+                body: description,
+            }),
+        )?;
+
+        Ok(issue.into())
+    }
+
+    #[instrument(skip(self))]
+    fn get_issue(&self, id: u32) -> Result<Issue> {
+        let issue: GitHubIssue = self.call(
+            "GET",
+            &self.get_repository_url(&format!("/issues/{id}")),
+            None as Option<i32>,
+        )?;
+        if issue.pull_request.is_some() {
+            return Err(eyre!("#{id} is a pull request, not an issue."));
+        }
+
+        Ok(issue.into())
+    }
+
+    #[instrument(skip(self))]
+    fn list_issues(&self, state: IssueStateFilter) -> Result<Vec<Issue>> {
+        let state = match state {
+            IssueStateFilter::Open => "open",
+            IssueStateFilter::Closed => "closed",
+            IssueStateFilter::All => "all",
+        };
+        let issues: Vec<GitHubIssue> = self.call_paginated(
+            &self.get_repository_url("/issues"),
+            &format!("&state={state}"),
+            None,
+        )?;
+
+        Ok(issues
+            .into_iter()
+            .filter(|issue| issue.pull_request.is_none())
+            .map(|issue| issue.into())
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    fn close_issue(&self, id: u32) -> Result<Issue> {
+        // Pull requests share the numbers of issues, don't close one by mistake
+        self.get_issue(id)?;
+
+        let issue: GitHubIssue = self.call(
+            "PATCH",
+            &self.get_repository_url(&format!("/issues/{id}")),
+            Some(GitHubUpdateIssue {
+                state: GitHubIssueState::Closed,
+            }),
+        )?;
+
+        Ok(issue.into())
+    }
+
     #[instrument(skip_all)]
     fn get_repository(&self) -> Result<Repository> {
         let repo = self.get_repository_data()?;
@@ -526,6 +1329,45 @@ impl VersionControl for GitHub {
         Ok(repo.into())
     }
 
+    #[instrument(skip(self))]
+    fn list_repositories(
+        &self,
+        owner: Option<&str>,
+        filters: ListRepositoryFilters,
+    ) -> Result<Vec<Repository>> {
+        let repos: Vec<GitHubRepository> = if let Some(owner) = owner {
+            // Organizations list private repositories too, users only the public ones
+            self.call_paginated(&format!("/orgs/{owner}/repos"), "", None)
+                .or_else(|err| {
+                    // Fall back to the user, if there is no organization with this name
+                    if is_status(&err, 404) {
+                        self.call_paginated(&format!("/users/{owner}/repos"), "", None)
+                    } else {
+                        Err(err)
+                    }
+                })?
+        } else {
+            self.call_paginated("/user/repos", "&affiliation=owner", None)?
+        };
+
+        Ok(repos
+            .into_iter()
+            .map(Repository::from)
+            .filter(|repo| filters.matches(repo))
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    fn search_repositories(&self, query: &str, limit: usize) -> Result<Vec<Repository>> {
+        let repos: Vec<GitHubRepository> = self.call_paginated_list(
+            "/search/repositories",
+            &format!("&q={}", encode(query)),
+            Some(limit),
+        )?;
+
+        Ok(repos.into_iter().map(|repo| repo.into()).collect())
+    }
+
     #[instrument(skip_all)]
     fn create_repository(&self, repo: CreateRepository) -> Result<Repository> {
         let CreateRepository {
@@ -568,10 +1410,559 @@ impl VersionControl for GitHub {
         Ok(new_repo.into())
     }
 
+    #[instrument(skip_all)]
+    fn update_repository(&self, repo: UpdateRepository) -> Result<Repository> {
+        let UpdateRepository {
+            name,
+            description,
+            visibility,
+            default_branch,
+            homepage,
+            topics,
+            archived,
+            allow_merge_commit,
+            allow_squash_merge,
+            allow_rebase_merge,
+        } = repo;
+
+        // Topics have to be replaced separately, before the repository may be renamed
+        if let Some(names) = topics {
+            let _: GitHubRepositoryTopics = self.call(
+                "PUT",
+                &self.get_repository_url("/topics"),
+                Some(GitHubRepositoryTopics { names }),
+            )?;
+        }
+
+        let repo: GitHubRepository = self.call(
+            "PATCH",
+            &self.get_repository_url(""),
+            Some(GitHubUpdateRepository {
+                name,
+                description,
+                homepage,
+                visibility: visibility.map(|visibility| {
+                    match visibility {
+                        RepositoryVisibility::Public => "public",
+                        RepositoryVisibility::Internal => "internal",
+                        RepositoryVisibility::Private => "private",
+                    }
+                    .to_string()
+                }),
+                default_branch,
+                archived,
+                allow_merge_commit,
+                allow_squash_merge,
+                allow_rebase_merge,
+            }),
+        )?;
+
+        Ok(repo.into())
+    }
+
+    #[instrument(skip_all)]
+    fn transfer_repository(&self, owner: &str) -> Result<Repository> {
+        let repo: GitHubRepository = self.call(
+            "POST",
+            &self.get_repository_url("/transfer"),
+            Some(GitHubTransferRepository {
+                new_owner: owner.to_string(),
+            }),
+        )?;
+
+        Ok(repo.into())
+    }
+
     #[instrument(skip_all)]
     fn delete_repository(&self) -> Result<()> {
-        self.call("DELETE", &self.get_repository_url(""), None as Option<i32>)?;
+        self.call::<(), _>("DELETE", &self.get_repository_url(""), None as Option<i32>)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    fn sync_fork(&self, branch: &str) -> Result<ForkSync> {
+        let result: GitHubMergeUpstreamResult = self
+            .call(
+                "POST",
+                &self.get_repository_url("/merge-upstream"),
+                Some(GitHubMergeUpstream {
+                    branch: branch.to_string(),
+                }),
+            )
+            .map_err(|err| {
+                // Only a conflict means the branches diverged, other errors are reported as is
+                if is_status(&err, 409) {
+                    err.wrap_err(eyre!(
+                        "Cannot sync {branch} with upstream, the branches have conflicting changes (use --force to reset it)."
+                    ))
+                } else {
+                    err
+                }
+            })?;
+
+        match result.merge_type.as_str() {
+            "fast-forward" => Ok(ForkSync::FastForward),
+            "merge" => Ok(ForkSync::Merge),
+            _ => Ok(ForkSync::UpToDate),
+        }
+    }
+
+    #[instrument(skip(self, writer))]
+    fn download_archive(
+        &self,
+        reference: &str,
+        format: ArchiveFormat,
+        writer: &mut dyn Write,
+    ) -> Result<u64> {
+        let archive = match format {
+            ArchiveFormat::TarGz => "tarball",
+            ArchiveFormat::Zip => "zipball",
+        };
+        self.download(
+            &self.get_api_url(
+                &self.get_repository_url(&format!("/{archive}/{}", encode(reference))),
+            ),
+            writer,
+            None,
+        )
+    }
+
+    #[instrument(skip_all)]
+    fn list_deploy_keys(&self) -> Result<Vec<DeployKey>> {
+        let keys: Vec<GitHubDeployKey> =
+            self.call_paginated(&self.get_repository_url("/keys"), "", None)?;
+
+        Ok(keys.into_iter().map(|k| k.into()).collect())
+    }
+
+    #[instrument(skip_all)]
+    fn add_deploy_key(&self, key: CreateDeployKey) -> Result<DeployKey> {
+        let CreateDeployKey {
+            title,
+            key,
+            read_only,
+        } = key;
+        let key: GitHubDeployKey = self.call(
+            "POST",
+            &self.get_repository_url("/keys"),
+            Some(GitHubCreateDeployKey {
+                title,
+                key,
+                read_only,
+            }),
+        )?;
+
+        Ok(key.into())
+    }
+
+    #[instrument(skip_all)]
+    fn remove_deploy_key(&self, id: &str) -> Result<()> {
+        self.call::<(), i32>(
+            "DELETE",
+            &self.get_repository_url(&format!("/keys/{id}")),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn list_branch_protections(&self) -> Result<Vec<BranchProtection>> {
+        let branches: Vec<GitHubBranch> = self.call_paginated(
+            &self.get_repository_url("/branches"),
+            "&protected=true",
+            None,
+        )?;
+
+        branches
+            .into_iter()
+            .map(|branch| {
+                let protection: GitHubBranchProtection = self.call(
+                    "GET",
+                    &self.get_repository_url(&format!(
+                        "/branches/{}/protection",
+                        encode(&branch.name)
+                    )),
+                    None as Option<i32>,
+                )?;
+
+                Ok(protection.into_branch_protection(branch.name))
+            })
+            .collect()
+    }
+
+    #[instrument(skip_all)]
+    fn protect_branch(&self, protection: BranchProtection) -> Result<BranchProtection> {
+        let BranchProtection {
+            branch,
+            required_approvals,
+            required_status_checks,
+            allow_force_push,
+            linear_history,
+        } = protection;
+
+        // The whole protection is replaced, so running it again gives the same result
+        let protection: GitHubBranchProtection = self.call(
+            "PUT",
+            &self.get_repository_url(&format!("/branches/{}/protection", encode(&branch))),
+            Some(GitHubUpdateBranchProtection {
+                required_status_checks: (!required_status_checks.is_empty()).then_some(
+                    GitHubRequiredStatusChecks {
+                        strict: false,
+                        contexts: required_status_checks,
+                    },
+                ),
+                enforce_admins: false,
+                required_pull_request_reviews: (required_approvals > 0).then_some(
+                    GitHubRequiredReviews {
+                        required_approving_review_count: required_approvals,
+                    },
+                ),
+                restrictions: None,
+                allow_force_pushes: allow_force_push,
+                required_linear_history: linear_history,
+            }),
+        )?;
+
+        Ok(protection.into_branch_protection(branch))
+    }
+
+    #[instrument(skip_all)]
+    fn list_collaborators(&self) -> Result<Vec<Collaborator>> {
+        let collaborators: Vec<GitHubCollaborator> =
+            self.call_paginated(&self.get_repository_url("/collaborators"), "", None)?;
+
+        Ok(collaborators.into_iter().map(|c| c.into()).collect())
+    }
+
+    #[instrument(skip_all)]
+    fn add_collaborator(&self, username: &str, permission: CollaboratorPermission) -> Result<()> {
+        // New collaborators get an invitation, existing ones are updated
+        self.call::<Option<IgnoredAny>, _>(
+            "PUT",
+            &self.get_repository_url(&format!("/collaborators/{username}")),
+            Some(GitHubAddCollaborator {
+                permission: match permission {
+                    CollaboratorPermission::Read => "pull",
+                    CollaboratorPermission::Write => "push",
+                    CollaboratorPermission::Admin => "admin",
+                }
+                .to_string(),
+            }),
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn remove_collaborator(&self, username: &str) -> Result<()> {
+        self.call::<(), i32>(
+            "DELETE",
+            &self.get_repository_url(&format!("/collaborators/{username}")),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn list_webhooks(&self) -> Result<Vec<Webhook>> {
+        let webhooks: Vec<GitHubWebhook> =
+            self.call_paginated(&self.get_repository_url("/hooks"), "", None)?;
+
+        Ok(webhooks.into_iter().map(|w| w.into()).collect())
+    }
+
+    #[instrument(skip_all)]
+    fn create_webhook(&self, webhook: CreateWebhook) -> Result<Webhook> {
+        let CreateWebhook {
+            url,
+            secret,
+            content_type,
+            events,
+        } = webhook;
+        let webhook: GitHubWebhook = self.call_secret(
+            "POST",
+            &self.get_repository_url("/hooks"),
+            Some(GitHubCreateWebhook {
+                name: "web".to_string(),
+                active: true,
+                // Tags are sent with the create event (that also includes branches)
+                events: events
+                    .into_iter()
+                    .map(|event| {
+                        match event {
+                            WebhookEvent::Push => "push",
+                            WebhookEvent::PullRequest => "pull_request",
+                            WebhookEvent::Tag => "create",
+                            WebhookEvent::Release => "release",
+                            WebhookEvent::Issue => "issues",
+                        }
+                        .to_string()
+                    })
+                    .collect(),
+                config: GitHubWebhookConfig {
+                    url,
+                    content_type: match content_type {
+                        WebhookContentType::Json => "json",
+                        WebhookContentType::Form => "form",
+                    }
+                    .to_string(),
+                    secret,
+                },
+            }),
+        )?;
+
+        Ok(webhook.into())
+    }
+
+    #[instrument(skip_all)]
+    fn delete_webhook(&self, id: &str) -> Result<()> {
+        self.call::<(), i32>(
+            "DELETE",
+            &self.get_repository_url(&format!("/hooks/{id}")),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn test_webhook(&self, id: &str) -> Result<()> {
+        self.call::<(), i32>(
+            "POST",
+            &self.get_repository_url(&format!("/hooks/{id}/pings")),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn list_secrets(&self, scope: &VariableScope) -> Result<Vec<Secret>> {
+        let secrets: Vec<GitHubSecret> =
+            self.call_paginated_list(&self.get_scope_url(scope, "/actions/secrets"), "", None)?;
+
+        Ok(secrets
+            .into_iter()
+            .map(|s| Secret { name: s.name })
+            .collect())
+    }
+
+    #[instrument(skip_all)]
+    fn set_secret(&self, scope: &VariableScope, name: &str, value: &str) -> Result<()> {
+        let public_key: GitHubSecretPublicKey = self.call_secret(
+            "GET",
+            &self.get_scope_url(scope, "/actions/secrets/public-key"),
+            None as Option<i32>,
+        )?;
+        let url = self.get_scope_url(scope, &format!("/actions/secrets/{name}"));
+
+        // Organization secrets need a visibility, keep the current one when updating
+        let visibility = match scope {
+            VariableScope::Repository => None,
+            VariableScope::Organization(_) => Some(
+                found(self.call_secret::<GitHubSecret, i32>("GET", &url, None))?
+                    .and_then(|secret| secret.visibility)
+                    .unwrap_or("private".to_string()),
+            ),
+        };
+
+        self.call_secret::<Option<IgnoredAny>, _>(
+            "PUT",
+            &url,
+            Some(GitHubUpdateSecret {
+                encrypted_value: seal_secret(&public_key.key, value)?,
+                key_id: public_key.key_id,
+                visibility,
+            }),
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn delete_secret(&self, scope: &VariableScope, name: &str) -> Result<()> {
+        self.call::<(), i32>(
+            "DELETE",
+            &self.get_scope_url(scope, &format!("/actions/secrets/{name}")),
+            None,
+        )?;
 
         Ok(())
     }
+
+    #[instrument(skip_all)]
+    fn list_variables(&self, scope: &VariableScope) -> Result<Vec<Variable>> {
+        let variables: Vec<GitHubVariable> =
+            self.call_paginated_list(&self.get_scope_url(scope, "/actions/variables"), "", None)?;
+
+        Ok(variables
+            .into_iter()
+            .map(|v| Variable {
+                name: v.name,
+                value: v.value,
+                protected: false,
+                masked: false,
+            })
+            .collect())
+    }
+
+    #[instrument(skip_all)]
+    fn set_variable(&self, scope: &VariableScope, variable: Variable) -> Result<()> {
+        let Variable {
+            name,
+            value,
+            protected,
+            masked,
+        } = variable;
+        if protected || masked {
+            return Err(eyre!(
+                "Protected and masked variables are not supported by GitHub, use a secret."
+            ));
+        }
+
+        // Update the variable if it exists already, otherwise create it
+        let url = self.get_scope_url(scope, &format!("/actions/variables/{name}"));
+        let exists = found(self.call_secret::<GitHubVariable, i32>("GET", &url, None))?.is_some();
+        if exists {
+            self.call_secret::<(), _>(
+                "PATCH",
+                &url,
+                Some(GitHubVariable {
+                    name,
+                    value,
+                    visibility: None,
+                }),
+            )?;
+        } else {
+            self.call_secret::<Option<IgnoredAny>, _>(
+                "POST",
+                &self.get_scope_url(scope, "/actions/variables"),
+                Some(GitHubVariable {
+                    name,
+                    value,
+                    visibility: match scope {
+                        VariableScope::Repository => None,
+                        VariableScope::Organization(_) => Some("private".to_string()),
+                    },
+                }),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn delete_variable(&self, scope: &VariableScope, name: &str) -> Result<()> {
+        self.call::<(), i32>(
+            "DELETE",
+            &self.get_scope_url(scope, &format!("/actions/variables/{name}")),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn create_release(&self, release: CreateRelease) -> Result<Release> {
+        let CreateRelease {
+            tag,
+            target,
+            name,
+            description,
+            draft,
+            prerelease,
+        } = release;
+        let release: GitHubRelease = self.call(
+            "POST",
+            &self.get_repository_url("/releases"),
+            Some(GitHubCreateRelease {
+                tag_name: tag,
+                target_commitish: target,
+                name,
+                body: description,
+                draft,
+                prerelease,
+            }),
+        )?;
+
+        Ok(release.into())
+    }
+
+    #[instrument(skip_all)]
+    fn get_release(&self, tag: &str) -> Result<Release> {
+        Ok(self.get_release_data(tag)?.into())
+    }
+
+    #[instrument(skip_all)]
+    fn list_releases(&self) -> Result<Vec<Release>> {
+        let releases: Vec<GitHubRelease> =
+            self.call_paginated(&self.get_repository_url("/releases"), "", None)?;
+
+        Ok(releases.into_iter().map(|r| r.into()).collect())
+    }
+
+    #[instrument(skip_all)]
+    fn delete_release(&self, tag: &str) -> Result<()> {
+        let release = self.get_release_data(tag)?;
+        self.call::<(), i32>(
+            "DELETE",
+            &self.get_repository_url(&format!("/releases/{}", release.id)),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self, content))]
+    fn upload_release_asset(&self, tag: &str, name: &str, content: &[u8]) -> Result<ReleaseAsset> {
+        let release = self.get_release_data(tag)?;
+
+        // The upload URL is a URI template like .../assets{?name,label}
+        let (upload_url, _) = release
+            .upload_url
+            .split_once('{')
+            .unwrap_or((&release.upload_url, ""));
+        let asset: GitHubReleaseAsset = self.upload(
+            &format!("{upload_url}?name={}", encode(name)),
+            "application/octet-stream",
+            content,
+        )?;
+
+        Ok(asset.into())
+    }
+
+    #[instrument(skip(self, writer))]
+    fn download_release_asset(&self, asset: &ReleaseAsset, writer: &mut dyn Write) -> Result<u64> {
+        // The browser URL doesn't work for private repositories, the API has to be used
+        self.download(
+            &self.get_api_url(&self.get_repository_url(&format!("/releases/assets/{}", asset.id))),
+            writer,
+            asset.size,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::seal_secret;
+    use crypto_box::{aead::OsRng, SecretKey};
+
+    #[test]
+    fn seals_secrets_for_the_public_key() {
+        let secret_key = SecretKey::generate(&mut OsRng);
+        let public_key = base64::encode(secret_key.public_key().as_bytes());
+
+        let sealed = seal_secret(&public_key, "hunter2").unwrap();
+        let unsealed = secret_key.unseal(&base64::decode(sealed).unwrap()).unwrap();
+
+        assert_eq!(unsealed, b"hunter2");
+    }
+
+    #[test]
+    fn rejects_malformed_public_keys() {
+        assert!(seal_secret("bm90IGEga2V5", "hunter2").is_err());
+    }
 }
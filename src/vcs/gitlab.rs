@@ -1,14 +1,18 @@
 // Documentation: https://docs.gitlab.com/ee/api/api_resources.html
 use super::common::{
-    CreatePullRequest, CreateRepository, ForkRepository, ForkedFromRepository,
-    ListPullRequestFilters, PullRequest, PullRequestState, PullRequestStateFilter,
-    PullRequestUserFilter, Repository, RepositoryVisibility, User, VersionControl,
-    VersionControlSettings,
+    found, is_status, multipart_file, save_response, ArchiveFormat, BranchProtection, BranchUpdate,
+    Collaborator, CollaboratorPermission, CreateDeployKey, CreateIssue, CreatePullRequest,
+    CreateRelease, CreateRepository, CreateWebhook, DeployKey, ForkRepository, ForkSync,
+    ForkedFromRepository, Issue, IssueState, IssueStateFilter, ListPullRequestFilters,
+    ListRepositoryFilters, PullRequest, PullRequestState, PullRequestStateFilter,
+    PullRequestUserFilter, Release, ReleaseAsset, Repository, RepositoryVisibility, RequestError,
+    Secret, UpdatePullRequest, UpdateRepository, User, Variable, VariableScope, VersionControl,
+    VersionControlSettings, Webhook, WebhookContentType, WebhookEvent,
 };
-use eyre::{eyre, Result};
+use eyre::{eyre, Context, ContextCompat, Result};
 use native_tls::TlsConnector;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::{fmt::Debug, sync::Arc};
+use std::{fmt::Debug, io::Write, sync::Arc};
 use time::OffsetDateTime;
 use tracing::{info, instrument, trace};
 use ureq::{Agent, AgentBuilder, Error};
@@ -46,6 +50,7 @@ pub struct GitLabApproval {
 pub struct GitLabNamespace {
     id: u32,
     name: String,
+    full_path: String,
     web_url: String,
 }
 
@@ -81,7 +86,8 @@ struct GitLabRepository {
     created_at: OffsetDateTime,
     #[serde(with = "time::serde::iso8601")]
     last_activity_at: OffsetDateTime,
-    default_branch: String,
+    // Empty projects don't have a default branch
+    default_branch: Option<String>,
     web_url: String,
     ssh_url_to_repo: String,
     http_url_to_repo: String,
@@ -127,7 +133,7 @@ impl From<GitLabRepository> for Repository {
             created_at,
             updated_at: last_activity_at,
             archived,
-            default_branch,
+            default_branch: default_branch.unwrap_or_default(),
             forks_count,
             stars_count: star_count,
             ssh_url: ssh_url_to_repo,
@@ -166,12 +172,37 @@ struct GitLabCreateRepository {
     default_branch: Option<String>,
 }
 
+#[derive(Debug, Deserialize, Serialize, Default, PartialEq)]
+struct GitLabUpdateRepository {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    visibility: Option<GitLabRepositoryVisibility>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    topics: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    merge_method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    squash_option: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitLabTransferRepository {
+    namespace: String,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 struct GitLabForkRepository {
     #[serde(skip_serializing_if = "Option::is_none")]
     name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    namespace_path: Option<String>,
+    namespace_id: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     path: Option<String>,
 }
@@ -292,6 +323,8 @@ pub struct GitLabCreatePullRequest {
     pub reviewer_ids: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub target_project_id: Option<u32>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub labels: String,
 }
 
 impl From<CreatePullRequest> for GitLabCreatePullRequest {
@@ -303,6 +336,7 @@ impl From<CreatePullRequest> for GitLabCreatePullRequest {
             target,
             close_source_branch,
             reviewers,
+            labels,
         } = pr;
         Self {
             title,
@@ -313,6 +347,7 @@ impl From<CreatePullRequest> for GitLabCreatePullRequest {
             remove_source_branch: close_source_branch,
             reviewer_ids: reviewers,
             target_project_id: None,
+            labels: labels.join(","),
         }
     }
 }
@@ -342,11 +377,364 @@ pub struct GitLabUpdatePullRequest {
     pub state_event: Option<GitLabUpdatePullRequestStateEvent>,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GitLabRebasePullRequest {
+    rebase_in_progress: bool,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct GitLabMergePullRequest {
     pub should_remove_source_branch: bool,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+struct GitLabProtectedBranch {
+    name: String,
+    #[serde(default)]
+    allow_force_push: bool,
+}
+
+impl From<GitLabProtectedBranch> for BranchProtection {
+    fn from(branch: GitLabProtectedBranch) -> BranchProtection {
+        BranchProtection {
+            branch: branch.name,
+            allow_force_push: branch.allow_force_push,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitLabUpdateProtectedBranch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    allow_force_push: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitLabMember {
+    id: u32,
+    username: String,
+    name: String,
+    access_level: u32,
+}
+
+impl From<GitLabMember> for Collaborator {
+    fn from(member: GitLabMember) -> Collaborator {
+        let GitLabMember {
+            id,
+            username,
+            name,
+            access_level,
+        } = member;
+        Collaborator {
+            user: GitLabUser { id, username, name }.into(),
+            // Maintainers and owners can change the settings, developers can push
+            permission: match access_level {
+                40.. => CollaboratorPermission::Admin,
+                30..=39 => CollaboratorPermission::Write,
+                _ => CollaboratorPermission::Read,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitLabAddMember {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user_id: Option<String>,
+    access_level: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitLabWebhook {
+    id: u32,
+    url: String,
+    #[serde(default)]
+    push_events: bool,
+    #[serde(default)]
+    merge_requests_events: bool,
+    #[serde(default)]
+    tag_push_events: bool,
+    #[serde(default)]
+    releases_events: bool,
+    #[serde(default)]
+    issues_events: bool,
+    #[serde(default)]
+    disabled_until: Option<String>,
+}
+
+impl From<GitLabWebhook> for Webhook {
+    fn from(webhook: GitLabWebhook) -> Webhook {
+        let events = [
+            (webhook.push_events, WebhookEvent::Push),
+            (webhook.merge_requests_events, WebhookEvent::PullRequest),
+            (webhook.tag_push_events, WebhookEvent::Tag),
+            (webhook.releases_events, WebhookEvent::Release),
+            (webhook.issues_events, WebhookEvent::Issue),
+        ];
+        Webhook {
+            id: webhook.id.to_string(),
+            url: webhook.url,
+            events: events
+                .into_iter()
+                .filter_map(|(enabled, event)| enabled.then_some(event))
+                .collect(),
+            // GitLab always sends JSON
+            content_type: WebhookContentType::Json,
+            active: webhook.disabled_until.is_none(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitLabCreateWebhook {
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+    push_events: bool,
+    merge_requests_events: bool,
+    tag_push_events: bool,
+    releases_events: bool,
+    issues_events: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitLabMessage {
+    message: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitLabDeployKey {
+    id: u32,
+    title: String,
+    key: String,
+    #[serde(default)]
+    can_push: bool,
+}
+
+impl From<GitLabDeployKey> for DeployKey {
+    fn from(key: GitLabDeployKey) -> DeployKey {
+        let GitLabDeployKey {
+            id,
+            title,
+            key,
+            can_push,
+        } = key;
+        DeployKey {
+            id: id.to_string(),
+            title,
+            key,
+            read_only: !can_push,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitLabCreateDeployKey {
+    title: String,
+    key: String,
+    can_push: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitLabVariable {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key: Option<String>,
+    value: String,
+    protected: bool,
+    masked: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitLabReleaseLink {
+    id: u32,
+    name: String,
+    url: String,
+    direct_asset_url: Option<String>,
+}
+
+impl From<GitLabReleaseLink> for ReleaseAsset {
+    fn from(link: GitLabReleaseLink) -> ReleaseAsset {
+        ReleaseAsset {
+            id: link.id.to_string(),
+            name: link.name,
+            url: link.direct_asset_url.unwrap_or(link.url),
+            size: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitLabReleaseAssets {
+    #[serde(default)]
+    links: Vec<GitLabReleaseLink>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitLabReleaseUrls {
+    #[serde(rename = "self")]
+    self_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitLabRelease {
+    tag_name: String,
+    name: Option<String>,
+    description: Option<String>,
+    #[serde(with = "time::serde::iso8601")]
+    created_at: OffsetDateTime,
+    #[serde(rename = "_links")]
+    links: GitLabReleaseUrls,
+    assets: GitLabReleaseAssets,
+}
+
+impl From<GitLabRelease> for Release {
+    fn from(release: GitLabRelease) -> Release {
+        let GitLabRelease {
+            tag_name,
+            name,
+            description,
+            created_at,
+            links,
+            assets,
+        } = release;
+        Release {
+            // Releases are identified by their tag in GitLab
+            id: tag_name.clone(),
+            name: name.filter(|n| !n.is_empty()).unwrap_or(tag_name.clone()),
+            tag: tag_name,
+            description: description.unwrap_or_default(),
+            draft: false,
+            prerelease: false,
+            created_at,
+            url: links.self_url.unwrap_or_default(),
+            assets: assets.links.into_iter().map(|l| l.into()).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitLabCreateRelease {
+    tag_name: String,
+    #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+    target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    description: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitLabUpload {
+    url: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GitLabCreateReleaseLink {
+    name: String,
+    url: String,
+    link_type: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub enum GitLabIssueState {
+    #[serde(rename = "opened")]
+    Open,
+    #[serde(rename = "closed")]
+    Closed,
+}
+
+impl From<GitLabIssueState> for IssueState {
+    fn from(state: GitLabIssueState) -> IssueState {
+        match state {
+            GitLabIssueState::Open => IssueState::Open,
+            GitLabIssueState::Closed => IssueState::Closed,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GitLabIssue {
+    pub iid: u32,
+    pub state: GitLabIssueState,
+    pub title: String,
+    pub description: Option<String>,
+    pub labels: Vec<String>,
+    pub web_url: String,
+    #[serde(with = "time::serde::iso8601")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::iso8601")]
+    pub updated_at: OffsetDateTime,
+    pub author: GitLabUser,
+}
+
+impl From<GitLabIssue> for Issue {
+    fn from(issue: GitLabIssue) -> Issue {
+        let GitLabIssue {
+            iid,
+            state,
+            title,
+            description,
+            labels,
+            web_url,
+            created_at,
+            updated_at,
+            author,
+        } = issue;
+        Issue {
+            id: iid,
+            state: state.into(),
+            title,
+            description: description.unwrap_or_default(),
+            labels,
+            url: web_url,
+            created_at,
+            updated_at,
+            author: Some(author.into()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GitLabCreateIssue {
+    pub title: String,
+    pub description: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GitLabUpdateIssue {
+    pub state_event: GitLabUpdatePullRequestStateEvent,
+}
+
+// GitLab only masks values of at least 8 characters, without spaces and most symbols
+fn can_mask(value: &str) -> bool {
+    value.len() >= 8
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_+=/@:.~-".contains(c))
+}
+
+// GitLab has a single merge method: merge commits, merge commits after a rebase or
+// fast-forward merges (rebased, without merge commits)
+fn get_merge_method(
+    allow_merge_commit: Option<bool>,
+    allow_rebase_merge: Option<bool>,
+) -> Result<Option<String>> {
+    let merge_method = match (allow_merge_commit, allow_rebase_merge) {
+        (None, None) => None,
+        (Some(true), None | Some(false)) | (None, Some(false)) => Some("merge"),
+        (Some(true) | None, Some(true)) => Some("rebase_merge"),
+        (Some(false), None | Some(true)) => Some("ff"),
+        (Some(false), Some(false)) => {
+            return Err(eyre!(
+                "GitLab needs a merge method, merge commits and rebase merges can't be both disallowed."
+            ))
+        }
+    };
+
+    Ok(merge_method.map(|method| method.to_string()))
+}
+
 #[derive(Debug)]
 pub struct GitLab {
     settings: VersionControlSettings,
@@ -362,11 +750,38 @@ impl GitLab {
     }
 
     #[instrument(skip_all)]
+    fn get_pull_request_url(&self, url: &str) -> String {
+        // In fork workflows the merge requests live on the upstream project
+        let repo = self.settings.upstream.as_ref().unwrap_or(&self.repo);
+        format!("/projects/{}{}", encode(repo), url)
+    }
+
     fn call<T: DeserializeOwned, U: Serialize + Debug>(
         &self,
         method: &str,
         url: &str,
         body: Option<U>,
+    ) -> Result<T> {
+        self.send(method, url, body, false)
+    }
+
+    // Secrets, variables and webhooks with secrets are kept out of the traces
+    fn call_secret<T: DeserializeOwned, U: Serialize + Debug>(
+        &self,
+        method: &str,
+        url: &str,
+        body: Option<U>,
+    ) -> Result<T> {
+        self.send(method, url, body, true)
+    }
+
+    #[instrument(skip_all)]
+    fn send<T: DeserializeOwned, U: Serialize + Debug>(
+        &self,
+        method: &str,
+        url: &str,
+        body: Option<U>,
+        secret: bool,
     ) -> Result<T> {
         let url = format!("https://{}/api/v4{}", self.hostname, url);
 
@@ -382,7 +797,11 @@ impl GitLab {
             .set("Authorization", &format!("Bearer {}", token))
             .set("Content-Type", "application/json");
         let result = if let Some(body) = &body {
-            trace!("Sending body: {}.", serde_json::to_string(&body)?);
+            if secret {
+                trace!("Sending body: <redacted>.");
+            } else {
+                trace!("Sending body: {}.", serde_json::to_string(&body)?);
+            }
             request.send_json(body)
         } else {
             request.call()
@@ -391,14 +810,22 @@ impl GitLab {
         match result {
             Ok(result) => {
                 let status = result.status();
-                let t = result.into_string()?;
+                let mut t = result.into_string()?;
 
                 info!(
                     "Received response with response code {} with body size {}.",
                     status,
                     t.len()
                 );
-                trace!("Response body: {t}.");
+                if !secret {
+                    trace!("Response body: {t}.");
+                }
+
+                // Somewhat hacky, if the response is empty, return null
+                if t.is_empty() {
+                    t = "null".to_string();
+                }
+
                 let t: T = serde_json::from_str(&t)?;
                 Ok(t)
             }
@@ -410,7 +837,7 @@ impl GitLab {
                     status,
                     t.len()
                 );
-                Err(eyre!("Request failed (response: {}).", t))
+                Err(RequestError { status, body: t }.into())
             }
             Err(Error::Transport(_)) => Err(eyre!("Sending data failed.")),
         }
@@ -422,73 +849,262 @@ impl GitLab {
     }
 
     #[instrument(skip(self))]
-    fn get_user_by_name(&self, username: &str) -> Result<User> {
-        let users: Vec<GitLabUser> = self.call(
-            "GET",
-            &format!("/users?username={username}"),
-            None as Option<i32>,
-        )?;
+    fn get_namespace_id(&self, path: &str) -> Result<u32> {
+        // Namespaces can be nested groups (e.g. group/subgroup/team), so query by the full path
+        let namespace: GitLabNamespace = self
+            .call(
+                "GET",
+                &format!("/namespaces/{}", encode(path)),
+                None as Option<i32>,
+            )
+            .wrap_err(eyre!("Namespace {path} not found."))?;
 
-        match users.into_iter().next() {
-            Some(user) => Ok(user.into()),
-            None => Err(eyre!("User with name {username} not found.")),
-        }
+        info!("Namespace {} has ID {}.", namespace.full_path, namespace.id);
+        Ok(namespace.id)
     }
-}
 
-impl VersionControl for GitLab {
-    #[instrument(skip_all)]
-    fn init(hostname: String, repo: String, settings: VersionControlSettings) -> Self {
-        let client = AgentBuilder::new()
-            .tls_connector(Arc::new(TlsConnector::new().unwrap()))
-            .build();
-        GitLab {
-            settings,
-            client,
-            hostname,
-            repo,
-        }
-    }
-    #[instrument(skip_all)]
-    fn login_url(&self) -> String {
-        format!(
-            "https://{}/-/profile/personal_access_tokens?name=gr&scopes=read_user,api",
-            self.hostname
-        )
-    }
-    #[instrument(skip_all)]
-    fn validate_token(&self, token: &str) -> Result<()> {
-        if !token.starts_with("glpat-") {
-            Err(eyre!("Your GitLab token has to start with 'glpat'."))
-        } else if token.len() != 26 {
-            Err(eyre!("Your GitLab token has to be 26 characters long."))
-        } else {
-            Ok(())
-        }
-    }
-    #[instrument(skip(self))]
-    fn create_pr(&self, mut pr: CreatePullRequest) -> Result<PullRequest> {
-        let reviewers = pr
-            .reviewers
-            .iter()
-            .map(|reviewer| self.get_user_by_name(reviewer))
+    #[instrument(skip(self, content))]
+    fn upload<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        content_type: &str,
+        content: &[u8],
+    ) -> Result<T> {
+        let url = format!("https://{}/api/v4{}", self.hostname, url);
+
+        info!("Uploading {} bytes to {url}.", content.len());
+
+        let token = &self.settings.auth;
+
+        trace!("Authenticating with token '{token}'.");
+
+        let result = self
+            .client
+            .request("POST", &url)
+            .set("Authorization", &format!("Bearer {}", token))
+            .set("Content-Type", content_type)
+            .send_bytes(content);
+
+        match result {
+            Ok(result) => {
+                let status = result.status();
+                let mut t = result.into_string()?;
+
+                info!(
+                    "Received response with response code {} with body size {}.",
+                    status,
+                    t.len()
+                );
+                trace!("Response body: {t}.");
+
+                // Somewhat hacky, if the response is empty, return null
+                if t.is_empty() {
+                    t = "null".to_string();
+                }
+
+                let t: T = serde_json::from_str(&t)?;
+                Ok(t)
+            }
+            Err(Error::Status(status, result)) => {
+                let t = result.into_string()?;
+
+                info!(
+                    "Received response with response code {} with body size {}.",
+                    status,
+                    t.len()
+                );
+                Err(RequestError { status, body: t }.into())
+            }
+            Err(Error::Transport(_)) => Err(eyre!("Sending data failed.")),
+        }
+    }
+
+    #[instrument(skip(self, writer))]
+    fn download(
+        &self,
+        url: &str,
+        writer: &mut dyn Write,
+        expected_size: Option<u64>,
+    ) -> Result<u64> {
+        info!("Downloading {url}.");
+
+        let token = &self.settings.auth;
+
+        trace!("Authenticating with token '{token}'.");
+
+        let result = self
+            .client
+            .get(url)
+            .set("Authorization", &format!("Bearer {}", token))
+            .call();
+
+        match result {
+            Ok(response) => save_response(response, writer, expected_size),
+            Err(Error::Status(status, result)) => {
+                let t = result.into_string()?;
+
+                info!(
+                    "Received response with response code {} with body size {}.",
+                    status,
+                    t.len()
+                );
+                Err(RequestError { status, body: t }.into())
+            }
+            Err(Error::Transport(_)) => Err(eyre!("Sending data failed.")),
+        }
+    }
+
+    fn call_paginated<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        params: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<T>> {
+        self.send_paginated(url, params, limit, false)
+    }
+
+    // Masked variables are listed with their values, keep them out of the traces
+    fn call_paginated_secret<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        params: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<T>> {
+        self.send_paginated(url, params, limit, true)
+    }
+
+    #[instrument(skip(self))]
+    fn send_paginated<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        params: &str,
+        limit: Option<usize>,
+        secret: bool,
+    ) -> Result<Vec<T>> {
+        let limit = limit.unwrap_or(usize::MAX);
+        let mut collected_values: Vec<T> = vec![];
+        let mut i = 1;
+        while collected_values.len() < limit {
+            info!("Reading page {}.", i);
+
+            let mut page: Vec<T> = self.send(
+                "GET",
+                &format!("{url}?page={i}&per_page=100{params}"),
+                None as Option<i32>,
+                secret,
+            )?;
+
+            if page.is_empty() {
+                break;
+            }
+
+            collected_values.append(&mut page);
+
+            i += 1;
+        }
+        collected_values.truncate(limit);
+        Ok(collected_values)
+    }
+
+    #[instrument(skip(self))]
+    fn get_user_by_name(&self, username: &str) -> Result<User> {
+        let users: Vec<GitLabUser> = self.call(
+            "GET",
+            &format!("/users?username={username}"),
+            None as Option<i32>,
+        )?;
+
+        match users.into_iter().next() {
+            Some(user) => Ok(user.into()),
+            None => Err(eyre!("User with name {username} not found.")),
+        }
+    }
+
+    #[instrument(skip_all)]
+    fn get_scope_url(&self, scope: &VariableScope, url: &str) -> String {
+        match scope {
+            VariableScope::Repository => self.get_repository_url(url),
+            VariableScope::Organization(group) => format!("/groups/{}{}", encode(group), url),
+        }
+    }
+}
+
+impl VersionControl for GitLab {
+    #[instrument(skip_all)]
+    fn init(hostname: String, repo: String, settings: VersionControlSettings) -> Self {
+        let client = AgentBuilder::new()
+            .tls_connector(Arc::new(TlsConnector::new().unwrap()))
+            .build();
+        GitLab {
+            settings,
+            client,
+            hostname,
+            repo,
+        }
+    }
+    #[instrument(skip_all)]
+    fn login_url(&self) -> String {
+        format!(
+            "https://{}/-/profile/personal_access_tokens?name=gr&scopes=read_user,api",
+            self.hostname
+        )
+    }
+    #[instrument(skip_all)]
+    fn validate_token(&self, token: &str) -> Result<()> {
+        if !token.starts_with("glpat-") {
+            Err(eyre!("Your GitLab token has to start with 'glpat'."))
+        } else if token.len() != 26 {
+            Err(eyre!("Your GitLab token has to be 26 characters long."))
+        } else {
+            Ok(())
+        }
+    }
+
+    #[instrument(skip_all)]
+    fn get_current_user(&self) -> Result<User> {
+        let user: GitLabUser = self.call("GET", "/user", None as Option<i32>)?;
+
+        Ok(user.into())
+    }
+
+    #[instrument(skip(self))]
+    fn requires_pushed_branch(&self) -> Result<bool> {
+        Ok(true)
+    }
+
+    #[instrument(skip(self))]
+    fn tracks_pull_requests(&self) -> Result<bool> {
+        Ok(true)
+    }
+
+    #[instrument(skip(self))]
+    fn create_pr(&self, mut pr: CreatePullRequest) -> Result<PullRequest> {
+        let reviewers = pr
+            .reviewers
+            .iter()
+            .map(|reviewer| self.get_user_by_name(reviewer))
             .collect::<Result<Vec<User>>>()?;
 
         pr.reviewers = reviewers.into_iter().map(|r| r.id).collect();
 
         pr.target = pr.target.or(self.settings.default_branch.clone());
         if pr.target.is_none() {
-            let GitLabRepository { default_branch, .. } = self.get_repository_data()?;
+            let default_branch = self.get_repository_data()?.default_branch.wrap_err(eyre!(
+                "Repository {} doesn't have a default branch.",
+                self.repo
+            ))?;
             info!("Using {default_branch} as target branch.");
             pr.target = Some(default_branch);
         }
 
         let mut gitlab_pr = GitLabCreatePullRequest::from(pr);
-        if self.settings.fork {
-            let repo = self.get_repository_data()?;
-            if let Some(forked) = repo.forked_from_project {
-                gitlab_pr.target_project_id = Some(forked.id);
-            }
+        if let Some(upstream) = &self.settings.upstream {
+            let GitLabRepository { id, .. } = self.call(
+                "GET",
+                &format!("/projects/{}", encode(upstream)),
+                None as Option<i32>,
+            )?;
+            gitlab_pr.target_project_id = Some(id);
         };
 
         let new_pr: GitLabPullRequest = self.call(
@@ -499,11 +1115,32 @@ impl VersionControl for GitLab {
 
         Ok(new_pr.into())
     }
+
+    #[instrument(skip(self))]
+    fn update_pr(&self, id: u32, pr: UpdatePullRequest) -> Result<PullRequest> {
+        let UpdatePullRequest {
+            title,
+            description,
+            target,
+        } = pr;
+        let pr: GitLabPullRequest = self.call(
+            "PUT",
+            &self.get_pull_request_url(&format!("/merge_requests/{id}")),
+            Some(GitLabUpdatePullRequest {
+                title,
+                description,
+                target_branch: target,
+                ..GitLabUpdatePullRequest::default()
+            }),
+        )?;
+
+        Ok(pr.into())
+    }
     #[instrument(skip(self))]
     fn get_pr_by_id(&self, id: u32) -> Result<PullRequest> {
         let pr: GitLabPullRequest = self.call(
             "GET",
-            &self.get_repository_url(&format!("/merge_requests/{id}")),
+            &self.get_pull_request_url(&format!("/merge_requests/{id}")),
             None as Option<i32>,
         )?;
 
@@ -513,7 +1150,7 @@ impl VersionControl for GitLab {
     fn get_pr_by_branch(&self, branch: &str) -> Result<PullRequest> {
         let prs: Vec<GitLabPullRequest> = self.call(
             "GET",
-            &self.get_repository_url(&format!("/merge_requests?source_branch={branch}")),
+            &self.get_pull_request_url(&format!("/merge_requests?source_branch={branch}")),
             None as Option<i32>,
         )?;
 
@@ -537,7 +1174,7 @@ impl VersionControl for GitLab {
         };
         let prs: Vec<GitLabPullRequest> = self.call(
             "GET",
-            &self.get_repository_url(&format!("/merge_requests{scope_param}{state_param}")),
+            &self.get_pull_request_url(&format!("/merge_requests{scope_param}{state_param}")),
             None as Option<i32>,
         )?;
 
@@ -547,7 +1184,7 @@ impl VersionControl for GitLab {
     fn approve_pr(&self, id: u32) -> Result<()> {
         let _: GitLabApproval = self.call(
             "POST",
-            &self.get_repository_url(&format!("/merge_requests/{id}/approve")),
+            &self.get_pull_request_url(&format!("/merge_requests/{id}/approve")),
             None as Option<i32>,
         )?;
 
@@ -561,7 +1198,7 @@ impl VersionControl for GitLab {
         };
         let pr: GitLabPullRequest = self.call(
             "PUT",
-            &self.get_repository_url(&format!("/merge_requests/{id}")),
+            &self.get_pull_request_url(&format!("/merge_requests/{id}")),
             Some(closing),
         )?;
 
@@ -571,7 +1208,7 @@ impl VersionControl for GitLab {
     fn merge_pr(&self, id: u32, should_remove_source_branch: bool) -> Result<PullRequest> {
         let pr: GitLabPullRequest = self.call(
             "PUT",
-            &self.get_repository_url(&format!("/merge_requests/{id}/merge")),
+            &self.get_pull_request_url(&format!("/merge_requests/{id}/merge")),
             Some(GitLabMergePullRequest {
                 should_remove_source_branch,
             }),
@@ -580,12 +1217,119 @@ impl VersionControl for GitLab {
         Ok(pr.into())
     }
 
+    #[instrument(skip(self))]
+    fn update_pr_branch(&self, id: u32, _: &str, rebase: bool) -> Result<BranchUpdate> {
+        // GitLab can only rebase the branch with the API
+        if !rebase {
+            return Ok(BranchUpdate::Unsupported);
+        }
+
+        let _: GitLabRebasePullRequest = self.call(
+            "PUT",
+            &self.get_pull_request_url(&format!("/merge_requests/{id}/rebase")),
+            None as Option<i32>,
+        )?;
+
+        Ok(BranchUpdate::Updated)
+    }
+
+    #[instrument(skip(self))]
+    fn create_issue(&self, issue: CreateIssue) -> Result<Issue> {
+        let CreateIssue { title, description } = issue;
+        let issue: GitLabIssue = self.call(
+            "POST",
+            &self.get_repository_url("/issues"),
+            Some(GitLabCreateIssue { title, description }),
+        )?;
+
+        Ok(issue.into())
+    }
+
+    #[instrument(skip(self))]
+    fn get_issue(&self, id: u32) -> Result<Issue> {
+        let issue: GitLabIssue = self.call(
+            "GET",
+            &self.get_repository_url(&format!("/issues/{id}")),
+            None as Option<i32>,
+        )?;
+
+        Ok(issue.into())
+    }
+
+    #[instrument(skip(self))]
+    fn list_issues(&self, state: IssueStateFilter) -> Result<Vec<Issue>> {
+        let params = match state {
+            IssueStateFilter::Open => "&state=opened",
+            IssueStateFilter::Closed => "&state=closed",
+            IssueStateFilter::All => "",
+        };
+        let issues: Vec<GitLabIssue> =
+            self.call_paginated(&self.get_repository_url("/issues"), params, None)?;
+
+        Ok(issues.into_iter().map(|issue| issue.into()).collect())
+    }
+
+    #[instrument(skip(self))]
+    fn close_issue(&self, id: u32) -> Result<Issue> {
+        let issue: GitLabIssue = self.call(
+            "PUT",
+            &self.get_repository_url(&format!("/issues/{id}")),
+            Some(GitLabUpdateIssue {
+                state_event: GitLabUpdatePullRequestStateEvent::Close,
+            }),
+        )?;
+
+        Ok(issue.into())
+    }
+
     #[instrument(skip_all)]
     fn get_repository(&self) -> Result<Repository> {
         let repo = self.get_repository_data()?;
 
         Ok(repo.into())
     }
+
+    #[instrument(skip(self))]
+    fn list_repositories(
+        &self,
+        owner: Option<&str>,
+        filters: ListRepositoryFilters,
+    ) -> Result<Vec<Repository>> {
+        let repos: Vec<GitLabRepository> = if let Some(owner) = owner {
+            self.call_paginated(
+                &format!("/groups/{}/projects", encode(owner)),
+                "&include_subgroups=true",
+                None,
+            )
+            .or_else(|err| {
+                // Fall back to the user, if there is no group with this name
+                if is_status(&err, 404) {
+                    self.call_paginated(&format!("/users/{owner}/projects"), "", None)
+                } else {
+                    Err(err)
+                }
+            })?
+        } else {
+            self.call_paginated("/projects", "&owned=true", None)?
+        };
+
+        Ok(repos
+            .into_iter()
+            .map(Repository::from)
+            .filter(|repo| filters.matches(repo))
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    fn search_repositories(&self, query: &str, limit: usize) -> Result<Vec<Repository>> {
+        let repos: Vec<GitLabRepository> = self.call_paginated(
+            "/projects",
+            &format!("&search={}", encode(query)),
+            Some(limit),
+        )?;
+
+        Ok(repos.into_iter().map(|repo| repo.into()).collect())
+    }
     #[instrument(skip_all)]
     fn create_repository(&self, repo: CreateRepository) -> Result<Repository> {
         let CreateRepository {
@@ -599,15 +1343,9 @@ impl VersionControl for GitLab {
             license: _,
         } = repo;
 
-        let namespace_id = organization.and_then(|org| {
-            self.call::<GitLabNamespace, Option<u32>>(
-                "POST",
-                &format!("/namespaces?search={org}"),
-                None,
-            )
-            .map(|ns| ns.id)
-            .ok()
-        });
+        let namespace_id = organization
+            .map(|org| self.get_namespace_id(&org))
+            .transpose()?;
 
         let create_repo = GitLabCreateRepository {
             path: name.clone(),
@@ -629,29 +1367,97 @@ impl VersionControl for GitLab {
     }
     #[instrument(skip_all)]
     fn fork_repository(&self, repo: ForkRepository) -> Result<Repository> {
-        let ForkRepository {
-            name,
-            organization: namespace_path,
-        } = repo;
-        let path = match (&namespace_path, &name) {
-            (Some(ns), Some(n)) => Some(format!("{ns}/{n}")),
-            (None, Some(n)) => Some(n.to_string()),
-            _ => None,
-        };
+        let ForkRepository { name, organization } = repo;
+        let namespace_id = organization
+            .map(|org| self.get_namespace_id(&org))
+            .transpose()?;
 
         let new_repo: GitLabRepository = self.call(
             "POST",
             &self.get_repository_url("/fork"),
             Some(GitLabForkRepository {
+                path: name.clone(),
                 name,
-                namespace_path,
-                path,
+                namespace_id,
             }),
         )?;
 
         Ok(new_repo.into())
     }
 
+    #[instrument(skip_all)]
+    fn update_repository(&self, repo: UpdateRepository) -> Result<Repository> {
+        let UpdateRepository {
+            name,
+            description,
+            visibility,
+            default_branch,
+            homepage,
+            topics,
+            archived,
+            allow_merge_commit,
+            allow_squash_merge,
+            allow_rebase_merge,
+        } = repo;
+        if homepage.is_some() {
+            return Err(eyre!("Setting the homepage is not supported by GitLab."));
+        }
+
+        let merge_method = get_merge_method(allow_merge_commit, allow_rebase_merge)?;
+        let update = GitLabUpdateRepository {
+            path: name.clone(),
+            name,
+            description,
+            visibility: visibility.map(|visibility| match visibility {
+                RepositoryVisibility::Public => GitLabRepositoryVisibility::Public,
+                RepositoryVisibility::Internal => GitLabRepositoryVisibility::Internal,
+                RepositoryVisibility::Private => GitLabRepositoryVisibility::Private,
+            }),
+            default_branch,
+            topics,
+            merge_method,
+            squash_option: allow_squash_merge
+                .map(|allow| if allow { "default_off" } else { "never" }.to_string()),
+        };
+
+        // GitLab fails if there is nothing to update (e.g. when only archiving)
+        let repo: GitLabRepository = if update != GitLabUpdateRepository::default() {
+            self.call("PUT", &self.get_repository_url(""), Some(update))?
+        } else {
+            self.get_repository_data()?
+        };
+
+        // Archiving is a separate endpoint, use the id in case the project was renamed
+        let repo = match archived {
+            Some(true) => self.call(
+                "POST",
+                &format!("/projects/{}/archive", repo.id),
+                None as Option<i32>,
+            )?,
+            Some(false) => self.call(
+                "POST",
+                &format!("/projects/{}/unarchive", repo.id),
+                None as Option<i32>,
+            )?,
+            None => repo,
+        };
+
+        Ok(repo.into())
+    }
+
+    #[instrument(skip_all)]
+    fn transfer_repository(&self, owner: &str) -> Result<Repository> {
+        let repo: GitLabRepository = self.call(
+            "PUT",
+            &self.get_repository_url("/transfer"),
+            Some(GitLabTransferRepository {
+                namespace: owner.to_string(),
+            }),
+        )?;
+
+        Ok(repo.into())
+    }
+
     #[instrument(skip_all)]
     fn delete_repository(&self) -> Result<()> {
         let _: GitLabRepositoryDeleted =
@@ -659,4 +1465,500 @@ impl VersionControl for GitLab {
 
         Ok(())
     }
+
+    #[instrument(skip_all)]
+    fn sync_fork(&self, _: &str) -> Result<ForkSync> {
+        // GitLab has no API to sync forks, fetch and push the upstream branch locally
+        Ok(ForkSync::Unsupported)
+    }
+
+    #[instrument(skip(self, writer))]
+    fn download_archive(
+        &self,
+        reference: &str,
+        format: ArchiveFormat,
+        writer: &mut dyn Write,
+    ) -> Result<u64> {
+        self.download(
+            &format!(
+                "https://{}/api/v4{}",
+                self.hostname,
+                self.get_repository_url(&format!(
+                    "/repository/archive.{}?sha={}",
+                    format.extension(),
+                    encode(reference)
+                ))
+            ),
+            writer,
+            None,
+        )
+    }
+
+    #[instrument(skip_all)]
+    fn list_deploy_keys(&self) -> Result<Vec<DeployKey>> {
+        let keys: Vec<GitLabDeployKey> =
+            self.call_paginated(&self.get_repository_url("/deploy_keys"), "", None)?;
+
+        Ok(keys.into_iter().map(|k| k.into()).collect())
+    }
+
+    #[instrument(skip_all)]
+    fn add_deploy_key(&self, key: CreateDeployKey) -> Result<DeployKey> {
+        let CreateDeployKey {
+            title,
+            key,
+            read_only,
+        } = key;
+        let key: GitLabDeployKey = self.call(
+            "POST",
+            &self.get_repository_url("/deploy_keys"),
+            Some(GitLabCreateDeployKey {
+                title,
+                key,
+                can_push: !read_only,
+            }),
+        )?;
+
+        Ok(key.into())
+    }
+
+    #[instrument(skip_all)]
+    fn remove_deploy_key(&self, id: &str) -> Result<()> {
+        self.call::<(), i32>(
+            "DELETE",
+            &self.get_repository_url(&format!("/deploy_keys/{id}")),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn list_branch_protections(&self) -> Result<Vec<BranchProtection>> {
+        let branches: Vec<GitLabProtectedBranch> =
+            self.call_paginated(&self.get_repository_url("/protected_branches"), "", None)?;
+
+        Ok(branches.into_iter().map(|b| b.into()).collect())
+    }
+
+    #[instrument(skip_all)]
+    fn protect_branch(&self, protection: BranchProtection) -> Result<BranchProtection> {
+        let BranchProtection {
+            branch,
+            required_approvals,
+            required_status_checks,
+            allow_force_push,
+            linear_history,
+        } = protection;
+        // These are project-wide settings or approval rules in GitLab, not part of protected branches
+        if required_approvals > 0 {
+            return Err(eyre!(
+                "Required approvals on protected branches are not supported by GitLab."
+            ));
+        }
+        if !required_status_checks.is_empty() {
+            return Err(eyre!(
+                "Required status checks on protected branches are not supported by GitLab."
+            ));
+        }
+        if linear_history {
+            return Err(eyre!(
+                "Requiring linear history on protected branches is not supported by GitLab."
+            ));
+        }
+
+        // Update the protection if it exists already, otherwise create it
+        let url = self.get_repository_url(&format!("/protected_branches/{}", encode(&branch)));
+        let exists = found(self.call::<GitLabProtectedBranch, i32>("GET", &url, None))?.is_some();
+        let protected: GitLabProtectedBranch = if exists {
+            self.call(
+                "PATCH",
+                &url,
+                Some(GitLabUpdateProtectedBranch {
+                    name: None,
+                    allow_force_push,
+                }),
+            )?
+        } else {
+            self.call(
+                "POST",
+                &self.get_repository_url("/protected_branches"),
+                Some(GitLabUpdateProtectedBranch {
+                    name: Some(branch),
+                    allow_force_push,
+                }),
+            )?
+        };
+
+        Ok(protected.into())
+    }
+
+    #[instrument(skip_all)]
+    fn list_collaborators(&self) -> Result<Vec<Collaborator>> {
+        let members: Vec<GitLabMember> =
+            self.call_paginated(&self.get_repository_url("/members"), "", None)?;
+
+        Ok(members.into_iter().map(|m| m.into()).collect())
+    }
+
+    #[instrument(skip_all)]
+    fn add_collaborator(&self, username: &str, permission: CollaboratorPermission) -> Result<()> {
+        let user_id = self.get_user_by_name(username)?.id;
+        let access_level = match permission {
+            CollaboratorPermission::Read => 20,
+            CollaboratorPermission::Write => 30,
+            CollaboratorPermission::Admin => 40,
+        };
+
+        // Update the member if it exists already, otherwise add it
+        let url = self.get_repository_url(&format!("/members/{user_id}"));
+        let exists = found(self.call::<GitLabMember, i32>("GET", &url, None))?.is_some();
+        let _: GitLabMember = if exists {
+            self.call(
+                "PUT",
+                &url,
+                Some(GitLabAddMember {
+                    user_id: None,
+                    access_level,
+                }),
+            )?
+        } else {
+            self.call(
+                "POST",
+                &self.get_repository_url("/members"),
+                Some(GitLabAddMember {
+                    user_id: Some(user_id),
+                    access_level,
+                }),
+            )?
+        };
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn remove_collaborator(&self, username: &str) -> Result<()> {
+        let user_id = self.get_user_by_name(username)?.id;
+        self.call::<(), i32>(
+            "DELETE",
+            &self.get_repository_url(&format!("/members/{user_id}")),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn list_webhooks(&self) -> Result<Vec<Webhook>> {
+        let webhooks: Vec<GitLabWebhook> =
+            self.call_paginated(&self.get_repository_url("/hooks"), "", None)?;
+
+        Ok(webhooks.into_iter().map(|w| w.into()).collect())
+    }
+
+    #[instrument(skip_all)]
+    fn create_webhook(&self, webhook: CreateWebhook) -> Result<Webhook> {
+        let CreateWebhook {
+            url,
+            secret,
+            content_type,
+            events,
+        } = webhook;
+        if content_type == WebhookContentType::Form {
+            return Err(eyre!(
+                "Sending webhooks as forms is not supported by GitLab."
+            ));
+        }
+
+        let webhook: GitLabWebhook = self.call_secret(
+            "POST",
+            &self.get_repository_url("/hooks"),
+            Some(GitLabCreateWebhook {
+                url,
+                token: secret,
+                push_events: events.contains(&WebhookEvent::Push),
+                merge_requests_events: events.contains(&WebhookEvent::PullRequest),
+                tag_push_events: events.contains(&WebhookEvent::Tag),
+                releases_events: events.contains(&WebhookEvent::Release),
+                issues_events: events.contains(&WebhookEvent::Issue),
+            }),
+        )?;
+
+        Ok(webhook.into())
+    }
+
+    #[instrument(skip_all)]
+    fn delete_webhook(&self, id: &str) -> Result<()> {
+        self.call::<(), i32>(
+            "DELETE",
+            &self.get_repository_url(&format!("/hooks/{id}")),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn test_webhook(&self, id: &str) -> Result<()> {
+        let webhook: Webhook = self
+            .call::<GitLabWebhook, i32>(
+                "GET",
+                &self.get_repository_url(&format!("/hooks/{id}")),
+                None,
+            )?
+            .into();
+
+        // GitLab tests a specific event, use the first one that the webhook listens to
+        let trigger = match webhook.events.first() {
+            Some(WebhookEvent::Push) | None => "push_events",
+            Some(WebhookEvent::PullRequest) => "merge_requests_events",
+            Some(WebhookEvent::Tag) => "tag_push_events",
+            Some(WebhookEvent::Release) => "releases_events",
+            Some(WebhookEvent::Issue) => "issues_events",
+        };
+        let _: GitLabMessage = self.call(
+            "POST",
+            &self.get_repository_url(&format!("/hooks/{id}/test/{trigger}")),
+            None as Option<i32>,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn list_secrets(&self, scope: &VariableScope) -> Result<Vec<Secret>> {
+        // GitLab has no separate secrets, these are the masked variables
+        let variables = self.list_variables(scope)?;
+
+        Ok(variables
+            .into_iter()
+            .filter(|v| v.masked)
+            .map(|v| Secret { name: v.name })
+            .collect())
+    }
+
+    #[instrument(skip_all)]
+    fn set_secret(&self, scope: &VariableScope, name: &str, value: &str) -> Result<()> {
+        self.set_variable(
+            scope,
+            Variable {
+                name: name.to_string(),
+                value: value.to_string(),
+                protected: false,
+                masked: true,
+            },
+        )
+    }
+
+    #[instrument(skip_all)]
+    fn delete_secret(&self, scope: &VariableScope, name: &str) -> Result<()> {
+        self.delete_variable(scope, name)
+    }
+
+    #[instrument(skip_all)]
+    fn list_variables(&self, scope: &VariableScope) -> Result<Vec<Variable>> {
+        let variables: Vec<GitLabVariable> =
+            self.call_paginated_secret(&self.get_scope_url(scope, "/variables"), "", None)?;
+
+        Ok(variables
+            .into_iter()
+            .map(|v| Variable {
+                name: v.key.unwrap_or_default(),
+                value: v.value,
+                protected: v.protected,
+                masked: v.masked,
+            })
+            .collect())
+    }
+
+    #[instrument(skip_all)]
+    fn set_variable(&self, scope: &VariableScope, variable: Variable) -> Result<()> {
+        let Variable {
+            name,
+            value,
+            protected,
+            masked,
+        } = variable;
+
+        if masked && !can_mask(&value) {
+            return Err(eyre!(
+                "GitLab can only mask values of at least 8 characters, made of letters, digits and _+=/@:.~-, {name} is not set."
+            ));
+        }
+
+        // Update the variable if it exists already, otherwise create it
+        let url = self.get_scope_url(scope, &format!("/variables/{}", encode(&name)));
+        let exists = found(self.call_secret::<GitLabVariable, i32>("GET", &url, None))?.is_some();
+        let _: GitLabVariable = if exists {
+            self.call_secret(
+                "PUT",
+                &url,
+                Some(GitLabVariable {
+                    key: None,
+                    value,
+                    protected,
+                    masked,
+                }),
+            )?
+        } else {
+            self.call_secret(
+                "POST",
+                &self.get_scope_url(scope, "/variables"),
+                Some(GitLabVariable {
+                    key: Some(name),
+                    value,
+                    protected,
+                    masked,
+                }),
+            )?
+        };
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn delete_variable(&self, scope: &VariableScope, name: &str) -> Result<()> {
+        self.call::<(), i32>(
+            "DELETE",
+            &self.get_scope_url(scope, &format!("/variables/{}", encode(name))),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn create_release(&self, release: CreateRelease) -> Result<Release> {
+        let CreateRelease {
+            tag,
+            target,
+            name,
+            description,
+            draft,
+            prerelease,
+        } = release;
+        if draft {
+            return Err(eyre!("Draft releases are not supported by GitLab."));
+        }
+        if prerelease {
+            return Err(eyre!("Marking prereleases is not supported by GitLab."));
+        }
+
+        let release: GitLabRelease = self.call(
+            "POST",
+            &self.get_repository_url("/releases"),
+            Some(GitLabCreateRelease {
+                tag_name: tag,
+                target,
+                name,
+                description,
+            }),
+        )?;
+
+        Ok(release.into())
+    }
+
+    #[instrument(skip_all)]
+    fn get_release(&self, tag: &str) -> Result<Release> {
+        let release: GitLabRelease = self.call(
+            "GET",
+            &self.get_repository_url(&format!("/releases/{}", encode(tag))),
+            None as Option<i32>,
+        )?;
+
+        Ok(release.into())
+    }
+
+    #[instrument(skip_all)]
+    fn list_releases(&self) -> Result<Vec<Release>> {
+        let releases: Vec<GitLabRelease> =
+            self.call_paginated(&self.get_repository_url("/releases"), "", None)?;
+
+        Ok(releases.into_iter().map(|r| r.into()).collect())
+    }
+
+    #[instrument(skip_all)]
+    fn delete_release(&self, tag: &str) -> Result<()> {
+        self.call::<GitLabRelease, i32>(
+            "DELETE",
+            &self.get_repository_url(&format!("/releases/{}", encode(tag))),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self, content))]
+    fn upload_release_asset(&self, tag: &str, name: &str, content: &[u8]) -> Result<ReleaseAsset> {
+        // Files are uploaded to the project first, then linked to the release
+        let (content_type, body) = multipart_file("file", name, content);
+        let upload: GitLabUpload =
+            self.upload(&self.get_repository_url("/uploads"), &content_type, &body)?;
+
+        let link: GitLabReleaseLink = self.call(
+            "POST",
+            &self.get_repository_url(&format!("/releases/{}/assets/links", encode(tag))),
+            Some(GitLabCreateReleaseLink {
+                name: name.to_string(),
+                url: format!("https://{}/{}{}", self.hostname, self.repo, upload.url),
+                link_type: "other".to_string(),
+            }),
+        )?;
+
+        Ok(ReleaseAsset {
+            size: Some(content.len() as u64),
+            ..link.into()
+        })
+    }
+
+    #[instrument(skip(self, writer))]
+    fn download_release_asset(&self, asset: &ReleaseAsset, writer: &mut dyn Write) -> Result<u64> {
+        self.download(&asset.url, writer, asset.size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{can_mask, get_merge_method};
+
+    #[test]
+    fn checks_maskable_values() {
+        assert!(can_mask("glpat-0123456789"));
+        assert!(can_mask("dXNlcjpwYXNz/w=="));
+        assert!(!can_mask("short"));
+        assert!(!can_mask("with spaces"));
+        assert!(!can_mask("quote\"s and $igns"));
+    }
+
+    #[test]
+    fn maps_merge_methods() {
+        assert_eq!(get_merge_method(None, None).unwrap(), None);
+        assert_eq!(
+            get_merge_method(Some(true), None).unwrap().as_deref(),
+            Some("merge")
+        );
+        assert_eq!(
+            get_merge_method(None, Some(false)).unwrap().as_deref(),
+            Some("merge")
+        );
+        assert_eq!(
+            get_merge_method(Some(true), Some(true)).unwrap().as_deref(),
+            Some("rebase_merge")
+        );
+        assert_eq!(
+            get_merge_method(None, Some(true)).unwrap().as_deref(),
+            Some("rebase_merge")
+        );
+        assert_eq!(
+            get_merge_method(Some(false), Some(true))
+                .unwrap()
+                .as_deref(),
+            Some("ff")
+        );
+        assert_eq!(
+            get_merge_method(Some(false), None).unwrap().as_deref(),
+            Some("ff")
+        );
+        assert!(get_merge_method(Some(false), Some(false)).is_err());
+    }
 }
@@ -0,0 +1,719 @@
+// Documentation: https://github.com/gogs/docs-api
+use super::common::{
+    is_status, ArchiveFormat, BranchProtection, BranchUpdate, Collaborator, CollaboratorPermission,
+    CreateDeployKey, CreateIssue, CreatePullRequest, CreateRelease, CreateRepository,
+    CreateWebhook, DeployKey, ForkRepository, ForkSync, ForkedFromRepository, Issue, IssueState,
+    IssueStateFilter, ListPullRequestFilters, ListRepositoryFilters, PullRequest, Release,
+    ReleaseAsset, Repository, RepositoryVisibility, RequestError, Secret, UpdatePullRequest,
+    UpdateRepository, User, Variable, VariableScope, VersionControl, VersionControlSettings,
+    Webhook,
+};
+use eyre::{eyre, Result};
+use native_tls::TlsConnector;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{cmp::Reverse, fmt::Debug, io::Write, sync::Arc};
+use time::OffsetDateTime;
+use tracing::{info, instrument, trace};
+use ureq::{Agent, AgentBuilder, Error};
+use urlencoding::encode;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GogsUser {
+    pub id: u32,
+    // Older versions of Gogs call this field login
+    #[serde(alias = "login")]
+    pub username: String,
+}
+
+impl From<GogsUser> for User {
+    fn from(user: GogsUser) -> User {
+        let GogsUser { id, username } = user;
+        User {
+            id: id.to_string(),
+            username,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GogsRepository {
+    name: String,
+    full_name: String,
+    private: bool,
+    owner: GogsUser,
+    html_url: String,
+    ssh_url: String,
+    clone_url: String,
+    description: Option<String>,
+    #[serde(with = "time::serde::iso8601")]
+    created_at: OffsetDateTime,
+    #[serde(with = "time::serde::iso8601")]
+    updated_at: OffsetDateTime,
+    default_branch: String,
+    stars_count: u32,
+    forks_count: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent: Option<Box<GogsRepository>>,
+}
+
+impl From<GogsRepository> for Repository {
+    fn from(repo: GogsRepository) -> Repository {
+        let GogsRepository {
+            name,
+            full_name,
+            private,
+            owner,
+            html_url,
+            ssh_url,
+            clone_url,
+            description,
+            created_at,
+            updated_at,
+            default_branch,
+            stars_count,
+            forks_count,
+            parent,
+        } = repo;
+        Repository {
+            name,
+            full_name,
+            owner: Some(owner.into()),
+            visibility: if private {
+                RepositoryVisibility::Private
+            } else {
+                RepositoryVisibility::Public
+            },
+            html_url,
+            ssh_url,
+            https_url: clone_url,
+            description: description.unwrap_or_default(),
+            created_at,
+            updated_at,
+            // Gogs doesn't support archiving repositories
+            archived: false,
+            default_branch,
+            stars_count,
+            forks_count,
+            forked_from: parent.map(|r| ForkedFromRepository::from(*r)),
+        }
+    }
+}
+
+impl From<GogsRepository> for ForkedFromRepository {
+    fn from(repo: GogsRepository) -> ForkedFromRepository {
+        let GogsRepository {
+            name,
+            full_name,
+            html_url,
+            ..
+        } = repo;
+        ForkedFromRepository {
+            name,
+            full_name,
+            html_url,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GogsSearchRepositories {
+    data: Vec<GogsRepository>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+struct GogsCreateRepository {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    private: bool,
+    auto_init: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    license: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gitignores: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    readme: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GogsForkRepository {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    organization: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub enum GogsIssueState {
+    #[serde(rename = "open")]
+    Open,
+    #[serde(rename = "closed")]
+    Closed,
+}
+
+impl From<GogsIssueState> for IssueState {
+    fn from(state: GogsIssueState) -> IssueState {
+        match state {
+            GogsIssueState::Open => IssueState::Open,
+            GogsIssueState::Closed => IssueState::Closed,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GogsLabel {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GogsIssue {
+    pub number: u32,
+    pub state: GogsIssueState,
+    pub title: String,
+    pub body: String,
+    pub labels: Vec<GogsLabel>,
+    #[serde(with = "time::serde::iso8601")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::iso8601")]
+    pub updated_at: OffsetDateTime,
+    pub user: GogsUser,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GogsCreateIssue {
+    title: String,
+    body: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GogsUpdateIssue {
+    state: GogsIssueState,
+}
+
+#[derive(Debug)]
+pub struct Gogs {
+    hostname: String,
+    settings: VersionControlSettings,
+    client: Agent,
+    repo: String,
+}
+
+impl Gogs {
+    #[instrument(skip_all)]
+    fn get_repository_url(&self, url: &str) -> String {
+        format!("/repos/{}{}", self.repo, url)
+    }
+
+    // Gogs doesn't return the URL of issues, build it from the repository
+    #[instrument(skip_all)]
+    fn get_issue_from_gogs(&self, issue: GogsIssue) -> Issue {
+        let GogsIssue {
+            number,
+            state,
+            title,
+            body,
+            labels,
+            created_at,
+            updated_at,
+            user,
+        } = issue;
+        Issue {
+            id: number,
+            state: state.into(),
+            title,
+            description: body,
+            labels: labels.into_iter().map(|l| l.name).collect(),
+            url: format!("https://{}/{}/issues/{number}", self.hostname, self.repo),
+            created_at,
+            updated_at,
+            author: Some(user.into()),
+        }
+    }
+
+    #[instrument(skip_all)]
+    fn call<T: DeserializeOwned, U: Serialize + Debug>(
+        &self,
+        method: &str,
+        url: &str,
+        body: Option<U>,
+    ) -> Result<T> {
+        let url = format!("https://{}/api/v1{}", self.hostname, url);
+
+        info!("Calling with {method} on {url}.");
+
+        let token = &self.settings.auth;
+
+        trace!("Authenticating with token '{token}'.");
+
+        let request = self
+            .client
+            .request(method, &url)
+            .set("Authorization", &format!("token {token}"))
+            .set("Content-Type", "application/json");
+        let result = if let Some(body) = &body {
+            trace!("Sending body: {}.", serde_json::to_string(&body)?);
+            request.send_json(body)
+        } else {
+            request.call()
+        };
+
+        match result {
+            Ok(result) => {
+                let status = result.status();
+                let mut t = result.into_string()?;
+
+                info!(
+                    "Received response with response code {} with body size {}.",
+                    status,
+                    t.len()
+                );
+                trace!("Response body: {t}.");
+
+                // Somewhat hacky, if the response is empty, return null
+                if t.is_empty() {
+                    t = "null".to_string();
+                }
+
+                let t: T = serde_json::from_str(&t)?;
+                Ok(t)
+            }
+            Err(Error::Status(status, result)) => {
+                let t = result.into_string()?;
+
+                info!(
+                    "Received response with response code {} with body size {}.",
+                    status,
+                    t.len()
+                );
+                Err(RequestError { status, body: t }.into())
+            }
+            Err(Error::Transport(_)) => Err(eyre!("Sending data failed.")),
+        }
+    }
+}
+
+impl VersionControl for Gogs {
+    #[instrument(skip_all)]
+    fn init(hostname: String, repo: String, settings: VersionControlSettings) -> Self {
+        let client = AgentBuilder::new()
+            .tls_connector(Arc::new(TlsConnector::new().unwrap()))
+            .build();
+        Gogs {
+            hostname,
+            settings,
+            client,
+            repo,
+        }
+    }
+
+    #[instrument(skip_all)]
+    fn login_url(&self) -> String {
+        format!("https://{}/user/settings/applications", self.hostname)
+    }
+
+    #[instrument(skip_all)]
+    fn validate_token(&self, token: &str) -> Result<()> {
+        if token.len() != 40 {
+            Err(eyre!("Your Gogs token has to be 40 characters long."))
+        } else {
+            Ok(())
+        }
+    }
+
+    #[instrument(skip_all)]
+    fn get_current_user(&self) -> Result<User> {
+        let user: GogsUser = self.call("GET", "/user", None as Option<i32>)?;
+
+        Ok(user.into())
+    }
+
+    #[instrument(skip(self))]
+    fn requires_pushed_branch(&self) -> Result<bool> {
+        // Pull requests are rejected on create, there is nothing to push for
+        Ok(false)
+    }
+
+    #[instrument(skip(self))]
+    fn tracks_pull_requests(&self) -> Result<bool> {
+        Ok(false)
+    }
+
+    #[instrument(skip(self))]
+    fn create_pr(&self, _: CreatePullRequest) -> Result<PullRequest> {
+        Err(eyre!("Creating pull requests is not supported by Gogs."))
+    }
+
+    #[instrument(skip(self))]
+    fn update_pr(&self, _: u32, _: UpdatePullRequest) -> Result<PullRequest> {
+        Err(eyre!("Updating pull requests is not supported by Gogs."))
+    }
+
+    #[instrument(skip(self))]
+    fn get_pr_by_id(&self, _: u32) -> Result<PullRequest> {
+        Err(eyre!("Getting pull requests is not supported by Gogs."))
+    }
+
+    #[instrument(skip(self))]
+    fn get_pr_by_branch(&self, _: &str) -> Result<PullRequest> {
+        Err(eyre!("Getting pull requests is not supported by Gogs."))
+    }
+
+    #[instrument(skip(self))]
+    fn list_prs(&self, _: ListPullRequestFilters) -> Result<Vec<PullRequest>> {
+        Err(eyre!("Listing pull requests is not supported by Gogs."))
+    }
+
+    #[instrument(skip(self))]
+    fn approve_pr(&self, _: u32) -> Result<()> {
+        Err(eyre!("Approving pull requests is not supported by Gogs."))
+    }
+
+    #[instrument(skip(self))]
+    fn close_pr(&self, _: u32) -> Result<PullRequest> {
+        Err(eyre!("Closing pull requests is not supported by Gogs."))
+    }
+
+    #[instrument(skip(self))]
+    fn merge_pr(&self, _: u32, _: bool) -> Result<PullRequest> {
+        Err(eyre!("Merging pull requests is not supported by Gogs."))
+    }
+
+    #[instrument(skip(self))]
+    fn update_pr_branch(&self, _: u32, _: &str, _: bool) -> Result<BranchUpdate> {
+        Err(eyre!("Updating pull requests is not supported by Gogs."))
+    }
+
+    #[instrument(skip(self))]
+    fn create_issue(&self, issue: CreateIssue) -> Result<Issue> {
+        let CreateIssue { title, description } = issue;
+        let issue: GogsIssue = self.call(
+            "POST",
+            &self.get_repository_url("/issues"),
+            Some(GogsCreateIssue {
+                title,
+                body: description,
+            }),
+        )?;
+
+        Ok(self.get_issue_from_gogs(issue))
+    }
+
+    #[instrument(skip(self))]
+    fn get_issue(&self, id: u32) -> Result<Issue> {
+        let issue: GogsIssue = self.call(
+            "GET",
+            &self.get_repository_url(&format!("/issues/{id}")),
+            None as Option<i32>,
+        )?;
+
+        Ok(self.get_issue_from_gogs(issue))
+    }
+
+    #[instrument(skip(self))]
+    fn list_issues(&self, state: IssueStateFilter) -> Result<Vec<Issue>> {
+        // Gogs can only filter by one state at a time
+        let states = match state {
+            IssueStateFilter::Open => vec!["open"],
+            IssueStateFilter::Closed => vec!["closed"],
+            IssueStateFilter::All => vec!["open", "closed"],
+        };
+        let mut issues: Vec<GogsIssue> = vec![];
+        for state in states {
+            let mut i = 1;
+            loop {
+                info!("Reading page {}.", i);
+
+                let mut page: Vec<GogsIssue> = self.call(
+                    "GET",
+                    &self.get_repository_url(&format!("/issues?state={state}&page={i}")),
+                    None as Option<i32>,
+                )?;
+
+                if page.is_empty() {
+                    break;
+                }
+
+                issues.append(&mut page);
+
+                i += 1;
+            }
+        }
+        issues.sort_by_key(|issue| Reverse(issue.number));
+
+        Ok(issues
+            .into_iter()
+            .map(|issue| self.get_issue_from_gogs(issue))
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    fn close_issue(&self, id: u32) -> Result<Issue> {
+        let issue: GogsIssue = self.call(
+            "PATCH",
+            &self.get_repository_url(&format!("/issues/{id}")),
+            Some(GogsUpdateIssue {
+                state: GogsIssueState::Closed,
+            }),
+        )?;
+
+        Ok(self.get_issue_from_gogs(issue))
+    }
+
+    #[instrument(skip_all)]
+    fn get_repository(&self) -> Result<Repository> {
+        let repo: GogsRepository =
+            self.call("GET", &self.get_repository_url(""), None as Option<i32>)?;
+
+        Ok(repo.into())
+    }
+
+    #[instrument(skip(self))]
+    fn list_repositories(
+        &self,
+        owner: Option<&str>,
+        filters: ListRepositoryFilters,
+    ) -> Result<Vec<Repository>> {
+        // Gogs returns every repository at once, these endpoints are not paginated
+        let repos: Vec<GogsRepository> = if let Some(owner) = owner {
+            self.call("GET", &format!("/orgs/{owner}/repos"), None as Option<i32>)
+                .or_else(|err| {
+                    // Fall back to the user, if there is no organization with this name
+                    if is_status(&err, 404) {
+                        self.call("GET", &format!("/users/{owner}/repos"), None as Option<i32>)
+                    } else {
+                        Err(err)
+                    }
+                })?
+        } else {
+            self.call("GET", "/user/repos", None as Option<i32>)?
+        };
+
+        Ok(repos
+            .into_iter()
+            .map(Repository::from)
+            .filter(|repo| filters.matches(repo))
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    fn search_repositories(&self, query: &str, limit: usize) -> Result<Vec<Repository>> {
+        let mut repos: Vec<Repository> = vec![];
+        let mut i = 1;
+        while repos.len() < limit {
+            info!("Reading page {}.", i);
+
+            let page: GogsSearchRepositories = self.call(
+                "GET",
+                &format!("/repos/search?q={}&page={i}&limit=50", encode(query)),
+                None as Option<i32>,
+            )?;
+
+            if page.data.is_empty() {
+                break;
+            }
+
+            repos.extend(page.data.into_iter().map(Repository::from));
+
+            i += 1;
+        }
+        repos.truncate(limit);
+
+        Ok(repos)
+    }
+
+    #[instrument(skip_all)]
+    fn create_repository(&self, repo: CreateRepository) -> Result<Repository> {
+        let CreateRepository {
+            name,
+            description,
+            visibility,
+            organization,
+            init,
+            default_branch: _,
+            gitignore,
+            license,
+        } = repo;
+        let create_repo = GogsCreateRepository {
+            name,
+            description,
+            private: visibility != RepositoryVisibility::Public,
+            auto_init: init,
+            gitignores: gitignore,
+            license,
+            readme: if init {
+                Some("Default".to_string())
+            } else {
+                None
+            },
+        };
+        // Gogs uses the singular /org prefix, unlike Gitea
+        let new_repo: GogsRepository = if let Some(org) = organization {
+            self.call("POST", &format!("/org/{org}/repos"), Some(create_repo))
+        } else {
+            self.call("POST", "/user/repos", Some(create_repo))
+        }?;
+
+        Ok(new_repo.into())
+    }
+
+    #[instrument(skip_all)]
+    fn fork_repository(&self, repo: ForkRepository) -> Result<Repository> {
+        let ForkRepository { name, organization } = repo;
+
+        let new_repo: GogsRepository = self.call(
+            "POST",
+            &self.get_repository_url("/forks"),
+            Some(GogsForkRepository { name, organization }),
+        )?;
+
+        Ok(new_repo.into())
+    }
+
+    #[instrument(skip_all)]
+    fn update_repository(&self, _: UpdateRepository) -> Result<Repository> {
+        Err(eyre!("Updating repositories is not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn transfer_repository(&self, _: &str) -> Result<Repository> {
+        Err(eyre!("Transferring repositories is not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn delete_repository(&self) -> Result<()> {
+        self.call::<(), _>("DELETE", &self.get_repository_url(""), None as Option<i32>)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn sync_fork(&self, _: &str) -> Result<ForkSync> {
+        // Gogs has no API to sync forks, fetch and push the upstream branch locally
+        Ok(ForkSync::Unsupported)
+    }
+
+    #[instrument(skip_all)]
+    fn download_archive(&self, _: &str, _: ArchiveFormat, _: &mut dyn Write) -> Result<u64> {
+        Err(eyre!("Downloading archives is not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_deploy_keys(&self) -> Result<Vec<DeployKey>> {
+        Err(eyre!("Deploy keys are not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn add_deploy_key(&self, _: CreateDeployKey) -> Result<DeployKey> {
+        Err(eyre!("Deploy keys are not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn remove_deploy_key(&self, _: &str) -> Result<()> {
+        Err(eyre!("Deploy keys are not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_branch_protections(&self) -> Result<Vec<BranchProtection>> {
+        Err(eyre!("Branch protections are not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn protect_branch(&self, _: BranchProtection) -> Result<BranchProtection> {
+        Err(eyre!("Branch protections are not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_collaborators(&self) -> Result<Vec<Collaborator>> {
+        Err(eyre!("Managing collaborators is not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn add_collaborator(&self, _: &str, _: CollaboratorPermission) -> Result<()> {
+        Err(eyre!("Managing collaborators is not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn remove_collaborator(&self, _: &str) -> Result<()> {
+        Err(eyre!("Managing collaborators is not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_webhooks(&self) -> Result<Vec<Webhook>> {
+        Err(eyre!("Managing webhooks is not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn create_webhook(&self, _: CreateWebhook) -> Result<Webhook> {
+        Err(eyre!("Managing webhooks is not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn delete_webhook(&self, _: &str) -> Result<()> {
+        Err(eyre!("Managing webhooks is not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn test_webhook(&self, _: &str) -> Result<()> {
+        Err(eyre!("Managing webhooks is not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_secrets(&self, _: &VariableScope) -> Result<Vec<Secret>> {
+        Err(eyre!("CI secrets are not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn set_secret(&self, _: &VariableScope, _: &str, _: &str) -> Result<()> {
+        Err(eyre!("CI secrets are not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn delete_secret(&self, _: &VariableScope, _: &str) -> Result<()> {
+        Err(eyre!("CI secrets are not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_variables(&self, _: &VariableScope) -> Result<Vec<Variable>> {
+        Err(eyre!("CI variables are not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn set_variable(&self, _: &VariableScope, _: Variable) -> Result<()> {
+        Err(eyre!("CI variables are not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn delete_variable(&self, _: &VariableScope, _: &str) -> Result<()> {
+        Err(eyre!("CI variables are not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn create_release(&self, _: CreateRelease) -> Result<Release> {
+        Err(eyre!("Releases are not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn get_release(&self, _: &str) -> Result<Release> {
+        Err(eyre!("Releases are not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_releases(&self) -> Result<Vec<Release>> {
+        Err(eyre!("Releases are not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn delete_release(&self, _: &str) -> Result<()> {
+        Err(eyre!("Releases are not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn upload_release_asset(&self, _: &str, _: &str, _: &[u8]) -> Result<ReleaseAsset> {
+        Err(eyre!("Releases are not supported by Gogs."))
+    }
+
+    #[instrument(skip_all)]
+    fn download_release_asset(&self, _: &ReleaseAsset, _: &mut dyn Write) -> Result<u64> {
+        Err(eyre!("Releases are not supported by Gogs."))
+    }
+}
@@ -1,5 +1,8 @@
 pub mod bitbucket;
 pub mod common;
+pub mod gerrit;
 pub mod gitea;
 pub mod github;
 pub mod gitlab;
+pub mod gogs;
+pub mod sourcehut;
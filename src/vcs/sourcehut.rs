@@ -0,0 +1,771 @@
+// Documentation: https://man.sr.ht/git.sr.ht/graphql.md
+use super::common::{
+    ArchiveFormat, BranchProtection, BranchUpdate, Collaborator, CollaboratorPermission,
+    CreateDeployKey, CreateIssue, CreatePullRequest, CreateRelease, CreateRepository,
+    CreateWebhook, DeployKey, ForkRepository, ForkSync, Issue, IssueStateFilter,
+    ListPullRequestFilters, ListRepositoryFilters, PullRequest, PullRequestState, Release,
+    ReleaseAsset, Repository, RepositoryVisibility, RequestError, Secret, UpdatePullRequest,
+    UpdateRepository, User, Variable, VariableScope, VersionControl, VersionControlSettings,
+    Webhook,
+};
+use crate::git::git::LocalRepository;
+use eyre::{eyre, Context, ContextCompat, Result};
+use native_tls::TlsConnector;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+    fs::{read_to_string, write},
+    io::Write,
+    process::{Command, Stdio},
+    sync::Arc,
+};
+use time::OffsetDateTime;
+use tracing::{info, instrument, trace, warn};
+use ureq::{Agent, AgentBuilder, Error};
+
+const REPOSITORY_FIELDS: &str =
+    "id name description visibility created updated owner { canonicalName } HEAD { name }";
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SourceHutOwner {
+    #[serde(rename = "canonicalName")]
+    pub canonical_name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SourceHutReference {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+pub enum SourceHutVisibility {
+    #[serde(rename = "PUBLIC")]
+    Public,
+    #[serde(rename = "UNLISTED")]
+    Unlisted,
+    #[serde(rename = "PRIVATE")]
+    Private,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SourceHutRepository {
+    pub id: u32,
+    pub name: String,
+    pub description: Option<String>,
+    pub visibility: SourceHutVisibility,
+    #[serde(with = "time::serde::iso8601")]
+    pub created: OffsetDateTime,
+    #[serde(with = "time::serde::iso8601")]
+    pub updated: OffsetDateTime,
+    pub owner: SourceHutOwner,
+    #[serde(rename = "HEAD")]
+    pub head: Option<SourceHutReference>,
+}
+
+impl SourceHutRepository {
+    fn into_repository(self, hostname: &str) -> Repository {
+        let SourceHutRepository {
+            name,
+            description,
+            visibility,
+            created,
+            updated,
+            owner,
+            head,
+            ..
+        } = self;
+        let full_name = format!("{}/{}", owner.canonical_name, name);
+        Repository {
+            name,
+            owner: Some(User {
+                id: owner.canonical_name.clone(),
+                username: owner.canonical_name,
+            }),
+            html_url: format!("https://{hostname}/{full_name}"),
+            ssh_url: format!("git@{hostname}:{full_name}"),
+            https_url: format!("https://{hostname}/{full_name}"),
+            full_name,
+            description: description.unwrap_or_default(),
+            created_at: created,
+            updated_at: updated,
+            visibility: match visibility {
+                SourceHutVisibility::Public => RepositoryVisibility::Public,
+                SourceHutVisibility::Unlisted => RepositoryVisibility::Internal,
+                SourceHutVisibility::Private => RepositoryVisibility::Private,
+            },
+            archived: false,
+            default_branch: head
+                .map(|h| h.name.trim_start_matches("refs/heads/").to_string())
+                .unwrap_or_default(),
+            forks_count: 0,
+            stars_count: 0,
+            forked_from: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SourceHutUserRepository {
+    pub repository: Option<SourceHutRepository>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SourceHutRepositoryCursor {
+    pub results: Vec<SourceHutRepository>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SourceHutUserRepositories {
+    pub repositories: SourceHutRepositoryCursor,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SourceHutRepositoriesQuery {
+    pub user: Option<SourceHutUserRepositories>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SourceHutMeQuery {
+    pub me: SourceHutOwner,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SourceHutRepositoryQuery {
+    pub user: Option<SourceHutUserRepository>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SourceHutCreateRepository {
+    #[serde(rename = "createRepository")]
+    pub create_repository: SourceHutRepository,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SourceHutDeletedRepository {
+    pub id: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SourceHutDeleteRepository {
+    #[serde(rename = "deleteRepository")]
+    pub delete_repository: SourceHutDeletedRepository,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SourceHutGraphQLError {
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SourceHutGraphQLResponse<T> {
+    pub data: Option<T>,
+    pub errors: Option<Vec<SourceHutGraphQLError>>,
+}
+
+#[derive(Debug)]
+pub struct SourceHut {
+    settings: VersionControlSettings,
+    client: Agent,
+    hostname: String,
+    repo: String,
+}
+
+impl SourceHut {
+    #[instrument(skip_all)]
+    fn call<T: DeserializeOwned>(&self, query: &str, variables: Value) -> Result<T> {
+        let url = format!("https://{}/query", self.hostname);
+
+        info!("Calling GraphQL on {url}.");
+
+        let token = &self.settings.auth;
+
+        trace!("Authenticating with token '{token}'.");
+
+        let body = json!({ "query": query, "variables": variables });
+        trace!("Sending body: {}.", body);
+
+        let result = self
+            .client
+            .post(&url)
+            .set("Authorization", &format!("Bearer {token}"))
+            .set("Content-Type", "application/json")
+            .send_json(body);
+
+        match result {
+            Ok(result) => {
+                let status = result.status();
+                let t = result.into_string()?;
+
+                info!(
+                    "Received response with response code {} with body size {}.",
+                    status,
+                    t.len()
+                );
+                trace!("Response body: {t}.");
+
+                let response: SourceHutGraphQLResponse<T> = serde_json::from_str(&t)?;
+                match response {
+                    SourceHutGraphQLResponse {
+                        errors: Some(errors),
+                        ..
+                    } if !errors.is_empty() => Err(eyre!(
+                        "Request failed (response: {}).",
+                        errors
+                            .into_iter()
+                            .map(|e| e.message)
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    )),
+                    SourceHutGraphQLResponse { data: Some(t), .. } => Ok(t),
+                    _ => Err(eyre!("Request failed (response: {}).", t)),
+                }
+            }
+            Err(Error::Status(status, result)) => {
+                let t = result.into_string()?;
+
+                info!(
+                    "Received response with response code {} with body size {}.",
+                    status,
+                    t.len()
+                );
+                Err(RequestError { status, body: t }.into())
+            }
+            Err(Error::Transport(_)) => Err(eyre!("Sending data failed.")),
+        }
+    }
+
+    #[instrument(skip_all)]
+    fn get_repository_data(&self) -> Result<SourceHutRepository> {
+        let (owner, name) = self
+            .repo
+            .split_once('/')
+            .wrap_err(eyre!("Invalid repo format: {}.", self.repo))?;
+
+        let query: SourceHutRepositoryQuery = self.call(
+            &format!(
+                "query ($owner: String!, $name: String!) {{ user(username: $owner) {{ repository(name: $name) {{ {REPOSITORY_FIELDS} }} }} }}"
+            ),
+            json!({ "owner": owner.trim_start_matches('~'), "name": name }),
+        )?;
+
+        query
+            .user
+            .and_then(|u| u.repository)
+            .wrap_err(eyre!("Repository {} not found.", self.repo))
+    }
+
+    #[instrument(skip(self, variables))]
+    fn call_paginated(
+        &self,
+        query: &str,
+        mut variables: Value,
+        limit: usize,
+    ) -> Result<Vec<SourceHutRepository>> {
+        let mut collected_values: Vec<SourceHutRepository> = vec![];
+        let mut i = 1;
+        while collected_values.len() < limit {
+            info!("Reading page {}.", i);
+
+            let page: SourceHutRepositoriesQuery = self.call(query, variables.clone())?;
+            let mut page = page
+                .user
+                .map(|u| u.repositories)
+                .wrap_err(eyre!("User not found."))?;
+
+            collected_values.append(&mut page.results);
+
+            // The cursor is empty on the last page
+            match page.cursor {
+                Some(cursor) => variables["cursor"] = json!(cursor),
+                None => break,
+            }
+
+            i += 1;
+        }
+        collected_values.truncate(limit);
+
+        Ok(collected_values)
+    }
+
+    #[instrument(skip(self, patches))]
+    fn send_patches(&self, sendmail: &str, patches: &[String]) -> Result<()> {
+        for patch in patches {
+            info!("Sending patch {patch} with {sendmail}.");
+            let content = read_to_string(patch)?;
+
+            let mut command = Command::new("sh")
+                .args(["-c", sendmail])
+                .stdin(Stdio::piped())
+                .stdout(Stdio::inherit())
+                .stderr(Stdio::inherit())
+                .spawn()
+                .wrap_err(eyre!("Could not run sendmail command {sendmail}."))?;
+            command
+                .stdin
+                .take()
+                .wrap_err("Could not write to sendmail command.")?
+                .write_all(content.as_bytes())?;
+
+            if !command.wait()?.success() {
+                return Err(eyre!("Sending patch {patch} failed."));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl VersionControl for SourceHut {
+    #[instrument(skip_all)]
+    fn init(hostname: String, repo: String, settings: VersionControlSettings) -> Self {
+        let client = AgentBuilder::new()
+            .tls_connector(Arc::new(TlsConnector::new().unwrap()))
+            .build();
+        SourceHut {
+            settings,
+            client,
+            hostname,
+            repo,
+        }
+    }
+
+    #[instrument(skip_all)]
+    fn login_url(&self) -> String {
+        "https://meta.sr.ht/oauth2/personal-token".to_string()
+    }
+
+    #[instrument(skip_all)]
+    fn validate_token(&self, token: &str) -> Result<()> {
+        if token.is_empty() || token.contains(char::is_whitespace) {
+            Err(eyre!(
+                "Your SourceHut token cannot be empty or contain spaces."
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    #[instrument(skip_all)]
+    fn get_current_user(&self) -> Result<User> {
+        let SourceHutMeQuery { me } = self.call("query { me { canonicalName } }", json!({}))?;
+
+        Ok(User {
+            id: me.canonical_name.clone(),
+            username: me.canonical_name,
+        })
+    }
+
+    #[instrument(skip(self))]
+    fn requires_pushed_branch(&self) -> Result<bool> {
+        // Patchsets are generated from the local branch on create
+        Ok(false)
+    }
+
+    #[instrument(skip(self))]
+    fn tracks_pull_requests(&self) -> Result<bool> {
+        // Patchsets are reviewed and applied on the mailing list
+        Ok(false)
+    }
+
+    #[instrument(skip(self))]
+    fn create_pr(&self, pr: CreatePullRequest) -> Result<PullRequest> {
+        let CreatePullRequest {
+            title,
+            description,
+            source,
+            target,
+            labels,
+            ..
+        } = pr;
+        if !labels.is_empty() {
+            warn!("Labels are not supported by SourceHut, skipping them.");
+        }
+        let target = match target.or(self.settings.default_branch.clone()) {
+            Some(target) => target,
+            None => {
+                self.get_repository_data()?
+                    .into_repository(&self.hostname)
+                    .default_branch
+            }
+        };
+        info!("Using {target} as target branch.");
+
+        // Contributions are sent as a patchset with the description as the cover letter
+        let repository = LocalRepository::init(None)?;
+        let name = self.repo.rsplit('/').next().unwrap_or(&self.repo);
+        let output_dir = format!(
+            "{}/patches/{}",
+            repository.get_git_dir()?,
+            source.replace('/', "-")
+        );
+        let patches = repository.format_patch(
+            &source,
+            &target,
+            &output_dir,
+            &format!("PATCH {name}"),
+            self.settings.mailing_list.clone(),
+        )?;
+        let cover_letter = patches
+            .first()
+            .wrap_err(eyre!("There are no commits between {target} and {source}."))?;
+        let content = read_to_string(cover_letter)
+            .wrap_err(eyre!("Cannot read cover letter {cover_letter}."))?
+            .replace("*** SUBJECT HERE ***", &title)
+            .replace("*** BLURB HERE ***", &description);
+        write(cover_letter, content)
+            .wrap_err(eyre!("Cannot write cover letter {cover_letter}."))?;
+
+        if let Some(sendmail) = &self.settings.sendmail {
+            self.send_patches(sendmail, &patches)?;
+        } else {
+            info!("There is no sendmail command set, patches are written to {output_dir}.");
+        }
+
+        let now = OffsetDateTime::now_utc();
+        Ok(PullRequest {
+            // Patchsets don't get an identifier until they reach the mailing list
+            id: 0,
+            state: PullRequestState::Open,
+            title,
+            description,
+            source_sha: repository.get_branch_sha(Some(source.clone()))?,
+            target_sha: repository.get_branch_sha(Some(target.clone()))?,
+            source,
+            target,
+            url: cover_letter.to_string(),
+            created_at: now,
+            updated_at: now,
+            author: User::default(),
+            closed_by: None,
+            reviewers: None,
+            delete_source_branch: false,
+        })
+    }
+
+    #[instrument(skip(self))]
+    fn update_pr(&self, _: u32, _: UpdatePullRequest) -> Result<PullRequest> {
+        Err(eyre!(
+            "Updating pull requests is not supported by SourceHut."
+        ))
+    }
+
+    #[instrument(skip(self))]
+    fn get_pr_by_id(&self, _: u32) -> Result<PullRequest> {
+        Err(eyre!(
+            "Patches are reviewed on the mailing list, getting them is not supported by SourceHut."
+        ))
+    }
+
+    #[instrument(skip(self))]
+    fn get_pr_by_branch(&self, _: &str) -> Result<PullRequest> {
+        Err(eyre!(
+            "Patches are reviewed on the mailing list, getting them is not supported by SourceHut."
+        ))
+    }
+
+    #[instrument(skip(self))]
+    fn list_prs(&self, _: ListPullRequestFilters) -> Result<Vec<PullRequest>> {
+        Err(eyre!(
+            "Patches are reviewed on the mailing list, listing them is not supported by SourceHut."
+        ))
+    }
+
+    #[instrument(skip(self))]
+    fn approve_pr(&self, _: u32) -> Result<()> {
+        Err(eyre!(
+            "Patches are reviewed on the mailing list, approving is not supported by SourceHut."
+        ))
+    }
+
+    #[instrument(skip(self))]
+    fn close_pr(&self, _: u32) -> Result<PullRequest> {
+        Err(eyre!(
+            "Patches are reviewed on the mailing list, closing is not supported by SourceHut."
+        ))
+    }
+
+    #[instrument(skip(self))]
+    fn merge_pr(&self, _: u32, _: bool) -> Result<PullRequest> {
+        Err(eyre!(
+            "Patches are applied by the maintainers, merging is not supported by SourceHut."
+        ))
+    }
+
+    #[instrument(skip(self))]
+    fn update_pr_branch(&self, _: u32, _: &str, _: bool) -> Result<BranchUpdate> {
+        Err(eyre!(
+            "Updating pull requests is not supported by SourceHut."
+        ))
+    }
+
+    #[instrument(skip(self))]
+    fn create_issue(&self, _: CreateIssue) -> Result<Issue> {
+        Err(eyre!("Issues are not supported by SourceHut."))
+    }
+
+    #[instrument(skip(self))]
+    fn get_issue(&self, _: u32) -> Result<Issue> {
+        Err(eyre!("Issues are not supported by SourceHut."))
+    }
+
+    #[instrument(skip(self))]
+    fn list_issues(&self, _: IssueStateFilter) -> Result<Vec<Issue>> {
+        Err(eyre!("Issues are not supported by SourceHut."))
+    }
+
+    #[instrument(skip(self))]
+    fn close_issue(&self, _: u32) -> Result<Issue> {
+        Err(eyre!("Issues are not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn get_repository(&self) -> Result<Repository> {
+        let repo = self.get_repository_data()?;
+
+        Ok(repo.into_repository(&self.hostname))
+    }
+
+    #[instrument(skip(self))]
+    fn list_repositories(
+        &self,
+        owner: Option<&str>,
+        filters: ListRepositoryFilters,
+    ) -> Result<Vec<Repository>> {
+        let owner = match owner {
+            Some(owner) => owner.to_string(),
+            None => self.get_current_user()?.username,
+        };
+
+        let repos = self.call_paginated(
+            &format!(
+                "query ($owner: String!, $cursor: Cursor) {{ user(username: $owner) {{ repositories(cursor: $cursor) {{ results {{ {REPOSITORY_FIELDS} }} cursor }} }} }}"
+            ),
+            json!({ "owner": owner.trim_start_matches('~') }),
+            usize::MAX,
+        )?;
+
+        Ok(repos
+            .into_iter()
+            .map(|repo| repo.into_repository(&self.hostname))
+            .filter(|repo| filters.matches(repo))
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    fn search_repositories(&self, query: &str, limit: usize) -> Result<Vec<Repository>> {
+        // SourceHut can't search globally, only in the repositories of the user
+        let repos = self.call_paginated(
+            &format!(
+                "query ($search: String, $cursor: Cursor) {{ user: me {{ repositories(cursor: $cursor, filter: {{ search: $search }}) {{ results {{ {REPOSITORY_FIELDS} }} cursor }} }} }}"
+            ),
+            json!({ "search": query }),
+            limit,
+        )?;
+
+        Ok(repos
+            .into_iter()
+            .map(|repo| repo.into_repository(&self.hostname))
+            .collect())
+    }
+
+    #[instrument(skip_all)]
+    fn create_repository(&self, repo: CreateRepository) -> Result<Repository> {
+        let CreateRepository {
+            name,
+            organization,
+            description,
+            visibility,
+            ..
+        } = repo;
+        if organization.is_some() {
+            return Err(eyre!(
+                "Repositories can only be created for the user on SourceHut."
+            ));
+        }
+
+        let SourceHutCreateRepository { create_repository } = self.call(
+            &format!(
+                "mutation ($name: String!, $visibility: Visibility!, $description: String) {{ createRepository(name: $name, visibility: $visibility, description: $description) {{ {REPOSITORY_FIELDS} }} }}"
+            ),
+            json!({
+                "name": name,
+                "visibility": match visibility {
+                    RepositoryVisibility::Public => SourceHutVisibility::Public,
+                    RepositoryVisibility::Internal => SourceHutVisibility::Unlisted,
+                    RepositoryVisibility::Private => SourceHutVisibility::Private,
+                },
+                "description": description,
+            }),
+        )?;
+
+        Ok(create_repository.into_repository(&self.hostname))
+    }
+
+    #[instrument(skip_all)]
+    fn fork_repository(&self, _: ForkRepository) -> Result<Repository> {
+        Err(eyre!("Forking repositories is not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn update_repository(&self, _: UpdateRepository) -> Result<Repository> {
+        Err(eyre!(
+            "Updating repositories is not supported by SourceHut."
+        ))
+    }
+
+    #[instrument(skip_all)]
+    fn transfer_repository(&self, _: &str) -> Result<Repository> {
+        Err(eyre!(
+            "Transferring repositories is not supported by SourceHut."
+        ))
+    }
+
+    #[instrument(skip_all)]
+    fn delete_repository(&self) -> Result<()> {
+        let SourceHutRepository { id, .. } = self.get_repository_data()?;
+
+        let _: SourceHutDeleteRepository = self.call(
+            "mutation ($id: Int!) { deleteRepository(id: $id) { id } }",
+            json!({ "id": id }),
+        )?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    fn sync_fork(&self, _: &str) -> Result<ForkSync> {
+        Err(eyre!("Syncing forks is not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn download_archive(&self, _: &str, _: ArchiveFormat, _: &mut dyn Write) -> Result<u64> {
+        Err(eyre!("Downloading archives is not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_deploy_keys(&self) -> Result<Vec<DeployKey>> {
+        Err(eyre!("Deploy keys are not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn add_deploy_key(&self, _: CreateDeployKey) -> Result<DeployKey> {
+        Err(eyre!("Deploy keys are not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn remove_deploy_key(&self, _: &str) -> Result<()> {
+        Err(eyre!("Deploy keys are not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_branch_protections(&self) -> Result<Vec<BranchProtection>> {
+        Err(eyre!("Branch protections are not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn protect_branch(&self, _: BranchProtection) -> Result<BranchProtection> {
+        Err(eyre!("Branch protections are not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_collaborators(&self) -> Result<Vec<Collaborator>> {
+        Err(eyre!(
+            "Managing collaborators is not supported by SourceHut."
+        ))
+    }
+
+    #[instrument(skip_all)]
+    fn add_collaborator(&self, _: &str, _: CollaboratorPermission) -> Result<()> {
+        Err(eyre!(
+            "Managing collaborators is not supported by SourceHut."
+        ))
+    }
+
+    #[instrument(skip_all)]
+    fn remove_collaborator(&self, _: &str) -> Result<()> {
+        Err(eyre!(
+            "Managing collaborators is not supported by SourceHut."
+        ))
+    }
+
+    #[instrument(skip_all)]
+    fn list_webhooks(&self) -> Result<Vec<Webhook>> {
+        Err(eyre!("Managing webhooks is not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn create_webhook(&self, _: CreateWebhook) -> Result<Webhook> {
+        Err(eyre!("Managing webhooks is not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn delete_webhook(&self, _: &str) -> Result<()> {
+        Err(eyre!("Managing webhooks is not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn test_webhook(&self, _: &str) -> Result<()> {
+        Err(eyre!("Managing webhooks is not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_secrets(&self, _: &VariableScope) -> Result<Vec<Secret>> {
+        Err(eyre!("CI secrets are not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn set_secret(&self, _: &VariableScope, _: &str, _: &str) -> Result<()> {
+        Err(eyre!("CI secrets are not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn delete_secret(&self, _: &VariableScope, _: &str) -> Result<()> {
+        Err(eyre!("CI secrets are not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_variables(&self, _: &VariableScope) -> Result<Vec<Variable>> {
+        Err(eyre!("CI variables are not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn set_variable(&self, _: &VariableScope, _: Variable) -> Result<()> {
+        Err(eyre!("CI variables are not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn delete_variable(&self, _: &VariableScope, _: &str) -> Result<()> {
+        Err(eyre!("CI variables are not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn create_release(&self, _: CreateRelease) -> Result<Release> {
+        Err(eyre!("Releases are not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn get_release(&self, _: &str) -> Result<Release> {
+        Err(eyre!("Releases are not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn list_releases(&self) -> Result<Vec<Release>> {
+        Err(eyre!("Releases are not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn delete_release(&self, _: &str) -> Result<()> {
+        Err(eyre!("Releases are not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn upload_release_asset(&self, _: &str, _: &str, _: &[u8]) -> Result<ReleaseAsset> {
+        Err(eyre!("Releases are not supported by SourceHut."))
+    }
+
+    #[instrument(skip_all)]
+    fn download_release_asset(&self, _: &ReleaseAsset, _: &mut dyn Write) -> Result<u64> {
+        Err(eyre!("Releases are not supported by SourceHut."))
+    }
+}